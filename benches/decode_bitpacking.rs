@@ -13,6 +13,25 @@ fn add_benchmark(c: &mut Criterion) {
         c.bench_function(&format!("bitpacking 2^{}", log2_size), |b| {
             b.iter(|| Decoder::<u32>::try_new(&bytes, 1, size).unwrap().count())
         });
+
+        // both the iterator and `decode_into` write to the same buffer
+        let mut out = vec![0u32; size];
+        c.bench_function(&format!("bitpacking iter 2^{}", log2_size), |b| {
+            b.iter(|| {
+                let decoder = Decoder::<u32>::try_new(&bytes, 1, size).unwrap();
+                for (slot, value) in out.iter_mut().zip(decoder) {
+                    *slot = value;
+                }
+            })
+        });
+
+        c.bench_function(&format!("bitpacking decode_into 2^{}", log2_size), |b| {
+            b.iter(|| {
+                Decoder::<u32>::try_new(&bytes, 1, size)
+                    .unwrap()
+                    .decode_into(&mut out)
+            })
+        });
     })
 }
 
@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 
-use parquet2::encoding::hybrid_rle::{encode_u32, Decoder};
+use parquet2::encoding::hybrid_rle::{encode_u32, Decoder, HybridRleDecoder};
 
 fn add_benchmark(c: &mut Criterion) {
     (10..=20).step_by(2).for_each(|log2_size| {
@@ -12,6 +12,26 @@ fn add_benchmark(c: &mut Criterion) {
         c.bench_function(&format!("rle decode 2^{}", log2_size), |b| {
             b.iter(|| Decoder::new(&bytes, 1).count())
         });
+
+        // both the iterator and `decode_into` write to the same buffer
+        let mut out = vec![0u32; size];
+        c.bench_function(&format!("hybrid rle iter 2^{}", log2_size), |b| {
+            b.iter(|| {
+                let decoder = HybridRleDecoder::try_new(&bytes, 8, size).unwrap();
+                for (slot, value) in out.iter_mut().zip(decoder) {
+                    *slot = value.unwrap();
+                }
+            })
+        });
+
+        c.bench_function(&format!("hybrid rle decode_into 2^{}", log2_size), |b| {
+            b.iter(|| {
+                HybridRleDecoder::try_new(&bytes, 8, size)
+                    .unwrap()
+                    .decode_into(&mut out)
+                    .unwrap()
+            })
+        });
     })
 }
 
@@ -0,0 +1,496 @@
+//! The built-in [`Codec`]s.
+use crate::error::{Error, Result};
+
+use super::{BrotliLevel, Codec, GzipLevel, ZstdLevel};
+
+#[cfg(any(feature = "snappy", feature = "lz4", feature = "lz4_flex"))]
+fn inner_compress<G: Fn(usize) -> Result<usize>, F: Fn(&[u8], &mut [u8]) -> Result<usize>>(
+    input: &[u8],
+    output: &mut Vec<u8>,
+    get_length: G,
+    compress: F,
+) -> Result<()> {
+    let original_length = output.len();
+    let max_required_length = get_length(input.len())?;
+
+    output.resize(original_length + max_required_length, 0);
+    let compressed_size = compress(input, &mut output[original_length..])?;
+
+    output.truncate(original_length + compressed_size);
+    Ok(())
+}
+
+/// The [`Codec`] of [`super::Compression::Snappy`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SnappyCodec;
+
+#[cfg(feature = "snappy")]
+impl Codec for SnappyCodec {
+    fn compress(&self, input: &[u8], output: &mut Vec<u8>) -> Result<()> {
+        inner_compress(
+            input,
+            output,
+            |len| self.max_compressed_len(len),
+            |input, output| Ok(snap::raw::Encoder::new().compress(input, output)?),
+        )
+    }
+
+    fn decompress(&self, input: &[u8], output: &mut [u8]) -> Result<()> {
+        use snap::raw::{decompress_len, Decoder};
+
+        let len = decompress_len(input)?;
+        if len > output.len() {
+            return Err(Error::OutOfSpec(String::from("snappy header out of spec")));
+        }
+        Decoder::new()
+            .decompress(input, output)
+            .map_err(|e| e.into())
+            .map(|_| ())
+    }
+
+    fn max_compressed_len(&self, input_length: usize) -> Result<usize> {
+        Ok(snap::raw::max_compress_len(input_length))
+    }
+}
+
+#[cfg(not(feature = "snappy"))]
+impl Codec for SnappyCodec {
+    fn compress(&self, _: &[u8], _: &mut Vec<u8>) -> Result<()> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Snappy,
+            "compress to snappy".to_string(),
+        ))
+    }
+
+    fn decompress(&self, _: &[u8], _: &mut [u8]) -> Result<()> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Snappy,
+            "decompress with snappy".to_string(),
+        ))
+    }
+
+    fn max_compressed_len(&self, _: usize) -> Result<usize> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Snappy,
+            "compress to snappy".to_string(),
+        ))
+    }
+}
+
+/// The [`Codec`] of [`super::Compression::Gzip`], compressing with a given level
+/// (or the default level if `None`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GzipCodec(pub Option<GzipLevel>);
+
+#[cfg(feature = "gzip")]
+impl Codec for GzipCodec {
+    fn compress(&self, input: &[u8], output: &mut Vec<u8>) -> Result<()> {
+        use std::io::Write;
+        let level = self.0.unwrap_or_default();
+        let mut encoder = flate2::write::GzEncoder::new(output, level.into());
+        encoder.write_all(input)?;
+        encoder.try_finish().map_err(|e| e.into())
+    }
+
+    fn decompress(&self, input: &[u8], output: &mut [u8]) -> Result<()> {
+        use std::io::Read;
+        let mut decoder = flate2::read::GzDecoder::new(input);
+        decoder.read_exact(output).map_err(|e| e.into())
+    }
+
+    fn max_compressed_len(&self, input_length: usize) -> Result<usize> {
+        // zlib's `deflateBound` plus the gzip header (10 bytes) and trailer (8 bytes)
+        Ok(input_length
+            + (input_length >> 12)
+            + (input_length >> 14)
+            + (input_length >> 25)
+            + 13
+            + 18)
+    }
+}
+
+#[cfg(not(feature = "gzip"))]
+impl Codec for GzipCodec {
+    fn compress(&self, _: &[u8], _: &mut Vec<u8>) -> Result<()> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Gzip,
+            "compress to gzip".to_string(),
+        ))
+    }
+
+    fn decompress(&self, _: &[u8], _: &mut [u8]) -> Result<()> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Gzip,
+            "decompress with gzip".to_string(),
+        ))
+    }
+
+    fn max_compressed_len(&self, _: usize) -> Result<usize> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Gzip,
+            "compress to gzip".to_string(),
+        ))
+    }
+}
+
+/// The [`Codec`] of [`super::Compression::Brotli`], compressing with a given level
+/// (or the default level if `None`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BrotliCodec(pub Option<BrotliLevel>);
+
+#[cfg(feature = "brotli")]
+const BROTLI_DEFAULT_BUFFER_SIZE: usize = 4096;
+
+#[cfg(feature = "brotli")]
+impl Codec for BrotliCodec {
+    fn compress(&self, input: &[u8], output: &mut Vec<u8>) -> Result<()> {
+        use std::io::Write;
+        const BROTLI_DEFAULT_LG_WINDOW_SIZE: u32 = 22; // recommended between 20-22
+
+        let q = self.0.unwrap_or_default();
+        let mut encoder = brotli::CompressorWriter::new(
+            output,
+            BROTLI_DEFAULT_BUFFER_SIZE,
+            q.compression_level(),
+            BROTLI_DEFAULT_LG_WINDOW_SIZE,
+        );
+        encoder.write_all(input)?;
+        encoder.flush().map_err(|e| e.into())
+    }
+
+    fn decompress(&self, input: &[u8], output: &mut [u8]) -> Result<()> {
+        use std::io::Read;
+        brotli::Decompressor::new(input, BROTLI_DEFAULT_BUFFER_SIZE)
+            .read_exact(output)
+            .map_err(|e| e.into())
+    }
+
+    fn max_compressed_len(&self, input_length: usize) -> Result<usize> {
+        Ok(brotli::enc::BrotliEncoderMaxCompressedSize(input_length))
+    }
+}
+
+#[cfg(not(feature = "brotli"))]
+impl Codec for BrotliCodec {
+    fn compress(&self, _: &[u8], _: &mut Vec<u8>) -> Result<()> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Brotli,
+            "compress to brotli".to_string(),
+        ))
+    }
+
+    fn decompress(&self, _: &[u8], _: &mut [u8]) -> Result<()> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Brotli,
+            "decompress with brotli".to_string(),
+        ))
+    }
+
+    fn max_compressed_len(&self, _: usize) -> Result<usize> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Brotli,
+            "compress to brotli".to_string(),
+        ))
+    }
+}
+
+/// The [`Codec`] of [`super::Compression::Zstd`], compressing with a given level
+/// (or the default level if `None`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ZstdCodec(pub Option<ZstdLevel>);
+
+#[cfg(feature = "zstd")]
+impl Codec for ZstdCodec {
+    fn compress(&self, input: &[u8], output: &mut Vec<u8>) -> Result<()> {
+        use std::io::Write;
+        let level = self.0.map(|v| v.compression_level()).unwrap_or_default();
+
+        let mut encoder = zstd::Encoder::new(output, level)?;
+        encoder.write_all(input)?;
+        match encoder.finish() {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn decompress(&self, input: &[u8], output: &mut [u8]) -> Result<()> {
+        use std::io::Read;
+        let mut decoder = zstd::Decoder::new(input)?;
+        decoder.read_exact(output).map_err(|e| e.into())
+    }
+
+    fn max_compressed_len(&self, input_length: usize) -> Result<usize> {
+        Ok(zstd::zstd_safe::compress_bound(input_length))
+    }
+}
+
+#[cfg(not(feature = "zstd"))]
+impl Codec for ZstdCodec {
+    fn compress(&self, _: &[u8], _: &mut Vec<u8>) -> Result<()> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Zstd,
+            "compress to zstd".to_string(),
+        ))
+    }
+
+    fn decompress(&self, _: &[u8], _: &mut [u8]) -> Result<()> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Zstd,
+            "decompress with zstd".to_string(),
+        ))
+    }
+
+    fn max_compressed_len(&self, _: usize) -> Result<usize> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Zstd,
+            "compress to zstd".to_string(),
+        ))
+    }
+}
+
+/// The [`Codec`] of [`super::Compression::Lz4Raw`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Lz4RawCodec;
+
+#[cfg(feature = "lz4")]
+impl Codec for Lz4RawCodec {
+    fn compress(&self, input: &[u8], output: &mut Vec<u8>) -> Result<()> {
+        inner_compress(
+            input,
+            output,
+            |len| self.max_compressed_len(len),
+            |input, output| {
+                let compressed_size = lz4::block::compress_to_buffer(input, None, false, output)?;
+                Ok(compressed_size)
+            },
+        )
+    }
+
+    fn decompress(&self, input: &[u8], output: &mut [u8]) -> Result<()> {
+        lz4::block::decompress_to_buffer(input, Some(output.len() as i32), output)
+            .map(|_| {})
+            .map_err(|e| e.into())
+    }
+
+    fn max_compressed_len(&self, input_length: usize) -> Result<usize> {
+        Ok(lz4::block::compress_bound(input_length)?)
+    }
+}
+
+#[cfg(all(feature = "lz4_flex", not(feature = "lz4")))]
+impl Codec for Lz4RawCodec {
+    fn compress(&self, input: &[u8], output: &mut Vec<u8>) -> Result<()> {
+        inner_compress(
+            input,
+            output,
+            |len| self.max_compressed_len(len),
+            |input, output| {
+                let compressed_size = lz4_flex::block::compress_into(input, output)?;
+                Ok(compressed_size)
+            },
+        )
+    }
+
+    fn decompress(&self, input: &[u8], output: &mut [u8]) -> Result<()> {
+        lz4_flex::block::decompress_into(input, output)
+            .map(|_| {})
+            .map_err(|e| e.into())
+    }
+
+    fn max_compressed_len(&self, input_length: usize) -> Result<usize> {
+        Ok(lz4_flex::block::get_maximum_output_size(input_length))
+    }
+}
+
+#[cfg(all(not(feature = "lz4"), not(feature = "lz4_flex")))]
+impl Codec for Lz4RawCodec {
+    fn compress(&self, _: &[u8], _: &mut Vec<u8>) -> Result<()> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Lz4,
+            "compress to lz4".to_string(),
+        ))
+    }
+
+    fn decompress(&self, _: &[u8], _: &mut [u8]) -> Result<()> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Lz4,
+            "decompress with lz4".to_string(),
+        ))
+    }
+
+    fn max_compressed_len(&self, _: usize) -> Result<usize> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Lz4,
+            "compress to lz4".to_string(),
+        ))
+    }
+}
+
+/// The [`Codec`] of the deprecated [`super::Compression::Lz4`], whose pages may be framed
+/// as by Hadoop's `Lz4Codec`. Only decompression is supported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Lz4Codec;
+
+impl Codec for Lz4Codec {
+    fn compress(&self, _: &[u8], _: &mut Vec<u8>) -> Result<()> {
+        Err(Error::FeatureNotSupported(
+            "Compression Lz4 is not supported".to_string(),
+        ))
+    }
+
+    #[cfg(any(feature = "lz4_flex", feature = "lz4"))]
+    fn decompress(&self, input: &[u8], output: &mut [u8]) -> Result<()> {
+        try_decompress_hadoop(input, output).or_else(|_| {
+            lz4_decompress_to_buffer(input, Some(output.len() as i32), output).map(|_| {})
+        })
+    }
+
+    #[cfg(all(not(feature = "lz4_flex"), not(feature = "lz4")))]
+    fn decompress(&self, _: &[u8], _: &mut [u8]) -> Result<()> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Lz4,
+            "decompress with legacy lz4".to_string(),
+        ))
+    }
+
+    fn max_compressed_len(&self, _: usize) -> Result<usize> {
+        Err(Error::FeatureNotSupported(
+            "Compression Lz4 is not supported".to_string(),
+        ))
+    }
+}
+
+/// The [`Codec`] of [`super::Compression::Lzo`]. Pages are compressed with the framing of
+/// Hadoop's `LzoCodec` and decompressed with or without it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LzoCodec;
+
+#[cfg(feature = "lzo")]
+impl Codec for LzoCodec {
+    fn compress(&self, input: &[u8], output: &mut Vec<u8>) -> Result<()> {
+        super::lzo::compress_hadoop(input, output);
+        Ok(())
+    }
+
+    fn decompress(&self, input: &[u8], output: &mut [u8]) -> Result<()> {
+        super::lzo::decompress_hadoop(input, output).or_else(|_| {
+            let written = super::lzo::decompress_raw(input, output)?;
+            if written != output.len() {
+                return Err(Error::oos("unexpected decompressed size"));
+            }
+            Ok(())
+        })
+    }
+
+    fn max_compressed_len(&self, input_length: usize) -> Result<usize> {
+        Ok(super::lzo::max_hadoop_compressed_len(input_length))
+    }
+}
+
+#[cfg(not(feature = "lzo"))]
+impl Codec for LzoCodec {
+    fn compress(&self, _: &[u8], _: &mut Vec<u8>) -> Result<()> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Lzo,
+            "compress to lzo".to_string(),
+        ))
+    }
+
+    fn decompress(&self, _: &[u8], _: &mut [u8]) -> Result<()> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Lzo,
+            "decompress with lzo".to_string(),
+        ))
+    }
+
+    fn max_compressed_len(&self, _: usize) -> Result<usize> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Lzo,
+            "compress to lzo".to_string(),
+        ))
+    }
+}
+
+/// Try to decompress the buffer as if it was compressed with the Hadoop Lz4Codec.
+/// Translated from the apache arrow c++ function [TryDecompressHadoop](https://github.com/apache/arrow/blob/bf18e6e4b5bb6180706b1ba0d597a65a4ce5ca48/cpp/src/arrow/util/compression_lz4.cc#L474).
+/// Returns error if decompression failed.
+#[cfg(any(feature = "lz4", feature = "lz4_flex"))]
+fn try_decompress_hadoop(input_buf: &[u8], output_buf: &mut [u8]) -> Result<()> {
+    // Parquet files written with the Hadoop Lz4Codec use their own framing.
+    // The input buffer can contain an arbitrary number of "frames", each
+    // with the following structure:
+    // - bytes 0..3: big-endian uint32_t representing the frame decompressed size
+    // - bytes 4..7: big-endian uint32_t representing the frame compressed size
+    // - bytes 8...: frame compressed data
+    //
+    // The Hadoop Lz4Codec source code can be found here:
+    // https://github.com/apache/hadoop/blob/trunk/hadoop-mapreduce-project/hadoop-mapreduce-client/hadoop-mapreduce-client-nativetask/src/main/native/src/codec/Lz4Codec.cc
+
+    const SIZE_U32: usize = std::mem::size_of::<u32>();
+    const PREFIX_LEN: usize = SIZE_U32 * 2;
+    let mut input_len = input_buf.len();
+    let mut input = input_buf;
+    let mut output_len = output_buf.len();
+    let mut output: &mut [u8] = output_buf;
+    while input_len >= PREFIX_LEN {
+        let mut bytes = [0; SIZE_U32];
+        bytes.copy_from_slice(&input[0..4]);
+        let expected_decompressed_size = u32::from_be_bytes(bytes);
+        let mut bytes = [0; SIZE_U32];
+        bytes.copy_from_slice(&input[4..8]);
+        let expected_compressed_size = u32::from_be_bytes(bytes);
+        input = &input[PREFIX_LEN..];
+        input_len -= PREFIX_LEN;
+
+        if input_len < expected_compressed_size as usize {
+            return Err(Error::oos("Not enough bytes for Hadoop frame"));
+        }
+
+        if output_len < expected_decompressed_size as usize {
+            return Err(Error::oos("Not enough bytes to hold advertised output"));
+        }
+        let decompressed_size = lz4_decompress_to_buffer(
+            &input[..expected_compressed_size as usize],
+            Some(output_len as i32),
+            output,
+        )?;
+        if decompressed_size != expected_decompressed_size as usize {
+            return Err(Error::oos("unexpected decompressed size"));
+        }
+        input_len -= expected_compressed_size as usize;
+        output_len -= expected_decompressed_size as usize;
+        if input_len > expected_compressed_size as usize {
+            input = &input[expected_compressed_size as usize..];
+            output = &mut output[expected_decompressed_size as usize..];
+        } else {
+            break;
+        }
+    }
+    if input_len == 0 {
+        Ok(())
+    } else {
+        Err(Error::oos("Not all input are consumed"))
+    }
+}
+
+#[cfg(all(feature = "lz4", not(feature = "lz4_flex")))]
+#[inline]
+fn lz4_decompress_to_buffer(
+    src: &[u8],
+    uncompressed_size: Option<i32>,
+    buffer: &mut [u8],
+) -> Result<usize> {
+    let size = lz4::block::decompress_to_buffer(src, uncompressed_size, buffer)?;
+    Ok(size)
+}
+
+#[cfg(all(feature = "lz4_flex", not(feature = "lz4")))]
+#[inline]
+fn lz4_decompress_to_buffer(
+    src: &[u8],
+    _uncompressed_size: Option<i32>,
+    buffer: &mut [u8],
+) -> Result<usize> {
+    let size = lz4_flex::block::decompress_into(src, buffer)?;
+    Ok(size)
+}
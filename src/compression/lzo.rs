@@ -0,0 +1,439 @@
+//! A pure-Rust implementation of the [LZO1X](http://www.oberhumer.com/opensource/lzo/)
+//! compression format, both raw and within the block framing of Hadoop's `LzoCodec`
+//! (used by parquet-mr).
+//!
+//! The decompressor accepts any valid LZO1X stream. The compressor is a greedy, hash-based
+//! LZ77 matcher whose output can be decompressed by `liblzo2`'s `lzo1x_decompress`.
+use crate::error::{Error, Result};
+
+/// The minimum length of a match emitted by the compressor
+const MIN_MATCH: usize = 4;
+/// The maximum distance of a match representable in LZO1X
+const MAX_DISTANCE: usize = 0xbfff;
+const HASH_BITS: u32 = 14;
+/// The uncompressed size of the blocks written by Hadoop's `LzoCodec` (256 KiB)
+const HADOOP_BLOCK_SIZE: usize = 256 * 1024;
+/// The end-of-stream marker: an M4 match with distance 16384
+const END_OF_STREAM: [u8; 3] = [0x11, 0x00, 0x00];
+
+/// Returns an upper bound of the length of `length` bytes compressed with [`compress_raw`].
+#[inline]
+pub fn max_compressed_len(length: usize) -> usize {
+    length + length / 16 + 64 + 3
+}
+
+/// Returns an upper bound of the length of `length` bytes compressed with [`compress_hadoop`].
+#[inline]
+pub fn max_hadoop_compressed_len(length: usize) -> usize {
+    let blocks = length.div_ceil(HADOOP_BLOCK_SIZE);
+    blocks * (8 + max_compressed_len(HADOOP_BLOCK_SIZE.min(length)))
+}
+
+#[inline]
+fn hash(bytes: &[u8]) -> usize {
+    let key = u32::from_le_bytes(bytes[..4].try_into().unwrap());
+    (key.wrapping_mul(2654435761) >> (32 - HASH_BITS)) as usize
+}
+
+/// Pushes the extension of a length that did not fit in its instruction.
+#[inline]
+fn push_length(output: &mut Vec<u8>, mut length: usize) {
+    while length > 255 {
+        output.push(0);
+        length -= 255;
+    }
+    output.push(length as u8);
+}
+
+/// Emits a run of literals. `state` is the position of the byte of the previous match holding
+/// the number of trailing literals, or `None` if nothing was emitted yet.
+fn push_literals(output: &mut Vec<u8>, literals: &[u8], state: Option<usize>) {
+    let length = literals.len();
+    match (length, state) {
+        (0, _) => return,
+        (1..=238, None) => output.push(17 + length as u8),
+        (1..=3, Some(position)) => output[position] |= length as u8,
+        (4..=18, _) => output.push(length as u8 - 3),
+        _ => {
+            output.push(0);
+            push_length(output, length - 18);
+        }
+    }
+    output.extend_from_slice(literals);
+}
+
+/// Emits a match, returning the position of the byte holding its number of trailing literals.
+fn push_match(output: &mut Vec<u8>, length: usize, distance: usize) -> usize {
+    if length <= 8 && distance <= 2048 {
+        let d = distance - 1;
+        let op = if length <= 4 {
+            64 | ((length - 3) << 5)
+        } else {
+            128 | ((length - 5) << 5)
+        };
+        output.push((op | ((d & 7) << 2)) as u8);
+        output.push((d >> 3) as u8);
+        output.len() - 2
+    } else {
+        let (d, max_length) = if distance <= 16384 {
+            (distance - 1, 31)
+        } else {
+            (distance - 16384, 7)
+        };
+        let op = if distance <= 16384 {
+            32
+        } else {
+            16 | ((d >> 11) & 8)
+        };
+        if length - 2 <= max_length {
+            output.push((op | (length - 2)) as u8);
+        } else {
+            output.push(op as u8);
+            push_length(output, length - 2 - max_length);
+        }
+        let d = d & 0x3fff;
+        output.push((d << 2) as u8);
+        output.push((d >> 6) as u8);
+        output.len() - 2
+    }
+}
+
+/// Compresses `input` into a raw LZO1X stream appended to `output`.
+pub fn compress_raw(input: &[u8], output: &mut Vec<u8>) {
+    let mut table = vec![0usize; 1 << HASH_BITS];
+    let mut state = None;
+    let mut anchor = 0;
+    let mut i = 0;
+    while i + MIN_MATCH <= input.len() {
+        let h = hash(&input[i..]);
+        // positions are stored + 1 so that 0 means empty
+        let candidate = std::mem::replace(&mut table[h], i + 1);
+        if candidate > 0 {
+            let candidate = candidate - 1;
+            let distance = i - candidate;
+            if distance <= MAX_DISTANCE && input[candidate..candidate + 4] == input[i..i + 4] {
+                let length = 4 + input[i + 4..]
+                    .iter()
+                    .zip(&input[candidate + 4..])
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                push_literals(output, &input[anchor..i], state);
+                state = Some(push_match(output, length, distance));
+                i += length;
+                anchor = i;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    push_literals(output, &input[anchor..], state);
+    output.extend_from_slice(&END_OF_STREAM);
+}
+
+/// Compresses `input` with the block framing of Hadoop's `LzoCodec`, appending it to `output`.
+/// Every block of up to 256 KiB is written as its big-endian uncompressed length,
+/// followed by a single chunk: its big-endian compressed length and a raw LZO1X stream.
+pub fn compress_hadoop(input: &[u8], output: &mut Vec<u8>) {
+    for block in input.chunks(HADOOP_BLOCK_SIZE) {
+        output.reserve(8 + max_compressed_len(block.len()));
+        output.extend_from_slice(&(block.len() as u32).to_be_bytes());
+        let start = output.len();
+        output.extend_from_slice(&[0; 4]);
+        compress_raw(block, output);
+        let compressed_length = (output.len() - start - 4) as u32;
+        output[start..start + 4].copy_from_slice(&compressed_length.to_be_bytes());
+    }
+}
+
+struct Reader<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    #[inline]
+    fn byte(&mut self) -> Result<usize> {
+        let byte = *self
+            .input
+            .get(self.position)
+            .ok_or_else(|| Error::oos("LZO stream ended unexpectedly"))?;
+        self.position += 1;
+        Ok(byte as usize)
+    }
+
+    #[inline]
+    fn le16(&mut self) -> Result<usize> {
+        Ok(self.byte()? | (self.byte()? << 8))
+    }
+
+    /// Reads the extension of a length whose value in the instruction was 0
+    #[inline]
+    fn length(&mut self, base: usize) -> Result<usize> {
+        let mut length = base;
+        loop {
+            match self.byte()? {
+                0 => length += 255,
+                byte => return Ok(length + byte),
+            }
+        }
+    }
+
+    #[inline]
+    fn slice(&mut self, length: usize) -> Result<&'a [u8]> {
+        let slice = self
+            .input
+            .get(self.position..self.position + length)
+            .ok_or_else(|| Error::oos("LZO stream ended unexpectedly"))?;
+        self.position += length;
+        Ok(slice)
+    }
+}
+
+struct Writer<'a> {
+    output: &'a mut [u8],
+    position: usize,
+}
+
+impl<'a> Writer<'a> {
+    #[inline]
+    fn literals(&mut self, literals: &[u8]) -> Result<()> {
+        self.output
+            .get_mut(self.position..self.position + literals.len())
+            .ok_or_else(|| Error::oos("LZO stream decompresses to more bytes than expected"))?
+            .copy_from_slice(literals);
+        self.position += literals.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn copy_match(&mut self, length: usize, distance: usize) -> Result<()> {
+        if distance > self.position {
+            return Err(Error::oos("LZO match references data before the stream"));
+        }
+        if self.position + length > self.output.len() {
+            return Err(Error::oos(
+                "LZO stream decompresses to more bytes than expected",
+            ));
+        }
+        // matches may overlap with the bytes they write
+        for i in self.position..self.position + length {
+            self.output[i] = self.output[i - distance];
+        }
+        self.position += length;
+        Ok(())
+    }
+}
+
+/// Decompresses the raw LZO1X stream `input` into `output`, returning the number of bytes
+/// written.
+/// # Errors
+/// Errors iff `input` is not a valid LZO1X stream, is not fully consumed by it, or
+/// decompresses to more bytes than `output` can hold.
+pub fn decompress_raw(input: &[u8], output: &mut [u8]) -> Result<usize> {
+    let mut reader = Reader { input, position: 0 };
+    let mut writer = Writer {
+        output,
+        position: 0,
+    };
+
+    // the number of literals after the last instruction, 4 meaning 4 or more
+    let mut state = 0;
+    if let Some(first @ 18..) = input.first() {
+        reader.position += 1;
+        let length = *first as usize - 17;
+        writer.literals(reader.slice(length)?)?;
+        state = length.min(4);
+    }
+
+    loop {
+        let op = reader.byte()?;
+        let (length, distance, trailing) = match op {
+            0..=15 if state == 0 => {
+                let length = if op == 0 { reader.length(18)? } else { op + 3 };
+                writer.literals(reader.slice(length)?)?;
+                state = 4;
+                continue;
+            }
+            0..=15 => {
+                let distance = (reader.byte()? << 2) + ((op >> 2) & 3) + 1;
+                if state < 4 {
+                    (2, distance, op & 3)
+                } else {
+                    (3, distance + 2048, op & 3)
+                }
+            }
+            16..=31 => {
+                let length = match op & 7 {
+                    0 => reader.length(2 + 7)?,
+                    l => 2 + l,
+                };
+                let value = reader.le16()?;
+                let distance = 16384 + ((op & 8) << 11) + (value >> 2);
+                if distance == 16384 {
+                    break;
+                }
+                (length, distance, value & 3)
+            }
+            32..=63 => {
+                let length = match op & 31 {
+                    0 => reader.length(2 + 31)?,
+                    l => 2 + l,
+                };
+                let value = reader.le16()?;
+                (length, (value >> 2) + 1, value & 3)
+            }
+            _ => {
+                let length = if op < 128 {
+                    3 + ((op >> 5) & 1)
+                } else {
+                    5 + ((op >> 5) & 3)
+                };
+                let distance = (reader.byte()? << 3) + ((op >> 2) & 7) + 1;
+                (length, distance, op & 3)
+            }
+        };
+        writer.copy_match(length, distance)?;
+        writer.literals(reader.slice(trailing)?)?;
+        state = trailing;
+    }
+
+    if reader.position != input.len() {
+        return Err(Error::oos("LZO stream has trailing bytes"));
+    }
+    Ok(writer.position)
+}
+
+#[inline]
+fn read_be_u32(input: &[u8]) -> Result<(usize, &[u8])> {
+    if input.len() < 4 {
+        return Err(Error::oos("Not enough bytes for Hadoop frame"));
+    }
+    let (length, remaining) = input.split_at(4);
+    Ok((
+        u32::from_be_bytes(length.try_into().unwrap()) as usize,
+        remaining,
+    ))
+}
+
+/// Decompresses `input`, framed by Hadoop's `LzoCodec`, into `output`.
+/// # Errors
+/// Errors iff `input` is not validly framed or does not decompress to exactly `output.len()` bytes.
+pub fn decompress_hadoop(mut input: &[u8], output: &mut [u8]) -> Result<()> {
+    let mut position = 0;
+    while !input.is_empty() {
+        let (block_length, remaining) = read_be_u32(input)?;
+        input = remaining;
+        let block_end = position + block_length;
+        if block_end > output.len() {
+            return Err(Error::oos("Not enough bytes to hold advertised output"));
+        }
+        while position < block_end {
+            let (chunk_length, remaining) = read_be_u32(input)?;
+            if remaining.len() < chunk_length {
+                return Err(Error::oos("Not enough bytes for Hadoop frame"));
+            }
+            let (chunk, remaining) = remaining.split_at(chunk_length);
+            input = remaining;
+            position += decompress_raw(chunk, &mut output[position..block_end])?;
+        }
+    }
+    if position != output.len() {
+        return Err(Error::oos("unexpected decompressed size"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "abc" as 3 initial literals followed by a match of length 9 at distance 3
+    const RAW: [u8; 10] = [20, b'a', b'b', b'c', 32 | 7, 2 << 2, 0, 0x11, 0, 0];
+
+    fn roundtrip(data: &[u8]) {
+        let mut compressed = vec![];
+        compress_raw(data, &mut compressed);
+        assert!(compressed.len() <= max_compressed_len(data.len()));
+        let mut decompressed = vec![0; data.len()];
+        assert_eq!(
+            decompress_raw(&compressed, &mut decompressed).unwrap(),
+            data.len()
+        );
+        assert_eq!(decompressed, data);
+
+        let mut compressed = vec![];
+        compress_hadoop(data, &mut compressed);
+        assert!(compressed.len() <= max_hadoop_compressed_len(data.len()));
+        let mut decompressed = vec![0; data.len()];
+        decompress_hadoop(&compressed, &mut decompressed).unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn decompress_known_stream() -> Result<()> {
+        let mut output = vec![0; 12];
+        assert_eq!(decompress_raw(&RAW, &mut output)?, 12);
+        assert_eq!(output, b"abcabcabcabc");
+
+        // too small and truncated
+        assert!(decompress_raw(&RAW, &mut [0; 11]).is_err());
+        assert!(decompress_raw(&RAW[..8], &mut output).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn decompress_hadoop_frames() -> Result<()> {
+        let mut framed = vec![];
+        framed.extend_from_slice(&12u32.to_be_bytes());
+        framed.extend_from_slice(&(RAW.len() as u32).to_be_bytes());
+        framed.extend_from_slice(&RAW);
+        // a second block made of 2 chunks
+        framed.extend_from_slice(&24u32.to_be_bytes());
+        for _ in 0..2 {
+            framed.extend_from_slice(&(RAW.len() as u32).to_be_bytes());
+            framed.extend_from_slice(&RAW);
+        }
+
+        let mut output = vec![0; 36];
+        decompress_hadoop(&framed, &mut output)?;
+        assert_eq!(output, b"abcabcabcabc".repeat(3));
+
+        assert!(decompress_hadoop(&framed, &mut [0; 35]).is_err());
+        assert!(decompress_hadoop(&RAW, &mut [0; 12]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrips() {
+        roundtrip(&[]);
+        roundtrip(b"a");
+        roundtrip(b"abcd");
+        roundtrip(&[7; 1000]);
+
+        // literal runs and matches of every kind of instruction
+        let mut state = 1u32;
+        let random = (0..70_000)
+            .map(|_| {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                (state >> 16) as u8
+            })
+            .collect::<Vec<_>>();
+        roundtrip(&random);
+
+        let mut data = random[..300].to_vec();
+        for (i, distance) in [3, 100, 2000, 5000, 16000, 16384, 20000, 40000]
+            .into_iter()
+            .enumerate()
+        {
+            data.extend_from_slice(&random[i * 500..i * 500 + 7 + i]);
+            data.extend_from_slice(&random[1000..1000 + distance]);
+            let start = data.len() - distance;
+            data.extend_from_within(start..start + 3 + 40 * i);
+        }
+        roundtrip(&data);
+
+        // more than one Hadoop block
+        let data = (0..600_000).map(|x| (x % 251) as u8).collect::<Vec<_>>();
+        roundtrip(&data);
+    }
+}
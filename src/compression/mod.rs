@@ -0,0 +1,354 @@
+//! Functionality to compress and decompress data according to the parquet specification
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub use super::parquet_bridge::{
+    BrotliLevel, Compression, CompressionOptions, GzipLevel, ZstdLevel,
+};
+
+mod codecs;
+#[cfg(feature = "lzo")]
+mod lzo;
+mod zstd_dictionary;
+
+pub use codecs::{BrotliCodec, GzipCodec, Lz4Codec, Lz4RawCodec, LzoCodec, SnappyCodec, ZstdCodec};
+pub use zstd_dictionary::{
+    compress_with_zstd_dictionary, decompress_with_zstd_dictionary, ZstdDictionary,
+    ZstdDictionaryCodec, ZSTD_DICTIONARY_KEY,
+};
+
+use crate::error::{Error, Result};
+
+/// A compression codec, used to compress and decompress the pages of a column chunk.
+///
+/// The built-in codecs (e.g. [`SnappyCodec`]) implement this trait. Custom implementations
+/// can be used to read and write pages via a [`CodecRegistry`].
+pub trait Codec: std::fmt::Debug + Send + Sync {
+    /// Compresses `input`, appending the result to `output`.
+    fn compress(&self, input: &[u8], output: &mut Vec<u8>) -> Result<()>;
+
+    /// Decompresses `input` into `output`, whose length is the uncompressed length of `input`.
+    fn decompress(&self, input: &[u8], output: &mut [u8]) -> Result<()>;
+
+    /// Returns an upper bound of the length of `input_length` bytes once compressed.
+    fn max_compressed_len(&self, input_length: usize) -> Result<usize>;
+}
+
+/// Returns the built-in [`Codec`] of `compression`.
+/// # Errors
+/// Errors iff `compression` is [`CompressionOptions::Uncompressed`].
+pub fn builtin_codec(compression: CompressionOptions) -> Result<Box<dyn Codec>> {
+    Ok(match compression {
+        CompressionOptions::Snappy => Box::new(SnappyCodec),
+        CompressionOptions::Gzip(level) => Box::new(GzipCodec(level)),
+        CompressionOptions::Lzo => Box::new(LzoCodec),
+        CompressionOptions::Brotli(level) => Box::new(BrotliCodec(level)),
+        CompressionOptions::Lz4 => Box::new(Lz4Codec),
+        CompressionOptions::Zstd(level) => Box::new(ZstdCodec(level)),
+        CompressionOptions::Lz4Raw => Box::new(Lz4RawCodec),
+        CompressionOptions::Uncompressed => {
+            return Err(Error::InvalidParameter(
+                "Compressing uncompressed".to_string(),
+            ))
+        }
+    })
+}
+
+/// Calls `f` with the built-in [`Codec`] of `compression`, without allocating it.
+fn with_builtin_codec<T, F: FnOnce(&dyn Codec) -> Result<T>>(
+    compression: CompressionOptions,
+    f: F,
+) -> Result<T> {
+    match compression {
+        CompressionOptions::Snappy => f(&SnappyCodec),
+        CompressionOptions::Gzip(level) => f(&GzipCodec(level)),
+        CompressionOptions::Lzo => f(&LzoCodec),
+        CompressionOptions::Brotli(level) => f(&BrotliCodec(level)),
+        CompressionOptions::Lz4 => f(&Lz4Codec),
+        CompressionOptions::Zstd(level) => f(&ZstdCodec(level)),
+        CompressionOptions::Lz4Raw => f(&Lz4RawCodec),
+        CompressionOptions::Uncompressed => Err(Error::InvalidParameter(
+            "Compressing uncompressed".to_string(),
+        )),
+    }
+}
+
+/// A registry of [`Codec`]s per [`Compression`], consulted by [`crate::write::Compressor`]
+/// and [`crate::read::Decompressor`]. Compressions without a registered codec use their
+/// built-in codec (see [`builtin_codec`]).
+///
+/// Registered codecs replace the built-in ones, including their compression level:
+/// the level of [`CompressionOptions`] is only used by built-in codecs.
+#[derive(Debug, Clone, Default)]
+pub struct CodecRegistry {
+    codecs: HashMap<Compression, Arc<dyn Codec>>,
+}
+
+impl CodecRegistry {
+    /// Returns a new [`CodecRegistry`] without registered codecs,
+    /// i.e. one using the built-in codecs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `codec` for `compression`, returning the codec previously registered for it.
+    pub fn register(
+        &mut self,
+        compression: Compression,
+        codec: Arc<dyn Codec>,
+    ) -> Option<Arc<dyn Codec>> {
+        self.codecs.insert(compression, codec)
+    }
+
+    /// Returns the codec registered for `compression`, if any.
+    pub fn get(&self, compression: Compression) -> Option<&Arc<dyn Codec>> {
+        self.codecs.get(&compression)
+    }
+
+    fn with_codec<T, F: FnOnce(&dyn Codec) -> Result<T>>(
+        &self,
+        compression: CompressionOptions,
+        f: F,
+    ) -> Result<T> {
+        if let Some(codec) = self.codecs.get(&compression.into()) {
+            f(codec.as_ref())
+        } else {
+            with_builtin_codec(compression, f)
+        }
+    }
+
+    /// Compresses `input_buf` with the codec of `compression`, appending the result
+    /// to `output_buf`.
+    pub fn compress(
+        &self,
+        compression: CompressionOptions,
+        input_buf: &[u8],
+        output_buf: &mut Vec<u8>,
+    ) -> Result<()> {
+        self.with_codec(compression, |codec| codec.compress(input_buf, output_buf))
+    }
+
+    /// Decompresses `input_buf` with the codec of `compression` into `output_buf`.
+    pub fn decompress(
+        &self,
+        compression: Compression,
+        input_buf: &[u8],
+        output_buf: &mut [u8],
+    ) -> Result<()> {
+        self.with_codec(compression.into(), |codec| {
+            codec.decompress(input_buf, output_buf)
+        })
+    }
+
+    /// Returns an upper bound of the length of `input_length` bytes compressed
+    /// with the codec of `compression`.
+    pub fn max_compressed_len(
+        &self,
+        compression: CompressionOptions,
+        input_length: usize,
+    ) -> Result<usize> {
+        self.with_codec(compression, |codec| codec.max_compressed_len(input_length))
+    }
+}
+
+/// Compresses data stored in slice `input_buf` and writes the compressed result
+/// to `output_buf`.
+/// Note that you'll need to call `clear()` before reusing the same `output_buf`
+/// across different `compress` calls.
+///
+/// This uses the built-in codecs; use [`compress_with_zstd_dictionary`] to compress with a
+/// [`ZstdDictionary`].
+pub fn compress(
+    compression: CompressionOptions,
+    input_buf: &[u8],
+    output_buf: &mut Vec<u8>,
+) -> Result<()> {
+    with_builtin_codec(compression, |codec| codec.compress(input_buf, output_buf))
+}
+
+/// Decompresses data stored in slice `input_buf` and writes output to `output_buf`.
+/// Returns the total number of bytes written.
+pub fn decompress(compression: Compression, input_buf: &[u8], output_buf: &mut [u8]) -> Result<()> {
+    with_builtin_codec(compression.into(), |codec| {
+        codec.decompress(input_buf, output_buf)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_roundtrip(c: CompressionOptions, data: &[u8]) {
+        let offset = 2048;
+
+        // Compress to a buffer that already has data is possible
+        let mut compressed = vec![2; offset];
+        compress(c, data, &mut compressed).expect("Error when compressing");
+
+        // data is compressed...
+        assert!(compressed.len() - offset < data.len());
+
+        let mut decompressed = vec![0; data.len()];
+        decompress(c.into(), &compressed[offset..], &mut decompressed)
+            .expect("Error when decompressing");
+        assert_eq!(data, decompressed.as_slice());
+    }
+
+    #[derive(Debug)]
+    struct ReverseCodec;
+
+    impl Codec for ReverseCodec {
+        fn compress(&self, input: &[u8], output: &mut Vec<u8>) -> Result<()> {
+            output.extend(input.iter().rev());
+            Ok(())
+        }
+
+        fn decompress(&self, input: &[u8], output: &mut [u8]) -> Result<()> {
+            output.copy_from_slice(input);
+            output.reverse();
+            Ok(())
+        }
+
+        fn max_compressed_len(&self, input_length: usize) -> Result<usize> {
+            Ok(input_length)
+        }
+    }
+
+    #[test]
+    fn registry() -> Result<()> {
+        let data = (0..100u8).collect::<Vec<_>>();
+
+        let mut registry = CodecRegistry::new();
+        assert!(registry.get(Compression::Gzip).is_none());
+        registry.register(Compression::Gzip, Arc::new(ReverseCodec));
+        assert!(registry.get(Compression::Gzip).is_some());
+
+        // the registered codec is used regardless of the level
+        let mut compressed = vec![];
+        let options = CompressionOptions::Gzip(Some(GzipLevel::try_new(9)?));
+        registry.compress(options, &data, &mut compressed)?;
+        assert_eq!(compressed, data.iter().rev().copied().collect::<Vec<_>>());
+        assert_eq!(registry.max_compressed_len(options, 100)?, 100);
+
+        let mut decompressed = vec![0; data.len()];
+        registry.decompress(Compression::Gzip, &compressed, &mut decompressed)?;
+        assert_eq!(decompressed, data);
+
+        // the other compressions use the built-in codecs
+        assert!(registry
+            .compress(CompressionOptions::Uncompressed, &data, &mut compressed)
+            .is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "snappy")]
+    #[test]
+    fn builtin_registry() -> Result<()> {
+        let data = vec![1u8; 1000];
+        let mut expected = vec![];
+        compress(CompressionOptions::Snappy, &data, &mut expected)?;
+
+        let mut compressed = vec![];
+        CodecRegistry::new().compress(CompressionOptions::Snappy, &data, &mut compressed)?;
+        assert_eq!(compressed, expected);
+        assert!(compressed.len() <= SnappyCodec.max_compressed_len(data.len())?);
+        Ok(())
+    }
+
+    fn test_codec(c: CompressionOptions) {
+        let sizes = vec![1000, 10000, 100000];
+        for size in sizes {
+            let data = (0..size).map(|x| (x % 255) as u8).collect::<Vec<_>>();
+            test_roundtrip(c, &data);
+        }
+    }
+
+    #[test]
+    fn test_codec_snappy() {
+        test_codec(CompressionOptions::Snappy);
+    }
+
+    #[test]
+    fn test_codec_gzip_default() {
+        test_codec(CompressionOptions::Gzip(None));
+    }
+
+    #[test]
+    fn test_codec_gzip_low_compression() {
+        test_codec(CompressionOptions::Gzip(Some(
+            GzipLevel::try_new(1).unwrap(),
+        )));
+    }
+
+    #[test]
+    fn test_codec_gzip_high_compression() {
+        test_codec(CompressionOptions::Gzip(Some(
+            GzipLevel::try_new(10).unwrap(),
+        )));
+    }
+
+    #[test]
+    fn test_codec_brotli_default() {
+        test_codec(CompressionOptions::Brotli(None));
+    }
+
+    #[test]
+    fn test_codec_brotli_low_compression() {
+        test_codec(CompressionOptions::Brotli(Some(
+            BrotliLevel::try_new(1).unwrap(),
+        )));
+    }
+
+    #[test]
+    fn test_codec_brotli_high_compression() {
+        test_codec(CompressionOptions::Brotli(Some(
+            BrotliLevel::try_new(11).unwrap(),
+        )));
+    }
+
+    #[test]
+    fn test_codec_lz4_raw() {
+        test_codec(CompressionOptions::Lz4Raw);
+    }
+
+    #[test]
+    fn test_codec_zstd_default() {
+        test_codec(CompressionOptions::Zstd(None));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_codec_zstd_low_compression() {
+        test_codec(CompressionOptions::Zstd(Some(
+            ZstdLevel::try_new(1).unwrap(),
+        )));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_codec_zstd_high_compression() {
+        test_codec(CompressionOptions::Zstd(Some(
+            ZstdLevel::try_new(21).unwrap(),
+        )));
+    }
+
+    #[cfg(feature = "lzo")]
+    #[test]
+    fn test_codec_lzo() {
+        test_codec(CompressionOptions::Lzo);
+    }
+
+    #[cfg(not(feature = "lzo"))]
+    #[test]
+    fn test_lzo_not_active() {
+        let mut compressed = vec![];
+        assert!(matches!(
+            compress(CompressionOptions::Lzo, &[1, 2, 3], &mut compressed),
+            Err(Error::FeatureNotActive(crate::error::Feature::Lzo, _))
+        ));
+        assert!(matches!(
+            decompress(Compression::Lzo, &[1, 2, 3], &mut [0; 3]),
+            Err(Error::FeatureNotActive(crate::error::Feature::Lzo, _))
+        ));
+    }
+}
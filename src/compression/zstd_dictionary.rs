@@ -0,0 +1,326 @@
+use std::sync::Arc;
+
+use crate::error::{Error, Result};
+use crate::metadata::KeyValue;
+use crate::page::Page;
+
+use super::{Codec, ZstdCodec, ZstdLevel};
+
+/// The key of the key-value metadata of a column chunk holding its [`ZstdDictionary`],
+/// encoded in base64.
+pub const ZSTD_DICTIONARY_KEY: &str = "parquet2.zstd_dictionary";
+
+/// The magic number starting the dictionaries in zstd's format (little endian)
+const ZSTD_DICTIONARY_MAGIC: [u8; 4] = [0x37, 0xa4, 0x30, 0xec];
+
+/// A zstd dictionary, used to compress small and repetitive pages of a column.
+///
+/// Column chunks written with a dictionary (see [`crate::write::Compressor::with_zstd_dictionary`])
+/// store it in their key-value metadata under [`ZSTD_DICTIONARY_KEY`], from where it is loaded
+/// back to decompress their pages.
+///
+/// Storing the dictionary in the key-value metadata is specific to this crate: other parquet
+/// implementations ignore it and can't decompress the pages of such column chunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZstdDictionary {
+    bytes: Vec<u8>,
+}
+
+impl ZstdDictionary {
+    /// Returns a new [`ZstdDictionary`] from its bytes, e.g. a dictionary trained by the `zstd` CLI.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self { bytes }
+    }
+
+    /// Trains a [`ZstdDictionary`] of at most `max_size` bytes from the buffers of
+    /// sample `pages` of a column, before their compression.
+    /// # Errors
+    /// Errors iff the `zstd` feature is not active or zstd fails to train a dictionary,
+    /// e.g. because there are not enough samples.
+    pub fn train(pages: &[Page], max_size: usize) -> Result<Self> {
+        let samples = pages
+            .iter()
+            .map(|page| match page {
+                Page::Data(page) => page.buffer(),
+                Page::Dict(page) => page.buffer.as_slice(),
+            })
+            .collect::<Vec<_>>();
+        Self::train_from_samples(&samples, max_size)
+    }
+
+    /// Trains a [`ZstdDictionary`] of at most `max_size` bytes from `samples`.
+    /// # Errors
+    /// Errors iff the `zstd` feature is not active or zstd fails to train a dictionary,
+    /// e.g. because there are not enough samples.
+    #[cfg(feature = "zstd")]
+    pub fn train_from_samples<S: AsRef<[u8]>>(samples: &[S], max_size: usize) -> Result<Self> {
+        Ok(Self::new(zstd::dict::from_samples(samples, max_size)?))
+    }
+
+    /// Trains a [`ZstdDictionary`] of at most `max_size` bytes from `samples`.
+    /// # Errors
+    /// Errors iff the `zstd` feature is not active or zstd fails to train a dictionary,
+    /// e.g. because there are not enough samples.
+    #[cfg(not(feature = "zstd"))]
+    pub fn train_from_samples<S: AsRef<[u8]>>(_: &[S], _: usize) -> Result<Self> {
+        Err(Error::FeatureNotActive(
+            crate::error::Feature::Zstd,
+            "train a zstd dictionary".to_string(),
+        ))
+    }
+
+    /// The bytes of this dictionary
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The id of this dictionary, or `None` if it is a raw content dictionary.
+    pub fn id(&self) -> Option<u32> {
+        if self.bytes.len() >= 8 && self.bytes[..4] == ZSTD_DICTIONARY_MAGIC {
+            Some(u32::from_le_bytes(self.bytes[4..8].try_into().unwrap()))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the [`KeyValue`] storing this dictionary in the metadata of a column chunk.
+    /// # Errors
+    /// Errors iff the `zstd` feature is not active.
+    pub(crate) fn to_key_value(&self) -> Result<KeyValue> {
+        Ok(KeyValue {
+            key: ZSTD_DICTIONARY_KEY.to_string(),
+            value: Some(base64_encode(&self.bytes)?),
+        })
+    }
+
+    /// Returns the [`ZstdDictionary`] stored in the key-value metadata of a column chunk, if any.
+    /// # Errors
+    /// Errors iff the stored dictionary is not valid base64 or the `zstd` feature is not active.
+    pub(crate) fn try_from_key_values(key_values: &[KeyValue]) -> Result<Option<Self>> {
+        key_values
+            .iter()
+            .find(|kv| kv.key == ZSTD_DICTIONARY_KEY)
+            .and_then(|kv| kv.value.as_ref())
+            .map(|value| base64_decode(value).map(Self::new))
+            .transpose()
+    }
+}
+
+/// Compresses `input_buf` with zstd and `dictionary` at `level` (or the default level if `None`),
+/// appending the result to `output_buf`.
+#[cfg(feature = "zstd")]
+pub fn compress_with_zstd_dictionary(
+    level: Option<ZstdLevel>,
+    dictionary: &ZstdDictionary,
+    input_buf: &[u8],
+    output_buf: &mut Vec<u8>,
+) -> Result<()> {
+    use std::io::Write;
+    let level = level.map(|v| v.compression_level()).unwrap_or_default();
+
+    let mut encoder = zstd::Encoder::with_dictionary(output_buf, level, dictionary.as_bytes())?;
+    encoder.write_all(input_buf)?;
+    encoder.finish()?;
+    Ok(())
+}
+
+/// Compresses `input_buf` with zstd and `dictionary` at `level` (or the default level if `None`),
+/// appending the result to `output_buf`.
+#[cfg(not(feature = "zstd"))]
+pub fn compress_with_zstd_dictionary(
+    _: Option<ZstdLevel>,
+    _: &ZstdDictionary,
+    _: &[u8],
+    _: &mut Vec<u8>,
+) -> Result<()> {
+    Err(Error::FeatureNotActive(
+        crate::error::Feature::Zstd,
+        "compress to zstd".to_string(),
+    ))
+}
+
+/// Decompresses `input_buf`, compressed with zstd and `dictionary`, into `output_buf`.
+#[cfg(feature = "zstd")]
+pub fn decompress_with_zstd_dictionary(
+    dictionary: &ZstdDictionary,
+    input_buf: &[u8],
+    output_buf: &mut [u8],
+) -> Result<()> {
+    use std::io::Read;
+    let mut decoder = zstd::Decoder::with_dictionary(input_buf, dictionary.as_bytes())?;
+    decoder.read_exact(output_buf).map_err(|e| e.into())
+}
+
+/// Decompresses `input_buf`, compressed with zstd and `dictionary`, into `output_buf`.
+#[cfg(not(feature = "zstd"))]
+pub fn decompress_with_zstd_dictionary(_: &ZstdDictionary, _: &[u8], _: &mut [u8]) -> Result<()> {
+    Err(Error::FeatureNotActive(
+        crate::error::Feature::Zstd,
+        "decompress with zstd".to_string(),
+    ))
+}
+
+/// A [`Codec`] compressing and decompressing zstd with a [`ZstdDictionary`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZstdDictionaryCodec {
+    dictionary: Arc<ZstdDictionary>,
+    level: Option<ZstdLevel>,
+}
+
+impl ZstdDictionaryCodec {
+    /// Returns a new [`ZstdDictionaryCodec`], compressing with `level`
+    /// (or the default level if `None`).
+    pub fn new(dictionary: Arc<ZstdDictionary>, level: Option<ZstdLevel>) -> Self {
+        Self { dictionary, level }
+    }
+
+    /// The dictionary of this codec
+    pub fn dictionary(&self) -> &Arc<ZstdDictionary> {
+        &self.dictionary
+    }
+}
+
+impl Codec for ZstdDictionaryCodec {
+    fn compress(&self, input: &[u8], output: &mut Vec<u8>) -> Result<()> {
+        compress_with_zstd_dictionary(self.level, &self.dictionary, input, output)
+    }
+
+    fn decompress(&self, input: &[u8], output: &mut [u8]) -> Result<()> {
+        decompress_with_zstd_dictionary(&self.dictionary, input, output)
+    }
+
+    fn max_compressed_len(&self, input_length: usize) -> Result<usize> {
+        ZstdCodec(self.level).max_compressed_len(input_length)
+    }
+}
+
+/// Encodes `bytes` in base64 (standard alphabet, padded)
+#[cfg(feature = "zstd")]
+fn base64_encode(bytes: &[u8]) -> Result<String> {
+    use base64::Engine;
+    Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn base64_encode(_: &[u8]) -> Result<String> {
+    Err(Error::FeatureNotActive(
+        crate::error::Feature::Zstd,
+        "store a zstd dictionary".to_string(),
+    ))
+}
+
+/// Decodes base64 (standard alphabet, padded), rejecting non-canonical encodings
+#[cfg(feature = "zstd")]
+fn base64_decode(encoded: &str) -> Result<Vec<u8>> {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|error| {
+            Error::oos(format!(
+                "The zstd dictionary of the column chunk is not valid base64: {error}"
+            ))
+        })
+}
+
+#[cfg(not(feature = "zstd"))]
+fn base64_decode(_: &str) -> Result<Vec<u8>> {
+    Err(Error::FeatureNotActive(
+        crate::error::Feature::Zstd,
+        "load a zstd dictionary".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn base64() -> Result<()> {
+        for (bytes, encoded) in [
+            (b"".as_ref(), ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"fooba", "Zm9vYmE="),
+            (b"foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(base64_encode(bytes)?, encoded);
+            assert_eq!(base64_decode(encoded)?, bytes);
+        }
+        let bytes = (0..=255u8).collect::<Vec<_>>();
+        assert_eq!(base64_decode(&base64_encode(&bytes)?)?, bytes);
+        Ok(())
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn base64_invalid() {
+        for encoded in [
+            // truncated
+            "Zm9", "Zm9vYmF", "Zg=", // missing padding
+            "Zg", "Zm9vYg", // non-canonical: the bits after the last byte are not zero
+            "Zh==", "Zm9=", // padding before the end
+            "Zg==Zm9v", "Z===", // outside of the alphabet
+            "Zm9*", "Zm9v\n", "Zm-v",
+        ] {
+            let result = base64_decode(encoded);
+            assert!(
+                matches!(&result, Err(Error::OutOfSpec(message)) if message.contains("base64")),
+                "{encoded:?}: {result:?}"
+            );
+        }
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn key_value() -> Result<()> {
+        let dictionary = ZstdDictionary::new(vec![0x37, 0xa4, 0x30, 0xec, 7, 0, 0, 0, 1, 2]);
+        assert_eq!(dictionary.id(), Some(7));
+        assert_eq!(ZstdDictionary::new(vec![1, 2, 3]).id(), None);
+
+        let key_values = vec![
+            KeyValue {
+                key: "other".to_string(),
+                value: None,
+            },
+            dictionary.to_key_value()?,
+        ];
+        assert_eq!(
+            ZstdDictionary::try_from_key_values(&key_values)?,
+            Some(dictionary)
+        );
+        assert_eq!(ZstdDictionary::try_from_key_values(&key_values[..1])?, None);
+        Ok(())
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn codec() -> Result<()> {
+        let samples = (0..1000)
+            .map(|i| format!(r#"{{"id": {i}, "name": "user {}", "active": true}}"#, i % 7))
+            .collect::<Vec<_>>();
+        let dictionary = ZstdDictionary::train_from_samples(&samples, 4096)?;
+        assert!(dictionary.id().is_some());
+        let codec = ZstdDictionaryCodec::new(Arc::new(dictionary), None);
+
+        let data = samples[3].as_bytes();
+        let mut compressed = vec![];
+        codec.compress(data, &mut compressed)?;
+        assert!(compressed.len() <= codec.max_compressed_len(data.len())?);
+
+        let mut without_dictionary = vec![];
+        super::super::compress(
+            super::super::CompressionOptions::Zstd(None),
+            data,
+            &mut without_dictionary,
+        )?;
+        assert!(compressed.len() < without_dictionary.len());
+
+        let mut decompressed = vec![0; data.len()];
+        codec.decompress(&compressed, &mut decompressed)?;
+        assert_eq!(decompressed, data);
+        Ok(())
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
     parquet_bridge::{Encoding, Repetition},
 };
 
-use super::utils;
+use super::{utils, DeltaBinaryIter, DeltaLengthBinaryIter};
 
 #[derive(Debug)]
 pub struct Dictionary<'a, P> {
@@ -32,6 +32,10 @@ pub enum BinaryPageState<'a, P> {
     Required(BinaryIter<'a>),
     RequiredDictionary(Dictionary<'a, P>),
     OptionalDictionary(utils::DefLevelsDecoder<'a>, Dictionary<'a, P>),
+    RequiredDeltaLengthByteArray(DeltaLengthBinaryIter<'a>),
+    OptionalDeltaLengthByteArray(utils::DefLevelsDecoder<'a>, DeltaLengthBinaryIter<'a>),
+    RequiredDeltaByteArray(DeltaBinaryIter<'a>),
+    OptionalDeltaByteArray(utils::DefLevelsDecoder<'a>, DeltaBinaryIter<'a>),
 }
 
 impl<'a, P> BinaryPageState<'a, P> {
@@ -63,6 +67,32 @@ impl<'a, P> BinaryPageState<'a, P> {
 
                 Ok(Self::Required(values))
             }
+            (Encoding::DeltaLengthByteArray, _, true) => {
+                let (_, _, values) = split_buffer(page)?;
+
+                Ok(Self::OptionalDeltaLengthByteArray(
+                    utils::DefLevelsDecoder::try_new(page)?,
+                    DeltaLengthBinaryIter::try_new(values)?,
+                ))
+            }
+            (Encoding::DeltaLengthByteArray, _, false) => {
+                let (_, _, values) = split_buffer(page)?;
+
+                DeltaLengthBinaryIter::try_new(values).map(Self::RequiredDeltaLengthByteArray)
+            }
+            (Encoding::DeltaByteArray, _, true) => {
+                let (_, _, values) = split_buffer(page)?;
+
+                Ok(Self::OptionalDeltaByteArray(
+                    utils::DefLevelsDecoder::try_new(page)?,
+                    DeltaBinaryIter::try_new(values)?,
+                ))
+            }
+            (Encoding::DeltaByteArray, _, false) => {
+                let (_, _, values) = split_buffer(page)?;
+
+                DeltaBinaryIter::try_new(values).map(Self::RequiredDeltaByteArray)
+            }
             _ => Err(Error::FeatureNotSupported(format!(
                 "Viewing page for encoding {:?} for binary type",
                 page.encoding(),
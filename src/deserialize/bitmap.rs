@@ -0,0 +1,194 @@
+use crate::error::Error;
+
+use super::hybrid_rle::HybridEncoded;
+
+#[inline]
+fn get_bit(bytes: &[u8], i: usize) -> bool {
+    bytes[i / 8] & (1 << (i % 8)) != 0
+}
+
+#[inline]
+fn set_bit(bytes: &mut [u8], i: usize, value: bool) {
+    if value {
+        bytes[i / 8] |= 1 << (i % 8);
+    } else {
+        bytes[i / 8] &= !(1 << (i % 8));
+    }
+}
+
+/// Errors iff `bitmap` has less than `length` bits.
+#[inline]
+pub(super) fn check_capacity(bitmap: &[u8], length: usize) -> Result<(), Error> {
+    if length > bitmap.len() * 8 {
+        return Err(Error::InvalidParameter(format!(
+            "The bitmap has {} bits but {} are required",
+            bitmap.len() * 8,
+            length
+        )));
+    }
+    Ok(())
+}
+
+/// Copies `length` bits (LSB first) of `src` starting at bit `src_offset` into `dst` starting
+/// at bit `dst_offset`. The other bits of `dst` are left unchanged.
+/// # Implementation
+/// When the offsets have the same alignment within a byte, whole bytes are copied
+/// with `copy_from_slice`.
+/// # Panics
+/// Panics iff `src` or `dst` are not large enough.
+pub fn copy_bits(src: &[u8], src_offset: usize, dst: &mut [u8], dst_offset: usize, length: usize) {
+    let mut i = 0;
+    if src_offset % 8 == dst_offset % 8 {
+        // leading bits until both are aligned
+        while i < length && (dst_offset + i) & 7 != 0 {
+            set_bit(dst, dst_offset + i, get_bit(src, src_offset + i));
+            i += 1;
+        }
+        let bytes = (length - i) / 8;
+        let src_start = (src_offset + i) / 8;
+        let dst_start = (dst_offset + i) / 8;
+        dst[dst_start..dst_start + bytes].copy_from_slice(&src[src_start..src_start + bytes]);
+        i += bytes * 8;
+    }
+    while i < length {
+        set_bit(dst, dst_offset + i, get_bit(src, src_offset + i));
+        i += 1;
+    }
+}
+
+/// Sets `length` bits of `dst` starting at bit `offset` to `value`.
+/// # Panics
+/// Panics iff `dst` is not large enough.
+pub fn set_bits(dst: &mut [u8], offset: usize, length: usize, value: bool) {
+    let mut i = 0;
+    while i < length && (offset + i) & 7 != 0 {
+        set_bit(dst, offset + i, value);
+        i += 1;
+    }
+    let bytes = (length - i) / 8;
+    let start = (offset + i) / 8;
+    dst[start..start + bytes].fill(if value { u8::MAX } else { 0 });
+    i += bytes * 8;
+    while i < length {
+        set_bit(dst, offset + i, value);
+        i += 1;
+    }
+}
+
+/// Writes `values` into `bitmap` starting at bit `offset`, returning the number of bits written.
+/// # Errors
+/// Errors iff `bitmap` is not large enough or `values` errors.
+pub(super) fn decode_bools_into_bitmap<I: Iterator<Item = Result<bool, Error>>>(
+    values: I,
+    bitmap: &mut [u8],
+    offset: usize,
+) -> Result<usize, Error> {
+    let mut written = 0;
+    for value in values {
+        check_capacity(bitmap, offset + written + 1)?;
+        set_bit(bitmap, offset + written, value?);
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Decodes the runs of a hybrid-RLE encoded bitmap (e.g. the definition levels of a page with
+/// a maximum definition level of 1) into `bitmap` starting at bit `offset`,
+/// returning the number of bits written.
+/// Bitpacked runs are copied with [`copy_bits`] and repeated runs with [`set_bits`].
+/// # Errors
+/// Errors iff `bitmap` is not large enough or `runs` errors.
+pub fn decode_runs_into_bitmap<'a, I: Iterator<Item = Result<HybridEncoded<'a>, Error>>>(
+    runs: I,
+    bitmap: &mut [u8],
+    offset: usize,
+) -> Result<usize, Error> {
+    let mut written = 0;
+    for run in runs {
+        let run = run?;
+        check_capacity(bitmap, offset + written + run.len())?;
+        match run {
+            HybridEncoded::Bitmap(values, length) => {
+                copy_bits(values, 0, bitmap, offset + written, length)
+            }
+            HybridEncoded::Repeated(is_set, length) => {
+                set_bits(bitmap, offset + written, length, is_set)
+            }
+        }
+        written += run.len();
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hybrid_rle::BitmapIter;
+
+    fn bits(bytes: &[u8], offset: usize, length: usize) -> Vec<bool> {
+        BitmapIter::new(bytes, offset, length).collect()
+    }
+
+    #[test]
+    fn copy() {
+        let src = (0..20u8).map(|x| x.wrapping_mul(37)).collect::<Vec<_>>();
+        for (src_offset, dst_offset) in [(0, 0), (3, 3), (8, 16), (1, 6), (7, 0)] {
+            for length in [0, 1, 5, 8, 13, 64, 100] {
+                let mut dst = vec![0b10101010u8; 20];
+                copy_bits(&src, src_offset, &mut dst, dst_offset, length);
+                assert_eq!(
+                    bits(&dst, dst_offset, length),
+                    bits(&src, src_offset, length)
+                );
+                // the surrounding bits are untouched
+                assert_eq!(
+                    bits(&dst, 0, dst_offset),
+                    bits(&[0b10101010; 20], 0, dst_offset)
+                );
+                let end = dst_offset + length;
+                assert_eq!(
+                    bits(&dst, end, 160 - end),
+                    bits(&[0b10101010; 20], end, 160 - end)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn set() {
+        for offset in [0, 3, 8] {
+            for length in [0, 1, 5, 30] {
+                let mut dst = vec![0b01010101u8; 6];
+                set_bits(&mut dst, offset, length, true);
+                assert!(bits(&dst, offset, length).iter().all(|x| *x));
+                set_bits(&mut dst, offset, length, false);
+                assert!(bits(&dst, offset, length).iter().all(|x| !*x));
+                assert_eq!(bits(&dst, 0, offset), bits(&[0b01010101], 0, offset));
+            }
+        }
+    }
+
+    #[test]
+    fn runs() -> Result<(), Error> {
+        let runs = vec![
+            Ok(HybridEncoded::Repeated(true, 10)),
+            Ok(HybridEncoded::Bitmap(&[0b00000101], 3)),
+            Ok(HybridEncoded::Repeated(false, 2)),
+        ];
+        let mut bitmap = vec![0u8; 3];
+        assert_eq!(
+            decode_runs_into_bitmap(runs.into_iter(), &mut bitmap, 4)?,
+            15
+        );
+
+        let mut expected = vec![false; 4];
+        expected.extend([true; 10]);
+        expected.extend([true, false, true, false, false]);
+        assert_eq!(bits(&bitmap, 0, 19), expected);
+
+        let runs = vec![Ok(HybridEncoded::Repeated(true, 10))];
+        let mut bitmap = vec![0u8; 1];
+        assert!(decode_runs_into_bitmap(runs.into_iter(), &mut bitmap, 0).is_err());
+        Ok(())
+    }
+}
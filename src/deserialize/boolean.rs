@@ -1,10 +1,14 @@
 use crate::{
-    encoding::hybrid_rle::BitmapIter,
+    encoding::{ceil8, get_length, hybrid_rle, hybrid_rle::BitmapIter},
     error::Error,
     page::{split_buffer, DataPage},
     parquet_bridge::{Encoding, Repetition},
 };
 
+use super::bitmap::{check_capacity, copy_bits, decode_bools_into_bitmap, decode_runs_into_bitmap};
+use super::hybrid_rle::{
+    HybridDecoderBitmapIter, HybridRleBooleanIter, HybridRleDecoderIter, HybridRleIter,
+};
 use super::utils;
 
 // The state of a `DataPage` of `Boolean` parquet boolean type
@@ -13,6 +17,21 @@ use super::utils;
 pub enum BooleanPageState<'a> {
     Optional(utils::DefLevelsDecoder<'a>, BitmapIter<'a>),
     Required(&'a [u8], usize),
+    /// A page of optional, RLE-encoded values
+    OptionalRle(utils::DefLevelsDecoder<'a>, HybridRleDecoderIter<'a>),
+    /// A page of required, RLE-encoded values
+    RequiredRle(HybridDecoderBitmapIter<'a>),
+}
+
+/// Returns the RLE-encoded values of a page, which are prefixed by their length.
+fn rle_values(values: &[u8]) -> Result<&[u8], Error> {
+    get_length(values)
+        .and_then(|length| values.get(4..4 + length))
+        .ok_or_else(|| {
+            Error::oos(
+                "The number of bytes declared in RLE boolean values is higher than the page size",
+            )
+        })
 }
 
 impl<'a> BooleanPageState<'a> {
@@ -33,10 +52,198 @@ impl<'a> BooleanPageState<'a> {
                 let (_, _, values) = split_buffer(page)?;
                 Ok(Self::Required(values, page.num_values()))
             }
+            (Encoding::Rle, true) => {
+                let validity = utils::DefLevelsDecoder::try_new(page)?;
+
+                let (_, _, values) = split_buffer(page)?;
+                let values = hybrid_rle::Decoder::new(rle_values(values)?, 1);
+                let values =
+                    HybridRleBooleanIter::new(HybridRleIter::new(values, page.num_values()));
+
+                Ok(Self::OptionalRle(validity, values))
+            }
+            (Encoding::Rle, false) => {
+                let (_, _, values) = split_buffer(page)?;
+                let values = hybrid_rle::Decoder::new(rle_values(values)?, 1);
+                Ok(Self::RequiredRle(HybridRleIter::new(
+                    values,
+                    page.num_values(),
+                )))
+            }
             _ => Err(Error::InvalidParameter(format!(
                 "Viewing page for encoding {:?} for boolean type not supported",
                 page.encoding(),
             ))),
         }
     }
+
+    /// Decodes the values of this page into `bitmap` starting at bit `offset`, LSB first,
+    /// returning the number of bits written. Nulls are written as unset bits; use
+    /// [`super::DefLevelsDecoder::decode_into_bitmap`] to decode the validity of the page.
+    /// # Implementation
+    /// The values of required `PLAIN` pages and the bitpacked runs of required `RLE` pages
+    /// are copied byte-wise whenever `offset` is a multiple of 8 (see [`copy_bits`]).
+    /// # Errors
+    /// Errors iff `bitmap` is not large enough or the page is invalid.
+    pub fn decode_into_bitmap(self, bitmap: &mut [u8], offset: usize) -> Result<usize, Error> {
+        match self {
+            Self::Optional(validity, values) => {
+                decode_optional_into_bitmap(validity, values.map(Ok), bitmap, offset)
+            }
+            Self::Required(values, length) => {
+                if values.len() < ceil8(length) {
+                    return Err(Error::oos(
+                        "The page has less PLAIN-encoded values than declared",
+                    ));
+                }
+                check_capacity(bitmap, offset + length)?;
+                copy_bits(values, 0, bitmap, offset, length);
+                Ok(length)
+            }
+            Self::OptionalRle(validity, values) => {
+                decode_optional_into_bitmap(validity, values, bitmap, offset)
+            }
+            Self::RequiredRle(runs) => decode_runs_into_bitmap(runs, bitmap, offset),
+        }
+    }
+}
+
+fn decode_optional_into_bitmap<I: Iterator<Item = Result<bool, Error>>>(
+    validity: utils::DefLevelsDecoder,
+    mut values: I,
+    bitmap: &mut [u8],
+    offset: usize,
+) -> Result<usize, Error> {
+    check_capacity(bitmap, offset + validity.len())?;
+
+    let mut value = |is_valid: bool| {
+        if is_valid {
+            values.next().unwrap_or_else(|| {
+                Err(Error::oos(
+                    "The page has less values than declared by its definition levels",
+                ))
+            })
+        } else {
+            Ok(false)
+        }
+    };
+    match validity {
+        utils::DefLevelsDecoder::Bitmap(runs) => decode_bools_into_bitmap(
+            HybridRleBooleanIter::new(runs).map(|x| x.and_then(&mut value)),
+            bitmap,
+            offset,
+        ),
+        utils::DefLevelsDecoder::Levels(levels, max) => decode_bools_into_bitmap(
+            levels.map(|x| x.and_then(|x| value(x == max))),
+            bitmap,
+            offset,
+        ),
+        utils::DefLevelsDecoder::BitPacked(levels, max) => {
+            decode_bools_into_bitmap(levels.map(|x| value(x == max)), bitmap, offset)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hybrid_rle::{encode_bool, BitmapIter};
+    use crate::metadata::Descriptor;
+    use crate::page::{DataPageHeader, DataPageHeaderV1};
+    use crate::schema::types::{PhysicalType, PrimitiveType};
+
+    fn page(values: &[Option<bool>], encoding: Encoding, is_optional: bool) -> DataPage {
+        let mut buffer = vec![];
+        if is_optional {
+            let mut levels = vec![];
+            encode_bool(&mut levels, values.iter().map(|x| x.is_some())).unwrap();
+            buffer.extend((levels.len() as u32).to_le_bytes());
+            buffer.extend(levels);
+        }
+        let non_null = values.iter().flatten().copied().collect::<Vec<_>>();
+        match encoding {
+            Encoding::Plain => {
+                hybrid_rle::bitpacked_encode(&mut buffer, non_null.into_iter()).unwrap();
+            }
+            _ => {
+                let mut encoded = vec![];
+                encode_bool(&mut encoded, non_null.into_iter()).unwrap();
+                buffer.extend((encoded.len() as u32).to_le_bytes());
+                buffer.extend(encoded);
+            }
+        }
+
+        let mut primitive_type =
+            PrimitiveType::from_physical("a".to_string(), PhysicalType::Boolean);
+        if !is_optional {
+            primitive_type.field_info.repetition = Repetition::Required;
+        }
+        let header = DataPageHeader::V1(DataPageHeaderV1 {
+            num_values: values.len() as i32,
+            encoding: encoding.into(),
+            definition_level_encoding: Encoding::Rle.into(),
+            repetition_level_encoding: Encoding::Rle.into(),
+            statistics: None,
+        });
+        let descriptor = Descriptor {
+            primitive_type,
+            max_def_level: is_optional as i16,
+            max_rep_level: 0,
+        };
+        DataPage::new(header, buffer, descriptor, None)
+    }
+
+    fn bits(bitmap: &[u8], offset: usize, length: usize) -> Vec<bool> {
+        BitmapIter::new(bitmap, offset, length).collect()
+    }
+
+    #[test]
+    fn required() -> Result<(), Error> {
+        let values = (0..100).map(|x| x % 3 == 0 || x > 80).collect::<Vec<_>>();
+        let optional = values.iter().copied().map(Some).collect::<Vec<_>>();
+
+        for encoding in [Encoding::Plain, Encoding::Rle] {
+            let page = page(&optional, encoding, false);
+            for offset in [0, 3, 8] {
+                let mut bitmap = vec![0u8; 14];
+                let state = BooleanPageState::try_new(&page)?;
+                assert_eq!(state.decode_into_bitmap(&mut bitmap, offset)?, 100);
+                assert_eq!(bits(&bitmap, offset, 100), values);
+                assert_eq!(bits(&bitmap, 0, offset), vec![false; offset]);
+            }
+
+            let mut bitmap = vec![0u8; 12];
+            let state = BooleanPageState::try_new(&page)?;
+            assert!(state.decode_into_bitmap(&mut bitmap, 0).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn optional() -> Result<(), Error> {
+        let values = (0..100)
+            .map(|x| (x % 7 != 0).then_some(x % 3 == 0 || x > 80))
+            .collect::<Vec<_>>();
+        let expected_validity = values.iter().map(|x| x.is_some()).collect::<Vec<_>>();
+        let expected_values = values
+            .iter()
+            .map(|x| x.unwrap_or_default())
+            .collect::<Vec<_>>();
+
+        for encoding in [Encoding::Plain, Encoding::Rle] {
+            let page = page(&values, encoding, true);
+            for offset in [0, 5] {
+                let mut bitmap = vec![0u8; 14];
+                let state = BooleanPageState::try_new(&page)?;
+                assert_eq!(state.decode_into_bitmap(&mut bitmap, offset)?, 100);
+                assert_eq!(bits(&bitmap, offset, 100), expected_values);
+
+                let mut validity = vec![0u8; 14];
+                let decoder = utils::DefLevelsDecoder::try_new(&page)?;
+                assert_eq!(decoder.decode_into_bitmap(&mut validity, offset)?, 100);
+                assert_eq!(bits(&validity, offset, 100), expected_validity);
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,329 @@
+use std::marker::PhantomData;
+
+use crate::{
+    encoding::{delta_bitpacked, delta_length_byte_array},
+    error::Error,
+    schema::types::PhysicalType,
+    types::{decode, NativeType},
+};
+
+/// Iterator of [`NativeType`] decoded from `DELTA_BINARY_PACKED`-encoded values.
+/// # Implementation
+/// `nth` skips values without decoding whole mini-blocks, which makes this iterator
+/// suitable for [`super::SliceFilteredIter`].
+#[derive(Debug)]
+pub struct DeltaBinaryPackedIter<'a, T: NativeType> {
+    decoder: delta_bitpacked::Decoder<'a>,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: NativeType> DeltaBinaryPackedIter<'a, T> {
+    /// Returns a new [`DeltaBinaryPackedIter`].
+    /// # Errors
+    /// Errors iff `T` is neither `i32` nor `i64`, or the header of `values` is invalid.
+    pub fn try_new(values: &'a [u8]) -> Result<Self, Error> {
+        if !matches!(T::TYPE, PhysicalType::Int32 | PhysicalType::Int64) {
+            return Err(Error::FeatureNotSupported(format!(
+                "DELTA_BINARY_PACKED-encoded values of physical type {:?}",
+                T::TYPE
+            )));
+        }
+        Ok(Self {
+            decoder: delta_bitpacked::Decoder::try_new(values)?,
+            phantom: PhantomData,
+        })
+    }
+
+    #[inline]
+    fn cast(value: i64) -> T {
+        if std::mem::size_of::<T>() == std::mem::size_of::<i32>() {
+            decode::<T>(&(value as i32).to_le_bytes())
+        } else {
+            decode::<T>(&value.to_le_bytes())
+        }
+    }
+}
+
+impl<'a, T: NativeType> Iterator for DeltaBinaryPackedIter<'a, T> {
+    type Item = Result<T, Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decoder.next().map(|x| x.map(Self::cast))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.decoder.nth(n).map(|x| x.map(Self::cast))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.decoder.size_hint()
+    }
+}
+
+/// Iterator of the values of `DELTA_LENGTH_BYTE_ARRAY`-encoded `BYTE_ARRAY`s.
+/// # Implementation
+/// `nth` skips values without slicing them, which makes this iterator
+/// suitable for [`super::SliceFilteredIter`].
+#[derive(Debug)]
+pub struct DeltaLengthBinaryIter<'a> {
+    lengths: delta_length_byte_array::Decoder<'a>,
+    values: &'a [u8],
+}
+
+impl<'a> DeltaLengthBinaryIter<'a> {
+    /// Returns a new [`DeltaLengthBinaryIter`].
+    /// # Errors
+    /// Errors iff the lengths in `values` are invalid.
+    pub fn try_new(values: &'a [u8]) -> Result<Self, Error> {
+        // the concatenated values start after the lengths
+        let mut lengths = delta_bitpacked::Decoder::try_new(values)?;
+        lengths.skip_values(usize::MAX)?;
+        let start = lengths.consumed_bytes();
+
+        Ok(Self {
+            lengths: delta_length_byte_array::Decoder::try_new(values)?,
+            values: values
+                .get(start..)
+                .ok_or_else(|| Error::oos("The lengths of DELTA_LENGTH_BYTE_ARRAY overflow"))?,
+        })
+    }
+
+    #[inline]
+    fn slice(&mut self, length: i32) -> Result<&'a [u8], Error> {
+        let length = usize::try_from(length)
+            .map_err(|_| Error::oos("DELTA_LENGTH_BYTE_ARRAY lengths must be positive"))?;
+        if length > self.values.len() {
+            return Err(Error::oos(
+                "DELTA_LENGTH_BYTE_ARRAY has less bytes than the sum of its lengths",
+            ));
+        }
+        let (value, remaining) = self.values.split_at(length);
+        self.values = remaining;
+        Ok(value)
+    }
+}
+
+impl<'a> Iterator for DeltaLengthBinaryIter<'a> {
+    type Item = Result<&'a [u8], Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lengths
+            .next()
+            .map(|length| length.and_then(|length| self.slice(length)))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self.lengths.skip_values(n) {
+            Ok(skipped) => {
+                if let Some(values) = self.values.get(skipped..) {
+                    self.values = values;
+                    self.next()
+                } else {
+                    Some(Err(Error::oos(
+                        "DELTA_LENGTH_BYTE_ARRAY has less bytes than the sum of its lengths",
+                    )))
+                }
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lengths.size_hint()
+    }
+}
+
+/// Iterator of the values of `DELTA_BYTE_ARRAY`-encoded `BYTE_ARRAY`s and
+/// `FIXED_LEN_BYTE_ARRAY`s.
+/// # Implementation
+/// Every value is the concatenation of a prefix of the previous value and a suffix and is
+/// therefore returned as a [`Vec`]. Skipping values requires reconstructing them.
+#[derive(Debug)]
+pub struct DeltaBinaryIter<'a> {
+    prefixes: delta_bitpacked::Decoder<'a>,
+    suffixes: DeltaLengthBinaryIter<'a>,
+    last: Vec<u8>,
+}
+
+impl<'a> DeltaBinaryIter<'a> {
+    /// Returns a new [`DeltaBinaryIter`].
+    /// # Errors
+    /// Errors iff the prefix lengths or the suffix lengths in `values` are invalid or their
+    /// number differs.
+    pub fn try_new(values: &'a [u8]) -> Result<Self, Error> {
+        // the suffixes start after the prefix lengths
+        let mut prefixes = delta_bitpacked::Decoder::try_new(values)?;
+        prefixes.skip_values(usize::MAX)?;
+        let start = prefixes.consumed_bytes();
+        let suffixes = values
+            .get(start..)
+            .ok_or_else(|| Error::oos("The prefix lengths of DELTA_BYTE_ARRAY overflow"))?;
+
+        let prefixes = delta_bitpacked::Decoder::try_new(values)?;
+        let suffixes = DeltaLengthBinaryIter::try_new(suffixes)?;
+        if prefixes.size_hint() != suffixes.size_hint() {
+            return Err(Error::oos(format!(
+                "DELTA_BYTE_ARRAY must have as many prefix lengths as suffixes. It has {} prefix lengths and {} suffixes",
+                prefixes.size_hint().0,
+                suffixes.size_hint().0
+            )));
+        }
+
+        Ok(Self {
+            prefixes,
+            suffixes,
+            last: vec![],
+        })
+    }
+
+    #[inline]
+    fn value(&mut self, prefix: i64, suffix: &[u8]) -> Result<Vec<u8>, Error> {
+        let prefix = usize::try_from(prefix)
+            .ok()
+            .filter(|prefix| *prefix <= self.last.len())
+            .ok_or_else(|| {
+                Error::oos("DELTA_BYTE_ARRAY prefixes must not be longer than the previous value")
+            })?;
+        self.last.truncate(prefix);
+        self.last.extend_from_slice(suffix);
+        Ok(self.last.clone())
+    }
+}
+
+impl<'a> Iterator for DeltaBinaryIter<'a> {
+    type Item = Result<Vec<u8>, Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let prefix = self.prefixes.next()?;
+        let suffix = self.suffixes.next()?;
+        Some(prefix.and_then(|prefix| suffix.and_then(|suffix| self.value(prefix, suffix))))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.prefixes.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{delta_byte_array, delta_length_byte_array};
+    use crate::indexes::Interval;
+
+    use super::super::SliceFilteredIter;
+
+    #[test]
+    fn binary_packed() -> Result<(), Error> {
+        let values = (0..300).map(|x| x * x - 1000).collect::<Vec<i32>>();
+        let mut buffer = vec![];
+        delta_bitpacked::encode_i32(values.iter().copied(), &mut buffer);
+
+        let iter = DeltaBinaryPackedIter::<i32>::try_new(&buffer)?;
+        assert_eq!(iter.collect::<Result<Vec<_>, _>>()?, values);
+
+        let iter = DeltaBinaryPackedIter::<i64>::try_new(&buffer)?;
+        let expected = values.iter().map(|x| *x as i64).collect::<Vec<_>>();
+        assert_eq!(iter.collect::<Result<Vec<_>, _>>()?, expected);
+
+        assert!(DeltaBinaryPackedIter::<f32>::try_new(&buffer).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn length_binary() -> Result<(), Error> {
+        let values = (0..200)
+            .map(|x| format!("v{}", x * 7).into_bytes())
+            .collect::<Vec<_>>();
+        let mut buffer = vec![];
+        delta_length_byte_array::encode(values.iter(), &mut buffer);
+
+        let iter = DeltaLengthBinaryIter::try_new(&buffer)?;
+        assert_eq!(iter.size_hint(), (200, Some(200)));
+        let result = iter.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(result, values);
+        Ok(())
+    }
+
+    #[test]
+    fn binary() -> Result<(), Error> {
+        let values = vec![b"Hello".as_ref(), b"Helicopter", b"", b"Hell", b"Hello"];
+        let mut buffer = vec![];
+        delta_byte_array::encode(values.iter().copied(), &mut buffer);
+
+        let iter = DeltaBinaryIter::try_new(&buffer)?;
+        let result = iter.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(result, values);
+        Ok(())
+    }
+
+    #[test]
+    fn binary_count_mismatch() {
+        // 3 prefix lengths but only 2 suffixes
+        let mut buffer = vec![];
+        delta_bitpacked::encode([0i64, 0, 0].into_iter(), &mut buffer);
+        delta_length_byte_array::encode([b"a".as_ref(), b"b"].iter(), &mut buffer);
+
+        assert!(matches!(
+            DeltaBinaryIter::try_new(&buffer),
+            Err(Error::OutOfSpec(_))
+        ));
+    }
+
+    #[test]
+    fn filtered() -> Result<(), Error> {
+        let intervals = [
+            Interval::new(1, 2),
+            Interval::new(130, 10),
+            Interval::new(299, 1),
+        ];
+        let select = |values: &[Vec<u8>]| {
+            intervals
+                .iter()
+                .flat_map(|i| values[i.start..i.start + i.length].to_vec())
+                .collect::<Vec<_>>()
+        };
+
+        let values = (0..300).map(|x| x % 11 - 3).collect::<Vec<i64>>();
+        let mut buffer = vec![];
+        delta_bitpacked::encode(values.iter().copied(), &mut buffer);
+        let iter = SliceFilteredIter::new(
+            DeltaBinaryPackedIter::<i64>::try_new(&buffer)?,
+            intervals.iter().copied().collect(),
+        );
+        let expected = intervals
+            .iter()
+            .flat_map(|i| values[i.start..i.start + i.length].to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(iter.collect::<Result<Vec<_>, _>>()?, expected);
+
+        let values = (0..300)
+            .map(|x| format!("value {}", x).into_bytes())
+            .collect::<Vec<_>>();
+        let mut buffer = vec![];
+        delta_length_byte_array::encode(values.iter(), &mut buffer);
+        let iter = SliceFilteredIter::new(
+            DeltaLengthBinaryIter::try_new(&buffer)?,
+            intervals.iter().copied().collect(),
+        );
+        let result = iter.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(result, select(&values));
+
+        let mut buffer = vec![];
+        delta_byte_array::encode(values.iter().map(|x| x.as_slice()), &mut buffer);
+        let iter = SliceFilteredIter::new(
+            DeltaBinaryIter::try_new(&buffer)?,
+            intervals.iter().copied().collect(),
+        );
+        let result = iter.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(result, select(&values));
+        Ok(())
+    }
+}
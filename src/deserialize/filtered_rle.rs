@@ -27,10 +27,26 @@ pub enum FilteredHybridEncoded<'a> {
     Skipped(usize),
 }
 
+/// Returns the number of set bits in `length` bits of `values` starting at `offset`.
+/// Whole bytes are counted at once, so that skipping a run does not require iterating over it.
 fn is_set_count(values: &[u8], offset: usize, length: usize) -> usize {
-    BitmapIter::new(values, offset, length)
-        .filter(|x| *x)
-        .count()
+    // the bits before the first whole byte
+    let head = ((8 - offset % 8) % 8).min(length);
+    let mut count = BitmapIter::new(values, offset, head).filter(|x| *x).count();
+
+    let start = (offset + head) / 8;
+    let bytes = (length - head) / 8;
+    count += values[start..start + bytes]
+        .iter()
+        .map(|x| x.count_ones() as usize)
+        .sum::<usize>();
+
+    // the bits after the last whole byte
+    let tail = length - head - bytes * 8;
+    count
+        + BitmapIter::new(values, offset + head + bytes * 8, tail)
+            .filter(|x| *x)
+            .count()
 }
 
 impl<'a> FilteredHybridEncoded<'a> {
@@ -272,3 +288,21 @@ impl<'a, I: Iterator<Item = Result<HybridEncoded<'a>, Error>>> Iterator
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_count() {
+        let values = [0b10110101u8, 0b11111111, 0b00000001, 0b10000000];
+        for offset in 0..32 {
+            for length in 0..(32 - offset) {
+                let expected = BitmapIter::new(&values, offset, length)
+                    .filter(|x| *x)
+                    .count();
+                assert_eq!(is_set_count(&values, offset, length), expected);
+            }
+        }
+    }
+}
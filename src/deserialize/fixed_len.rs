@@ -1,12 +1,12 @@
 use crate::{
-    encoding::hybrid_rle,
+    encoding::{byte_stream_split, hybrid_rle},
     error::Error,
     page::{split_buffer, DataPage},
     parquet_bridge::{Encoding, Repetition},
     schema::types::PhysicalType,
 };
 
-use super::utils;
+use super::{utils, DeltaBinaryIter};
 
 #[derive(Debug)]
 pub struct FixexBinaryIter<'a> {
@@ -59,6 +59,13 @@ pub enum FixedLenBinaryPageState<'a, P> {
     Required(FixexBinaryIter<'a>),
     RequiredDictionary(Dictionary<'a, P>),
     OptionalDictionary(utils::DefLevelsDecoder<'a>, Dictionary<'a, P>),
+    RequiredByteStreamSplit(byte_stream_split::FixedLenDecoder<'a>),
+    OptionalByteStreamSplit(
+        utils::DefLevelsDecoder<'a>,
+        byte_stream_split::FixedLenDecoder<'a>,
+    ),
+    RequiredDeltaByteArray(DeltaBinaryIter<'a>),
+    OptionalDeltaByteArray(utils::DefLevelsDecoder<'a>, DeltaBinaryIter<'a>),
 }
 
 impl<'a, P> FixedLenBinaryPageState<'a, P> {
@@ -101,6 +108,33 @@ impl<'a, P> FixedLenBinaryPageState<'a, P> {
 
                 Ok(Self::Required(values))
             }
+            (Encoding::ByteStreamSplit, _, true) => {
+                let (_, _, values) = split_buffer(page)?;
+
+                Ok(Self::OptionalByteStreamSplit(
+                    utils::DefLevelsDecoder::try_new(page)?,
+                    byte_stream_split::FixedLenDecoder::try_new(values, size)?,
+                ))
+            }
+            (Encoding::ByteStreamSplit, _, false) => {
+                let (_, _, values) = split_buffer(page)?;
+
+                byte_stream_split::FixedLenDecoder::try_new(values, size)
+                    .map(Self::RequiredByteStreamSplit)
+            }
+            (Encoding::DeltaByteArray, _, true) => {
+                let (_, _, values) = split_buffer(page)?;
+
+                Ok(Self::OptionalDeltaByteArray(
+                    utils::DefLevelsDecoder::try_new(page)?,
+                    DeltaBinaryIter::try_new(values)?,
+                ))
+            }
+            (Encoding::DeltaByteArray, _, false) => {
+                let (_, _, values) = split_buffer(page)?;
+
+                DeltaBinaryIter::try_new(values).map(Self::RequiredDeltaByteArray)
+            }
             _ => Err(Error::FeatureNotSupported(format!(
                 "Viewing page for encoding {:?} for binary type",
                 page.encoding(),
@@ -1,15 +1,21 @@
 mod binary;
+mod bitmap;
 mod boolean;
+mod delta;
 mod filtered_rle;
 mod fixed_len;
 mod hybrid_rle;
 mod native;
 mod utils;
+mod values;
 
 pub use binary::*;
+pub use bitmap::{copy_bits, decode_runs_into_bitmap, set_bits};
 pub use boolean::*;
+pub use delta::*;
 pub use filtered_rle::*;
 pub use fixed_len::*;
 pub use hybrid_rle::*;
 pub use native::*;
 pub use utils::{DefLevelsDecoder, OptionalValues, SliceFilteredIter};
+pub use values::ColumnValue;
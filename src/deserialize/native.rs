@@ -1,12 +1,12 @@
 use crate::{
-    encoding::hybrid_rle,
+    encoding::{byte_stream_split, hybrid_rle, plain},
     error::Error,
     page::{split_buffer, DataPage},
     parquet_bridge::{Encoding, Repetition},
     types::{decode, NativeType},
 };
 
-use super::utils;
+use super::{utils, DeltaBinaryPackedIter};
 
 /// Typedef of an iterator over PLAIN page values
 pub type Casted<'a, T> = std::iter::Map<std::slice::ChunksExact<'a, u8>, fn(&'a [u8]) -> T>;
@@ -14,11 +14,7 @@ pub type Casted<'a, T> = std::iter::Map<std::slice::ChunksExact<'a, u8>, fn(&'a
 /// Views the values of the data page as [`Casted`] to [`NativeType`].
 pub fn native_cast<T: NativeType>(page: &DataPage) -> Result<Casted<T>, Error> {
     let (_, _, values) = split_buffer(page)?;
-    if values.len() % std::mem::size_of::<T>() != 0 {
-        return Err(Error::oos(
-            "A primitive page data's len must be a multiple of the type",
-        ));
-    }
+    plain::num_values::<T>(values)?;
 
     Ok(values
         .chunks_exact(std::mem::size_of::<T>())
@@ -63,6 +59,17 @@ where
     RequiredDictionary(Dictionary<'a, P>),
     /// A page of optional, dictionary-encoded values
     OptionalDictionary(utils::DefLevelsDecoder<'a>, Dictionary<'a, P>),
+    /// A page of required, byte-stream-split-encoded values
+    RequiredByteStreamSplit(byte_stream_split::Decoder<'a, T>),
+    /// A page of optional, byte-stream-split-encoded values
+    OptionalByteStreamSplit(
+        utils::DefLevelsDecoder<'a>,
+        byte_stream_split::Decoder<'a, T>,
+    ),
+    /// A page of required, `DELTA_BINARY_PACKED`-encoded values
+    RequiredDeltaBinaryPacked(DeltaBinaryPackedIter<'a, T>),
+    /// A page of optional, `DELTA_BINARY_PACKED`-encoded values
+    OptionalDeltaBinaryPacked(utils::DefLevelsDecoder<'a>, DeltaBinaryPackedIter<'a, T>),
 }
 
 impl<'a, T: NativeType, P> NativePageState<'a, T, P> {
@@ -90,6 +97,32 @@ impl<'a, T: NativeType, P> NativePageState<'a, T, P> {
                 Ok(Self::Optional(validity, values))
             }
             (Encoding::Plain, _, false) => native_cast(page).map(Self::Required),
+            (Encoding::ByteStreamSplit, _, true) => {
+                let (_, _, values) = split_buffer(page)?;
+
+                Ok(Self::OptionalByteStreamSplit(
+                    utils::DefLevelsDecoder::try_new(page)?,
+                    byte_stream_split::Decoder::try_new(values)?,
+                ))
+            }
+            (Encoding::ByteStreamSplit, _, false) => {
+                let (_, _, values) = split_buffer(page)?;
+
+                byte_stream_split::Decoder::try_new(values).map(Self::RequiredByteStreamSplit)
+            }
+            (Encoding::DeltaBinaryPacked, _, true) => {
+                let (_, _, values) = split_buffer(page)?;
+
+                Ok(Self::OptionalDeltaBinaryPacked(
+                    utils::DefLevelsDecoder::try_new(page)?,
+                    DeltaBinaryPackedIter::try_new(values)?,
+                ))
+            }
+            (Encoding::DeltaBinaryPacked, _, false) => {
+                let (_, _, values) = split_buffer(page)?;
+
+                DeltaBinaryPackedIter::try_new(values).map(Self::RequiredDeltaBinaryPacked)
+            }
             _ => Err(Error::FeatureNotSupported(format!(
                 "Viewing page for encoding {:?} for native type {}",
                 page.encoding(),
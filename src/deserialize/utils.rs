@@ -1,13 +1,17 @@
 use std::collections::VecDeque;
 
 use crate::{
-    encoding::hybrid_rle::{self, HybridRleDecoder},
+    encoding::{
+        get_bit_width,
+        hybrid_rle::{self, HybridRleDecoder},
+        legacy_bitpacked, Encoding,
+    },
     error::Error,
     indexes::Interval,
     page::{split_buffer, DataPage},
-    read::levels::get_bit_width,
 };
 
+use super::bitmap::{check_capacity, decode_bools_into_bitmap, decode_runs_into_bitmap};
 use super::hybrid_rle::{HybridDecoderBitmapIter, HybridRleIter};
 
 pub(super) fn dict_indices_decoder(page: &DataPage) -> Result<hybrid_rle::HybridRleDecoder, Error> {
@@ -35,6 +39,9 @@ pub enum DefLevelsDecoder<'a> {
     Bitmap(HybridDecoderBitmapIter<'a>),
     /// When the maximum definition level is larger than 1
     Levels(HybridRleDecoder<'a>, u32),
+    /// When the definition levels are encoded with the deprecated `BIT_PACKED` encoding.
+    /// Contains the decoded levels and the maximum definition level.
+    BitPacked(legacy_bitpacked::Decoder<'a>, u32),
 }
 
 impl<'a> DefLevelsDecoder<'a> {
@@ -42,7 +49,14 @@ impl<'a> DefLevelsDecoder<'a> {
         let (_, def_levels, _) = split_buffer(page)?;
 
         let max_def_level = page.descriptor.max_def_level;
-        Ok(if max_def_level == 1 {
+        Ok(if page.definition_level_encoding() == Encoding::BitPacked {
+            let iter = legacy_bitpacked::Decoder::try_new(
+                def_levels,
+                get_bit_width(max_def_level) as usize,
+                page.num_values(),
+            )?;
+            Self::BitPacked(iter, max_def_level as u32)
+        } else if max_def_level == 1 {
             let iter = hybrid_rle::Decoder::new(def_levels, 1);
             let iter = HybridRleIter::new(iter, page.num_values());
             Self::Bitmap(iter)
@@ -55,6 +69,42 @@ impl<'a> DefLevelsDecoder<'a> {
             Self::Levels(iter, max_def_level as u32)
         })
     }
+
+    /// Returns the number of definition levels remaining
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Bitmap(iter) => iter.len(),
+            Self::Levels(iter, _) => iter.size_hint().0,
+            Self::BitPacked(iter, _) => iter.size_hint().0,
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Decodes the validity of the remaining values (whether their definition level is the
+    /// maximum definition level) into `bitmap` starting at bit `offset`, LSB first.
+    /// Returns the number of bits written.
+    /// # Implementation
+    /// With a maximum definition level of 1, the runs of the levels are copied
+    /// (see [`decode_runs_into_bitmap`]).
+    /// # Errors
+    /// Errors iff `bitmap` is not large enough or the levels are invalid.
+    pub fn decode_into_bitmap(self, bitmap: &mut [u8], offset: usize) -> Result<usize, Error> {
+        check_capacity(bitmap, offset + self.len())?;
+        match self {
+            Self::Bitmap(iter) => decode_runs_into_bitmap(iter, bitmap, offset),
+            Self::Levels(iter, max) => {
+                decode_bools_into_bitmap(iter.map(|x| x.map(|x| x == max)), bitmap, offset)
+            }
+            Self::BitPacked(iter, max) => {
+                decode_bools_into_bitmap(iter.map(|x| Ok(x == max)), bitmap, offset)
+            }
+        }
+    }
 }
 
 /// Iterator adapter to convert an iterator of non-null values and an iterator over validity
@@ -94,6 +144,8 @@ impl<T, V: Iterator<Item = Result<bool, Error>>, I: Iterator<Item = T>> Iterator
 ///
 /// This iterator is best used with iterators that implement `nth` since skipping items
 /// allows this iterator to skip sequences of items without having to call each of them.
+/// The decoders of this crate (e.g. [`HybridRleDecoder`]) implement `nth` by skipping
+/// whole runs without decoding them.
 #[derive(Debug, Clone)]
 pub struct SliceFilteredIter<I> {
     iter: I,
@@ -174,4 +226,26 @@ mod test {
         assert_eq!(expected, a.by_ref().collect::<Vec<_>>());
         assert_eq!((0, Some(0)), a.size_hint());
     }
+
+    #[test]
+    fn hybrid_rle() -> Result<(), Error> {
+        let values = (0..1000u32).map(|x| x % 7).collect::<Vec<_>>();
+        let mut buffer = vec![];
+        hybrid_rle::encode_u32(&mut buffer, values.iter().copied(), 3).unwrap();
+
+        let intervals = vec![
+            Interval::new(3, 2),
+            Interval::new(100, 50),
+            Interval::new(998, 2),
+        ];
+        let iter = HybridRleDecoder::try_new(&buffer, 3, values.len())?;
+        let iter = SliceFilteredIter::new(iter, intervals.iter().copied().collect());
+
+        let expected = intervals
+            .into_iter()
+            .flat_map(|interval| values[interval.start..interval.start + interval.length].to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(expected, iter.collect::<Result<Vec<_>, _>>()?);
+        Ok(())
+    }
 }
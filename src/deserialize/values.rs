@@ -0,0 +1,342 @@
+use crate::{
+    encoding::{byte_stream_split, get_length, hybrid_rle, plain, plain_byte_array, Encoding},
+    error::Error,
+    schema::types::PhysicalType,
+    types::NativeType,
+    FallibleStreamingIterator,
+};
+
+use super::{DeltaBinaryIter, DeltaBinaryPackedIter, DeltaLengthBinaryIter};
+
+/// A type that the values of a physical type are decoded to, e.g. by
+/// [`crate::read::ColumnReader`]:
+/// * `BOOLEAN` to `bool`
+/// * `INT32`, `INT64`, `INT96`, `FLOAT` and `DOUBLE` to their [`NativeType`]
+/// * `BYTE_ARRAY` and `FIXED_LEN_BYTE_ARRAY` to `Vec<u8>`
+pub trait ColumnValue: std::fmt::Debug + Clone + Send + Sync + 'static {
+    /// Whether values of `physical_type` are decoded to this type
+    fn is_compatible(physical_type: &PhysicalType) -> bool;
+
+    /// Decodes `num_values` values of `physical_type` encoded with `encoding` (other than the
+    /// dictionary encodings) from `values`, appending them to `decoded`.
+    /// # Errors
+    /// Errors iff `encoding` is not supported for this type or `values` has less than
+    /// `num_values` valid values.
+    fn decode(
+        values: &[u8],
+        encoding: Encoding,
+        physical_type: &PhysicalType,
+        num_values: usize,
+        decoded: &mut Vec<Self>,
+    ) -> Result<(), Error>;
+}
+
+fn not_supported<T>(encoding: Encoding) -> Error {
+    Error::FeatureNotSupported(format!(
+        "Decoding values of type {} encoded with {:?}",
+        std::any::type_name::<T>(),
+        encoding
+    ))
+}
+
+fn missing_values() -> Error {
+    Error::oos("The page has less values than declared by its definition levels")
+}
+
+/// Appends exactly `num_values` items of `iter` to `decoded`.
+fn extend_exact<T, I: Iterator<Item = Result<T, Error>>>(
+    iter: I,
+    num_values: usize,
+    decoded: &mut Vec<T>,
+) -> Result<(), Error> {
+    decoded.reserve(num_values);
+    let length = decoded.len();
+    for value in iter.take(num_values) {
+        decoded.push(value?);
+    }
+    if decoded.len() - length != num_values {
+        return Err(missing_values());
+    }
+    Ok(())
+}
+
+/// Appends `num_values` items to `decoded`, filled in by `decode`.
+fn extend_with<T: Clone, F: FnOnce(&mut [T]) -> Result<(), Error>>(
+    decode: F,
+    num_values: usize,
+    decoded: &mut Vec<T>,
+    default: T,
+) -> Result<(), Error> {
+    decoded.try_reserve(num_values)?;
+    let length = decoded.len();
+    decoded.resize(length + num_values, default);
+    let result = decode(&mut decoded[length..]);
+    if result.is_err() {
+        decoded.truncate(length);
+    }
+    result
+}
+
+fn decode_native<T: NativeType + Default>(
+    values: &[u8],
+    encoding: Encoding,
+    num_values: usize,
+    decoded: &mut Vec<T>,
+) -> Result<(), Error> {
+    match encoding {
+        Encoding::Plain => extend_with(
+            |out| plain::decode(values, out),
+            num_values,
+            decoded,
+            T::default(),
+        ),
+        Encoding::ByteStreamSplit => extend_exact(
+            byte_stream_split::Decoder::<T>::try_new(values)?.map(Ok),
+            num_values,
+            decoded,
+        ),
+        Encoding::DeltaBinaryPacked => extend_exact(
+            DeltaBinaryPackedIter::<T>::try_new(values)?,
+            num_values,
+            decoded,
+        ),
+        _ => Err(not_supported::<T>(encoding)),
+    }
+}
+
+macro_rules! native_value {
+    ($type:ty) => {
+        impl ColumnValue for $type {
+            fn is_compatible(physical_type: &PhysicalType) -> bool {
+                *physical_type == <$type as NativeType>::TYPE
+            }
+
+            fn decode(
+                values: &[u8],
+                encoding: Encoding,
+                _: &PhysicalType,
+                num_values: usize,
+                decoded: &mut Vec<Self>,
+            ) -> Result<(), Error> {
+                decode_native(values, encoding, num_values, decoded)
+            }
+        }
+    };
+}
+
+native_value!(i32);
+native_value!(i64);
+native_value!([u32; 3]);
+native_value!(f32);
+native_value!(f64);
+
+impl ColumnValue for bool {
+    fn is_compatible(physical_type: &PhysicalType) -> bool {
+        *physical_type == PhysicalType::Boolean
+    }
+
+    fn decode(
+        values: &[u8],
+        encoding: Encoding,
+        _: &PhysicalType,
+        num_values: usize,
+        decoded: &mut Vec<Self>,
+    ) -> Result<(), Error> {
+        match encoding {
+            Encoding::Plain => extend_with(
+                |out| plain::decode_bool(values, out),
+                num_values,
+                decoded,
+                false,
+            ),
+            Encoding::Rle => {
+                // RLE-encoded booleans are prefixed by their length
+                let values = get_length(values)
+                    .and_then(|length| values.get(4..4 + length))
+                    .ok_or_else(|| {
+                        Error::oos("The number of bytes declared in RLE boolean values is higher than the page size")
+                    })?;
+                let values = hybrid_rle::HybridRleDecoder::try_new(values, 1, num_values)?;
+                extend_exact(values.map(|x| x.map(|x| x == 1)), num_values, decoded)
+            }
+            _ => Err(not_supported::<Self>(encoding)),
+        }
+    }
+}
+
+impl ColumnValue for Vec<u8> {
+    fn is_compatible(physical_type: &PhysicalType) -> bool {
+        matches!(
+            physical_type,
+            PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_)
+        )
+    }
+
+    fn decode(
+        values: &[u8],
+        encoding: Encoding,
+        physical_type: &PhysicalType,
+        num_values: usize,
+        decoded: &mut Vec<Self>,
+    ) -> Result<(), Error> {
+        match (encoding, physical_type) {
+            (Encoding::Plain, PhysicalType::ByteArray) => extend_exact(
+                plain_byte_array::BinaryIter::new(values, Some(num_values))
+                    .map(|x| x.map(|x| x.to_vec())),
+                num_values,
+                decoded,
+            ),
+            (Encoding::Plain, PhysicalType::FixedLenByteArray(size)) => {
+                let length = num_values
+                    .checked_mul(*size)
+                    .filter(|length| *length <= values.len())
+                    .ok_or_else(missing_values)?;
+                let mut items = vec![0; length];
+                plain::decode_fixed_len(values, *size, &mut items)?;
+                decoded.extend(items.chunks_exact(*size).map(|x| x.to_vec()));
+                Ok(())
+            }
+            (Encoding::ByteStreamSplit, PhysicalType::FixedLenByteArray(size)) => {
+                let mut values = byte_stream_split::FixedLenDecoder::try_new(values, *size)?;
+                let length = decoded.len();
+                while decoded.len() - length < num_values {
+                    let value = values.next()?.ok_or_else(missing_values)?;
+                    decoded.push(value.to_vec());
+                }
+                Ok(())
+            }
+            (Encoding::DeltaLengthByteArray, PhysicalType::ByteArray) => extend_exact(
+                DeltaLengthBinaryIter::try_new(values)?.map(|x| x.map(|x| x.to_vec())),
+                num_values,
+                decoded,
+            ),
+            (Encoding::DeltaByteArray, _) => {
+                extend_exact(DeltaBinaryIter::try_new(values)?, num_values, decoded)
+            }
+            _ => Err(not_supported::<Self>(encoding)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{byte_stream_split, delta_bitpacked, delta_byte_array};
+
+    #[test]
+    fn native() -> Result<(), Error> {
+        let values = vec![1i32, -2, 3];
+        let plain = values
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect::<Vec<_>>();
+
+        let mut decoded = vec![];
+        i32::decode(
+            &plain,
+            Encoding::Plain,
+            &PhysicalType::Int32,
+            3,
+            &mut decoded,
+        )?;
+        assert_eq!(decoded, values);
+        assert!(i32::decode(
+            &plain,
+            Encoding::Plain,
+            &PhysicalType::Int32,
+            4,
+            &mut vec![]
+        )
+        .is_err());
+
+        let mut split = vec![];
+        byte_stream_split::encode(&values, &mut split);
+        let mut decoded = vec![];
+        i32::decode(
+            &split,
+            Encoding::ByteStreamSplit,
+            &PhysicalType::Int32,
+            3,
+            &mut decoded,
+        )?;
+        assert_eq!(decoded, values);
+
+        let mut delta = vec![];
+        delta_bitpacked::encode_i32(values.iter().copied(), &mut delta);
+        let mut decoded = vec![];
+        i64::decode(
+            &delta,
+            Encoding::DeltaBinaryPacked,
+            &PhysicalType::Int64,
+            3,
+            &mut decoded,
+        )?;
+        assert_eq!(decoded, vec![1i64, -2, 3]);
+
+        assert!(f32::decode(&plain, Encoding::Rle, &PhysicalType::Float, 3, &mut vec![]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn binary() -> Result<(), Error> {
+        let values = vec![b"aa".to_vec(), b"ab".to_vec(), b"b".to_vec()];
+
+        let mut plain = vec![];
+        for value in &values {
+            plain.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            plain.extend_from_slice(value);
+        }
+        let mut decoded = vec![];
+        Vec::<u8>::decode(
+            &plain,
+            Encoding::Plain,
+            &PhysicalType::ByteArray,
+            3,
+            &mut decoded,
+        )?;
+        assert_eq!(decoded, values);
+
+        let mut delta = vec![];
+        delta_byte_array::encode(values.iter().map(|x| x.as_slice()), &mut delta);
+        let mut decoded = vec![];
+        Vec::<u8>::decode(
+            &delta,
+            Encoding::DeltaByteArray,
+            &PhysicalType::ByteArray,
+            3,
+            &mut decoded,
+        )?;
+        assert_eq!(decoded, values);
+
+        let mut decoded = vec![];
+        let physical_type = PhysicalType::FixedLenByteArray(2);
+        Vec::<u8>::decode(b"aabbcc", Encoding::Plain, &physical_type, 3, &mut decoded)?;
+        assert_eq!(
+            decoded,
+            vec![b"aa".to_vec(), b"bb".to_vec(), b"cc".to_vec()]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn boolean() -> Result<(), Error> {
+        let mut decoded = vec![];
+        bool::decode(
+            &[0b101],
+            Encoding::Plain,
+            &PhysicalType::Boolean,
+            3,
+            &mut decoded,
+        )?;
+        assert_eq!(decoded, vec![true, false, true]);
+        assert!(bool::decode(
+            &[0b101],
+            Encoding::Plain,
+            &PhysicalType::Boolean,
+            9,
+            &mut vec![]
+        )
+        .is_err());
+        Ok(())
+    }
+}
@@ -25,6 +25,18 @@ fn decode_pack<T: Unpackable>(packed: &[u8], num_bits: usize, unpacked: &mut T::
     }
 }
 
+/// Unpacks a pack straight into `unpacked`, whose length must be `T::Unpacked::LENGTH`.
+#[inline]
+fn decode_pack_into<T: Unpackable>(packed: &[u8], num_bits: usize, unpacked: &mut [T]) {
+    if packed.len() < T::Unpacked::LENGTH * num_bits / 8 {
+        let mut buf = T::Packed::zero();
+        buf.as_mut()[..packed.len()].copy_from_slice(packed);
+        T::unpack_slice(buf.as_ref(), num_bits, unpacked)
+    } else {
+        T::unpack_slice(packed, num_bits, unpacked)
+    }
+}
+
 impl<'a, T: Unpackable> Decoder<'a, T> {
     /// Returns a [`Decoder`] with `T` encoded in `packed` with `num_bits`.
     pub fn try_new(packed: &'a [u8], num_bits: usize, mut length: usize) -> Result<Self, Error> {
@@ -57,6 +69,75 @@ impl<'a, T: Unpackable> Decoder<'a, T> {
             current_pack_index: 0,
         })
     }
+
+    /// Decodes up to `out.len()` items into `out`, returning the number of items decoded.
+    /// # Implementation
+    /// Complete packs are unpacked directly into `out`.
+    pub fn decode_into(&mut self, out: &mut [T]) -> usize {
+        let length = out.len().min(self.remaining);
+        let out = &mut out[..length];
+
+        // the items remaining in the current pack
+        let available = (T::Unpacked::LENGTH - self.current_pack_index).min(length);
+        out[..available].copy_from_slice(
+            &self.unpacked.as_ref()[self.current_pack_index..self.current_pack_index + available],
+        );
+        self.current_pack_index += available;
+        let mut written = available;
+
+        // complete packs
+        while length - written >= T::Unpacked::LENGTH {
+            // unwrap is ok: `remaining` is bounded by the number of packs
+            let packed = self.packed.next().unwrap();
+            decode_pack_into(
+                packed,
+                self.num_bits,
+                &mut out[written..written + T::Unpacked::LENGTH],
+            );
+            written += T::Unpacked::LENGTH;
+        }
+
+        // the start of the last pack
+        if written < length {
+            // unwrap is ok: `remaining` is bounded by the number of packs
+            let packed = self.packed.next().unwrap();
+            decode_pack::<T>(packed, self.num_bits, &mut self.unpacked);
+            self.current_pack_index = length - written;
+            out[written..].copy_from_slice(&self.unpacked.as_ref()[..length - written]);
+        }
+        self.remaining -= length;
+
+        // like `next`, the next pack is unpacked once the current one is consumed
+        if self.current_pack_index == T::Unpacked::LENGTH {
+            if let Some(packed) = self.packed.next() {
+                decode_pack::<T>(packed, self.num_bits, &mut self.unpacked);
+                self.current_pack_index = 0;
+            }
+        }
+        length
+    }
+
+    /// Skips `n` items, or all remaining items if there are less than `n`.
+    /// Packs that are fully skipped are not unpacked.
+    pub fn skip_values(&mut self, n: usize) {
+        let n = n.min(self.remaining);
+        self.remaining -= n;
+
+        let index = self.current_pack_index + n;
+        if index < T::Unpacked::LENGTH {
+            self.current_pack_index = index;
+            return;
+        }
+        // the current pack is consumed; jump over the remaining fully skipped packs
+        let packs = index / T::Unpacked::LENGTH;
+        if let Some(packed) = self.packed.nth(packs - 1) {
+            decode_pack::<T>(packed, self.num_bits, &mut self.unpacked);
+            self.current_pack_index = index % T::Unpacked::LENGTH;
+        } else {
+            // there are no more items
+            self.current_pack_index = T::Unpacked::LENGTH;
+        }
+    }
 }
 
 impl<'a, T: Unpackable> Iterator for Decoder<'a, T> {
@@ -79,6 +160,12 @@ impl<'a, T: Unpackable> Iterator for Decoder<'a, T> {
         Some(result)
     }
 
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.skip_values(n);
+        self.next()
+    }
+
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
         (self.remaining, Some(self.remaining))
@@ -209,4 +296,68 @@ mod tests {
         // zero num_bits
         assert!(Decoder::<u64>::try_new(&[1], 0, 1).is_err());
     }
+
+    #[test]
+    fn skip() {
+        let (num_bits, expected, data) = case1();
+
+        for n in [
+            0,
+            1,
+            7,
+            31,
+            32,
+            33,
+            64,
+            70,
+            expected.len() - 1,
+            expected.len(),
+            1000,
+        ] {
+            let mut decoder = Decoder::<u32>::try_new(&data, num_bits, expected.len()).unwrap();
+            decoder.skip_values(n);
+            assert_eq!(decoder.size_hint().0, expected.len().saturating_sub(n));
+            let decoded = decoder.collect::<Vec<_>>();
+            assert_eq!(
+                decoded,
+                expected.iter().skip(n).copied().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn decode_into() {
+        let (num_bits, expected, data) = case1();
+
+        for chunk in [1, 3, 31, 32, 33, 64, 100] {
+            let mut decoder = Decoder::<u32>::try_new(&data, num_bits, expected.len()).unwrap();
+            let mut result = vec![];
+            let mut buffer = vec![0; chunk];
+            loop {
+                let decoded = decoder.decode_into(&mut buffer);
+                if decoded == 0 {
+                    break;
+                }
+                result.extend_from_slice(&buffer[..decoded]);
+                // interleave with the iterator
+                result.extend(decoder.next());
+            }
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn decode_into_u64() {
+        let values = (0..200u64).map(|x| x * 3 % 128).collect::<Vec<_>>();
+        let num_bits = 7;
+        let mut data = vec![0; 256 * 7 / 8];
+        crate::encoding::bitpacked::encode(&values, num_bits, &mut data);
+
+        let mut decoder = Decoder::<u64>::try_new(&data, num_bits, values.len()).unwrap();
+        let mut result = vec![0; 250];
+        assert_eq!(decoder.decode_into(&mut result[..5]), 5);
+        assert_eq!(decoder.decode_into(&mut result[5..]), 195);
+        assert_eq!(&result[..200], values);
+        assert_eq!(decoder.next(), None);
+    }
 }
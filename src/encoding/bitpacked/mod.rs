@@ -103,6 +103,15 @@ pub trait Unpackable: Copy + Sized + Default {
     type Unpacked: Unpacked<Self>;
     fn unpack(packed: &[u8], num_bits: usize, unpacked: &mut Self::Unpacked);
     fn pack(unpacked: &Self::Unpacked, num_bits: usize, packed: &mut [u8]);
+
+    /// Unpacks a complete pack into `unpacked`, whose length must be `Self::Unpacked::LENGTH`.
+    /// The default implementation unpacks into an intermediary [`Unpackable::Unpacked`].
+    #[inline]
+    fn unpack_slice(packed: &[u8], num_bits: usize, unpacked: &mut [Self]) {
+        let mut buffer = Self::Unpacked::zero();
+        Self::unpack(packed, num_bits, &mut buffer);
+        unpacked.copy_from_slice(buffer.as_ref());
+    }
 }
 
 impl Unpackable for u8 {
@@ -114,6 +123,11 @@ impl Unpackable for u8 {
         unpack::unpack8(packed, unpacked, num_bits)
     }
 
+    #[inline]
+    fn unpack_slice(packed: &[u8], num_bits: usize, unpacked: &mut [Self]) {
+        unpack::unpack8(packed, unpacked.try_into().unwrap(), num_bits)
+    }
+
     #[inline]
     fn pack(packed: &Self::Unpacked, num_bits: usize, unpacked: &mut [u8]) {
         pack::pack8(packed, unpacked, num_bits)
@@ -129,6 +143,11 @@ impl Unpackable for u16 {
         unpack::unpack16(packed, unpacked, num_bits)
     }
 
+    #[inline]
+    fn unpack_slice(packed: &[u8], num_bits: usize, unpacked: &mut [Self]) {
+        unpack::unpack16(packed, unpacked.try_into().unwrap(), num_bits)
+    }
+
     #[inline]
     fn pack(packed: &Self::Unpacked, num_bits: usize, unpacked: &mut [u8]) {
         pack::pack16(packed, unpacked, num_bits)
@@ -144,6 +163,11 @@ impl Unpackable for u32 {
         unpack::unpack32(packed, unpacked, num_bits)
     }
 
+    #[inline]
+    fn unpack_slice(packed: &[u8], num_bits: usize, unpacked: &mut [Self]) {
+        unpack::unpack32(packed, unpacked.try_into().unwrap(), num_bits)
+    }
+
     #[inline]
     fn pack(packed: &Self::Unpacked, num_bits: usize, unpacked: &mut [u8]) {
         pack::pack32(packed, unpacked, num_bits)
@@ -159,6 +183,11 @@ impl Unpackable for u64 {
         unpack::unpack64(packed, unpacked, num_bits)
     }
 
+    #[inline]
+    fn unpack_slice(packed: &[u8], num_bits: usize, unpacked: &mut [Self]) {
+        unpack::unpack64(packed, unpacked.try_into().unwrap(), num_bits)
+    }
+
     #[inline]
     fn pack(packed: &Self::Unpacked, num_bits: usize, unpacked: &mut [u8]) {
         pack::pack64(packed, unpacked, num_bits)
@@ -0,0 +1,201 @@
+use std::marker::PhantomData;
+
+use crate::error::Error;
+use crate::types::{decode, NativeType};
+use crate::FallibleStreamingIterator;
+
+/// The size of the stack buffer items are gathered into: the size of the largest [`NativeType`]
+/// (`INT96`) and of common `FIXED_LEN_BYTE_ARRAY`s (e.g. `DECIMAL(38, _)` or `UUID`)
+const STACK_SIZE: usize = 16;
+
+/// Validates that `values` contains a whole number of items of `size` bytes and
+/// returns the number of items.
+fn try_num_elements(values: &[u8], size: usize) -> Result<usize, Error> {
+    if size == 0 {
+        return Err(Error::InvalidParameter(
+            "Byte stream split requires items with at least one byte".to_string(),
+        ));
+    }
+    let num_elements = values.len() / size;
+    if num_elements * size != values.len() {
+        return Err(Error::oos(
+            "A byte stream split page data's len must be a multiple of the type",
+        ));
+    }
+    Ok(num_elements)
+}
+
+/// Gathers the bytes of the item at position `index` from each of the streams into `item`.
+#[inline]
+fn gather(values: &[u8], num_elements: usize, index: usize, item: &mut [u8]) {
+    item.iter_mut()
+        .enumerate()
+        .for_each(|(stream, byte)| *byte = values[stream * num_elements + index]);
+}
+
+/// Decodes [Byte stream split](https://github.com/apache/parquet-format/blob/master/Encodings.md#byte-stream-split-byte_stream_split--9)
+/// values into [`NativeType`]. Implements `Iterator<Item = T>`.
+/// # Implementation
+/// The decoder borrows the page's buffer and gathers each value from the streams on demand,
+/// on the stack.
+#[derive(Debug, Clone)]
+pub struct Decoder<'a, T: NativeType> {
+    values: &'a [u8],
+    num_elements: usize,
+    current: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: NativeType> Decoder<'a, T> {
+    /// Returns a new [`Decoder`] over `values`.
+    /// # Errors
+    /// Errors iff `values.len()` is not a multiple of the size of `T`.
+    pub fn try_new(values: &'a [u8]) -> Result<Self, Error> {
+        let size = std::mem::size_of::<T>();
+        let num_elements = try_num_elements(values, size)?;
+        Ok(Self {
+            values,
+            num_elements,
+            current: 0,
+            phantom: PhantomData,
+        })
+    }
+
+    /// The number of values remaining in this decoder
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.num_elements - self.current
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<'a, T: NativeType> Iterator for Decoder<'a, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current == self.num_elements {
+            return None;
+        }
+        let mut item = [0; STACK_SIZE];
+        let item = &mut item[..std::mem::size_of::<T>()];
+        gather(self.values, self.num_elements, self.current, item);
+        self.current += 1;
+        Some(decode(item))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.current = self.current.saturating_add(n).min(self.num_elements);
+        self.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+impl<'a, T: NativeType> ExactSizeIterator for Decoder<'a, T> {}
+
+/// Decodes [Byte stream split](https://github.com/apache/parquet-format/blob/master/Encodings.md#byte-stream-split-byte_stream_split--9)
+/// values of a fixed number of bytes (e.g. `FIXED_LEN_BYTE_ARRAY`).
+/// # Implementation
+/// Since the bytes of a value are not contiguous in the page, this is a
+/// [`FallibleStreamingIterator`] that gathers each value into an internal buffer of `size` bytes,
+/// only allocated when items are larger than 16 bytes.
+#[derive(Debug, Clone)]
+pub struct FixedLenDecoder<'a> {
+    values: &'a [u8],
+    num_elements: usize,
+    // invariant: `current <= num_elements`. It is the index of the next item to gather
+    current: usize,
+    size: usize,
+    // the item, when `size <= STACK_SIZE`
+    stack: [u8; STACK_SIZE],
+    // the item, when `size > STACK_SIZE`
+    heap: Vec<u8>,
+    has_item: bool,
+}
+
+impl<'a> FixedLenDecoder<'a> {
+    /// Returns a new [`FixedLenDecoder`] over `values` of items with `size` bytes.
+    /// # Errors
+    /// Errors iff `size` is zero or `values.len()` is not a multiple of `size`.
+    pub fn try_new(values: &'a [u8], size: usize) -> Result<Self, Error> {
+        let num_elements = try_num_elements(values, size)?;
+        Ok(Self {
+            values,
+            num_elements,
+            current: 0,
+            size,
+            stack: [0; STACK_SIZE],
+            heap: if size > STACK_SIZE {
+                vec![0; size]
+            } else {
+                vec![]
+            },
+            has_item: false,
+        })
+    }
+
+    /// The number of bytes of each item
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    fn item(&self) -> &[u8] {
+        if self.size > STACK_SIZE {
+            &self.heap
+        } else {
+            &self.stack[..self.size]
+        }
+    }
+
+    /// The number of values remaining in this decoder
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.num_elements - self.current
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<'a> FallibleStreamingIterator for FixedLenDecoder<'a> {
+    type Item = [u8];
+    type Error = Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), Self::Error> {
+        self.has_item = self.current < self.num_elements;
+        if self.has_item {
+            let item = if self.size > STACK_SIZE {
+                &mut self.heap
+            } else {
+                &mut self.stack[..self.size]
+            };
+            gather(self.values, self.num_elements, self.current, item);
+            self.current += 1;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&Self::Item> {
+        self.has_item.then(|| self.item())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
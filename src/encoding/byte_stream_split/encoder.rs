@@ -0,0 +1,51 @@
+use crate::error::Error;
+use crate::types::NativeType;
+
+/// Encodes a slice of [`NativeType`] according to parquet's `BYTE_STREAM_SPLIT` into `buffer`.
+/// # Implementation
+/// The `K` bytes of every value are scattered into `K` streams, which are appended
+/// to `buffer` back to back.
+pub fn encode<T: NativeType>(values: &[T], buffer: &mut Vec<u8>) {
+    let num_elements = values.len();
+    let start = buffer.len();
+    buffer.resize(start + std::mem::size_of_val(values), 0);
+    let streams = &mut buffer[start..];
+
+    values.iter().enumerate().for_each(|(i, value)| {
+        value
+            .to_le_bytes()
+            .as_ref()
+            .iter()
+            .enumerate()
+            .for_each(|(stream, byte)| streams[stream * num_elements + i] = *byte)
+    });
+}
+
+/// Encodes `values`, a sequence of concatenated items of `size` bytes each (e.g. the values
+/// of a `FIXED_LEN_BYTE_ARRAY` column), according to parquet's `BYTE_STREAM_SPLIT` into `buffer`.
+/// # Errors
+/// Errors iff `size` is zero or `values.len()` is not a multiple of `size`.
+pub fn encode_fixed_len(values: &[u8], size: usize, buffer: &mut Vec<u8>) -> Result<(), Error> {
+    if size == 0 || values.len() / size * size != values.len() {
+        return Err(Error::InvalidParameter(format!(
+            "Byte stream split requires the values' length ({}) to be a multiple of the item size ({})",
+            values.len(),
+            size
+        )));
+    }
+    let num_elements = values.len() / size;
+    let start = buffer.len();
+    buffer.resize(start + values.len(), 0);
+    let streams = &mut buffer[start..];
+
+    values
+        .chunks_exact(size)
+        .enumerate()
+        .for_each(|(i, value)| {
+            value
+                .iter()
+                .enumerate()
+                .for_each(|(stream, byte)| streams[stream * num_elements + i] = *byte)
+        });
+    Ok(())
+}
@@ -0,0 +1,100 @@
+// See https://github.com/apache/parquet-format/blob/master/Encodings.md#byte-stream-split-byte_stream_split--9
+mod decoder;
+mod encoder;
+
+pub use decoder::{Decoder, FixedLenDecoder};
+pub use encoder::{encode, encode_fixed_len};
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error;
+    use crate::FallibleStreamingIterator;
+
+    use super::*;
+
+    #[test]
+    fn from_spec() -> Result<(), Error> {
+        // the example of the spec: the bytes of each value are scattered to 4 streams
+        let data = [
+            0xAA, 0xBB, 0xCC, 0xDD, // element 0
+            0x00, 0x11, 0x22, 0x33, // element 1
+            0xA3, 0xB4, 0xC5, 0xD6, // element 2
+        ];
+        let expected = [
+            0xAA, 0x00, 0xA3, // stream of byte 0
+            0xBB, 0x11, 0xB4, // stream of byte 1
+            0xCC, 0x22, 0xC5, // stream of byte 2
+            0xDD, 0x33, 0xD6, // stream of byte 3
+        ];
+
+        let mut buffer = vec![];
+        encode_fixed_len(&data, 4, &mut buffer)?;
+        assert_eq!(buffer, expected);
+
+        let mut decoder = FixedLenDecoder::try_new(&buffer, 4)?;
+        let mut result = vec![];
+        while let Some(value) = decoder.next()? {
+            result.extend_from_slice(value);
+        }
+        assert_eq!(result, data);
+        Ok(())
+    }
+
+    #[test]
+    fn f32() -> Result<(), Error> {
+        let data = vec![1.0f32, -2.5, 0.0, f32::MAX, f32::MIN_POSITIVE, 3.25];
+
+        let mut buffer = vec![];
+        encode(&data, &mut buffer);
+        assert_eq!(buffer.len(), data.len() * 4);
+
+        let decoder = Decoder::<f32>::try_new(&buffer)?;
+        assert_eq!(decoder.len(), data.len());
+
+        let result = decoder.collect::<Vec<_>>();
+        assert_eq!(result, data);
+        Ok(())
+    }
+
+    #[test]
+    fn f64() -> Result<(), Error> {
+        let data = (0..1000).map(|x| x as f64 * 0.1).collect::<Vec<_>>();
+
+        let mut buffer = vec![];
+        encode(&data, &mut buffer);
+
+        let result = Decoder::<f64>::try_new(&buffer)?.collect::<Vec<_>>();
+        assert_eq!(result, data);
+        Ok(())
+    }
+
+    #[test]
+    fn extends_buffer() -> Result<(), Error> {
+        let data = vec![1i32, 2, 3];
+
+        let mut buffer = vec![1, 2];
+        encode(&data, &mut buffer);
+
+        let result = Decoder::<i32>::try_new(&buffer[2..])?.collect::<Vec<_>>();
+        assert_eq!(result, data);
+        Ok(())
+    }
+
+    #[test]
+    fn empty() -> Result<(), Error> {
+        let mut buffer = vec![];
+        encode::<f64>(&[], &mut buffer);
+        assert!(buffer.is_empty());
+
+        assert_eq!(Decoder::<f64>::try_new(&buffer)?.count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_length() {
+        assert!(Decoder::<f32>::try_new(&[0, 1, 2, 3, 4]).is_err());
+        assert!(FixedLenDecoder::try_new(&[0, 1, 2], 2).is_err());
+        assert!(FixedLenDecoder::try_new(&[], 0).is_err());
+        assert!(encode_fixed_len(&[0, 1, 2], 2, &mut vec![]).is_err());
+    }
+}
@@ -92,6 +92,51 @@ impl<'a> Block<'a> {
     }
 }
 
+impl<'a> Block<'a> {
+    /// Consumes up to `n` deltas of this block, without materializing them.
+    /// `value` is the value preceding the first delta and is updated with each delta;
+    /// `sum` is incremented by every value that precedes a consumed delta.
+    /// Returns the number of deltas consumed.
+    fn skip_deltas(&mut self, n: usize, value: &mut i64, sum: &mut i64) -> Result<usize, Error> {
+        let mut consumed = 0;
+        while consumed < n && self.remaining > 0 {
+            let length = (n - consumed)
+                .min(self.remaining)
+                .min(self.values_per_mini_block - self.current_index);
+
+            if let Some(miniblock) = self.current_miniblock.as_mut() {
+                for _ in 0..length {
+                    let delta = miniblock
+                        .next()
+                        .ok_or_else(|| Error::oos("Mini block must contain all its deltas"))?;
+                    let delta = self.min_delta.wrapping_add(delta as i64);
+                    *sum = sum.wrapping_add(*value);
+                    *value = value.wrapping_add(delta);
+                }
+            } else {
+                // all deltas of this mini-block are equal to `min_delta`:
+                // the skipped values are an arithmetic progression
+                let length = length as i64;
+                let progression = self
+                    .min_delta
+                    .wrapping_mul(length.wrapping_mul(length - 1) / 2);
+                *sum = sum
+                    .wrapping_add(value.wrapping_mul(length))
+                    .wrapping_add(progression);
+                *value = value.wrapping_add(self.min_delta.wrapping_mul(length));
+            }
+            self.current_index += length;
+            self.remaining -= length;
+            consumed += length;
+
+            if self.remaining > 0 && self.current_index == self.values_per_mini_block {
+                self.advance_miniblock()?;
+            }
+        }
+        Ok(consumed)
+    }
+}
+
 impl<'a> Iterator for Block<'a> {
     type Item = Result<i64, Error>;
 
@@ -99,12 +144,12 @@ impl<'a> Iterator for Block<'a> {
         if self.remaining == 0 {
             return None;
         }
-        let result = self.min_delta
-            + self
-                .current_miniblock
+        let result = self.min_delta.wrapping_add(
+            self.current_miniblock
                 .as_mut()
                 .map(|x| x.next().unwrap_or_default())
-                .unwrap_or(0) as i64;
+                .unwrap_or(0) as i64,
+        );
         self.current_index += 1;
         self.remaining -= 1;
 
@@ -183,33 +228,78 @@ impl<'a> Decoder<'a> {
         self.consumed_bytes + self.current_block.as_ref().map_or(0, |b| b.consumed_bytes)
     }
 
+    /// Replaces the current block by the next one, that contains up to `remaining` deltas.
+    fn load_block(&mut self, remaining: usize) -> Result<(), Error> {
+        // At this point we must have at least one block
+        let current_block = self.current_block.as_ref().unwrap();
+        self.values = &self.values[current_block.consumed_bytes..];
+        self.consumed_bytes += current_block.consumed_bytes;
+
+        let next_block = Block::try_new(
+            self.values,
+            self.num_mini_blocks,
+            self.values_per_mini_block,
+            remaining,
+        )?;
+        self.current_block = Some(next_block);
+        Ok(())
+    }
+
     fn load_delta(&mut self) -> Result<i64, Error> {
         // At this point we must have at least one block and value available
         let current_block = self.current_block.as_mut().unwrap();
         if let Some(x) = current_block.next() {
             x
         } else {
-            // load next block
-            self.values = &self.values[current_block.consumed_bytes..];
-            self.consumed_bytes += current_block.consumed_bytes;
-
-            let next_block = Block::try_new(
-                self.values,
-                self.num_mini_blocks,
-                self.values_per_mini_block,
-                self.values_remaining,
-            );
-            match next_block {
-                Ok(mut next_block) => {
-                    let delta = next_block
-                        .next()
-                        .ok_or_else(|| Error::oos("Missing block"))?;
-                    self.current_block = Some(next_block);
-                    delta
-                }
-                Err(e) => Err(e),
+            self.load_block(self.values_remaining)?;
+            self.current_block
+                .as_mut()
+                .unwrap()
+                .next()
+                .ok_or_else(|| Error::oos("Missing block"))?
+        }
+    }
+
+    /// Skips `n` values, or all remaining values if there are less than `n`.
+    /// Mini-blocks whose deltas are all equal are skipped without decoding them.
+    ///
+    /// This is not named `skip` since [`Iterator::skip`] would take precedence over it.
+    pub fn skip_values(&mut self, n: usize) -> Result<(), Error> {
+        self.skip_and_sum(n).map(|_| ())
+    }
+
+    /// Skips `n` values like [`Decoder::skip_values`], returning the (wrapping) sum of the skipped values.
+    pub(crate) fn skip_and_sum(&mut self, n: usize) -> Result<i64, Error> {
+        let n = n.min(self.values_remaining);
+        let mut sum = 0i64;
+        if n == 0 {
+            return Ok(sum);
+        }
+
+        // every value but the last one is followed by a delta
+        let num_deltas = n.min(self.values_remaining - 1);
+        let mut skipped = 0;
+        while skipped < num_deltas {
+            // the number of deltas remaining, including the ones to skip
+            let remaining = self.values_remaining - 1;
+            let block = self.current_block.as_mut().unwrap();
+            let consumed =
+                block.skip_deltas(num_deltas - skipped, &mut self.next_value, &mut sum)?;
+            if consumed == 0 {
+                // the current block is exhausted; the next one has at least one delta
+                self.load_block(remaining)?;
+                continue;
             }
+            skipped += consumed;
+            self.values_remaining -= consumed;
+        }
+
+        if num_deltas < n {
+            // the last value was skipped
+            sum = sum.wrapping_add(self.next_value);
+            self.values_remaining -= 1;
         }
+        Ok(sum)
     }
 }
 
@@ -234,10 +324,17 @@ impl<'a> Iterator for Decoder<'a> {
             Err(e) => return Some(Err(e)),
         };
 
-        self.next_value += delta;
+        self.next_value = self.next_value.wrapping_add(delta);
         result
     }
 
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if let Err(e) = self.skip_values(n) {
+            return Some(Err(e));
+        }
+        self.next()
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         (self.values_remaining, Some(self.values_remaining))
     }
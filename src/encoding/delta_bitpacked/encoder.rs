@@ -1,76 +1,214 @@
 use crate::encoding::ceil8;
+use crate::error::Error;
 
 use super::super::bitpacked;
 use super::super::uleb128;
 use super::super::zigzag_leb128;
 
-/// Encodes an iterator of `i64` according to parquet's `DELTA_BINARY_PACKED`.
+/// Options of the `DELTA_BINARY_PACKED` encoder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncoderOptions {
+    /// The number of values in a block. Must be a multiple of 128.
+    pub block_size: usize,
+    /// The number of mini-blocks in a block. `block_size / num_mini_blocks` must be a multiple of 32.
+    pub num_mini_blocks: usize,
+}
+
+impl Default for EncoderOptions {
+    fn default() -> Self {
+        Self {
+            block_size: 128,
+            num_mini_blocks: 4,
+        }
+    }
+}
+
+impl EncoderOptions {
+    fn try_validate(&self) -> Result<(), Error> {
+        let blocks_of_128 = self.block_size / 128;
+        if blocks_of_128 == 0 || blocks_of_128 * 128 != self.block_size {
+            return Err(Error::InvalidParameter(format!(
+                "The block size of DELTA_BINARY_PACKED must be a positive multiple of 128 (got {})",
+                self.block_size
+            )));
+        }
+        let values_per_mini_block = self.block_size.checked_div(self.num_mini_blocks);
+        let is_valid = values_per_mini_block.is_some_and(|values| {
+            values * self.num_mini_blocks == self.block_size && values / 32 * 32 == values
+        });
+        if !is_valid {
+            return Err(Error::InvalidParameter(format!(
+                "The number of values in each mini-block of DELTA_BINARY_PACKED must be a multiple of 32 (got {} mini-blocks for a block size of {})",
+                self.num_mini_blocks, self.block_size
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Encodes an iterator of `i64` according to parquet's `DELTA_BINARY_PACKED`,
+/// using the default [`EncoderOptions`].
+/// # Implementation
+/// Deltas are computed with wrapping arithmetic, so that any sequence of `i64` round-trips.
+pub fn encode<I: Iterator<Item = i64>>(iterator: I, buffer: &mut Vec<u8>) {
+    encode_impl(iterator, buffer, EncoderOptions::default())
+}
+
+/// Encodes an iterator of `i64` according to parquet's `DELTA_BINARY_PACKED` using `options`.
+/// # Errors
+/// Errors iff `options` is not a valid block layout.
+pub fn encode_with_options<I: Iterator<Item = i64>>(
+    iterator: I,
+    buffer: &mut Vec<u8>,
+    options: EncoderOptions,
+) -> Result<(), Error> {
+    options.try_validate()?;
+    encode_impl(iterator, buffer, options);
+    Ok(())
+}
+
+/// Encodes an iterator of `i32` according to parquet's `DELTA_BINARY_PACKED`,
+/// using the default [`EncoderOptions`].
 /// # Implementation
-/// * This function does not allocate on the heap.
-/// * The number of mini-blocks is always 1. This may change in the future.
-pub fn encode<I: Iterator<Item = i64>>(mut iterator: I, buffer: &mut Vec<u8>) {
-    let block_size = 128;
-    let mini_blocks = 1;
+/// Deltas are computed in 32-bit wrapping arithmetic, so that they always fit in 32 bits
+/// and any sequence of `i32` round-trips.
+pub fn encode_i32<I: Iterator<Item = i32>>(iterator: I, buffer: &mut Vec<u8>) {
+    encode_i32_impl(iterator, buffer, EncoderOptions::default())
+}
+
+/// Encodes an iterator of `i32` according to parquet's `DELTA_BINARY_PACKED` using `options`.
+/// # Errors
+/// Errors iff `options` is not a valid block layout.
+pub fn encode_i32_with_options<I: Iterator<Item = i32>>(
+    iterator: I,
+    buffer: &mut Vec<u8>,
+    options: EncoderOptions,
+) -> Result<(), Error> {
+    options.try_validate()?;
+    encode_i32_impl(iterator, buffer, options);
+    Ok(())
+}
+
+fn encode_impl<I: Iterator<Item = i64>>(
+    mut iterator: I,
+    buffer: &mut Vec<u8>,
+    options: EncoderOptions,
+) {
+    let length = iterator.size_hint().1.unwrap();
+    let first_value = iterator.next().unwrap_or_default();
+
+    let mut prev = first_value;
+    let deltas = iterator.map(|value| {
+        let delta = value.wrapping_sub(prev);
+        prev = value;
+        delta
+    });
+    encode_deltas(first_value, length, deltas, buffer, options)
+}
+
+fn encode_i32_impl<I: Iterator<Item = i32>>(
+    mut iterator: I,
+    buffer: &mut Vec<u8>,
+    options: EncoderOptions,
+) {
+    let length = iterator.size_hint().1.unwrap();
+    let first_value = iterator.next().unwrap_or_default();
+
+    let mut prev = first_value;
+    let deltas = iterator.map(|value| {
+        let delta = value.wrapping_sub(prev);
+        prev = value;
+        delta as i64
+    });
+    encode_deltas(first_value as i64, length, deltas, buffer, options)
+}
 
+/// Writes the header followed by the blocks of `deltas`.
+fn encode_deltas<I: Iterator<Item = i64>>(
+    first_value: i64,
+    length: usize,
+    mut deltas: I,
+    buffer: &mut Vec<u8>,
+    options: EncoderOptions,
+) {
+    let EncoderOptions {
+        block_size,
+        num_mini_blocks,
+    } = options;
+    let values_per_mini_block = block_size / num_mini_blocks;
+
+    // <block size in values> <number of miniblocks in a block> <total value count> <first value>
     let mut container = [0u8; 10];
-    let encoded_len = uleb128::encode(block_size, &mut container);
+    let encoded_len = uleb128::encode(block_size as u64, &mut container);
     buffer.extend_from_slice(&container[..encoded_len]);
 
-    let encoded_len = uleb128::encode(mini_blocks, &mut container);
+    let encoded_len = uleb128::encode(num_mini_blocks as u64, &mut container);
     buffer.extend_from_slice(&container[..encoded_len]);
 
-    let length = iterator.size_hint().1.unwrap();
     let encoded_len = uleb128::encode(length as u64, &mut container);
     buffer.extend_from_slice(&container[..encoded_len]);
 
-    let mut values = [0i64; 128];
-    let mut deltas = [0u64; 128];
-
-    let first_value = iterator.next().unwrap_or_default();
     let (container, encoded_len) = zigzag_leb128::encode(first_value);
     buffer.extend_from_slice(&container[..encoded_len]);
 
-    let mut prev = first_value;
-    let mut length = iterator.size_hint().1.unwrap();
-    while length != 0 {
-        let mut min_delta = i64::MAX;
-        let mut max_delta = i64::MIN;
-        let mut num_bits = 0;
-        for (i, integer) in (0..128).zip(&mut iterator) {
-            let delta = integer - prev;
-            min_delta = min_delta.min(delta);
-            max_delta = max_delta.max(delta);
-
-            num_bits = 64 - (max_delta - min_delta).leading_zeros();
-            values[i] = delta;
-            prev = integer;
+    let mut block = vec![0i64; block_size];
+    let mut packed = vec![0u64; values_per_mini_block];
+    let mut num_bits = vec![0u8; num_mini_blocks];
+    loop {
+        let consumed = block
+            .iter_mut()
+            .zip(&mut deltas)
+            .map(|(slot, delta)| *slot = delta)
+            .count();
+        if consumed == 0 {
+            break;
         }
-        let consumed = std::cmp::min(length - iterator.size_hint().1.unwrap(), 128);
-        length = iterator.size_hint().1.unwrap();
-        let values = &values[..consumed];
+        let block = &block[..consumed];
 
-        values.iter().zip(deltas.iter_mut()).for_each(|(v, delta)| {
-            *delta = (v - min_delta) as u64;
-        });
+        let min_delta = block.iter().copied().min().unwrap();
+
+        // the bit width of each mini-block; unused mini-blocks have a bit width of 0
+        num_bits.iter_mut().for_each(|x| *x = 0);
+        block
+            .chunks(values_per_mini_block)
+            .zip(num_bits.iter_mut())
+            .for_each(|(mini_block, num_bits)| {
+                let max_delta = mini_block.iter().copied().max().unwrap();
+                *num_bits = (64 - max_delta.wrapping_sub(min_delta).leading_zeros()) as u8;
+            });
 
         // <min delta> <list of bitwidths of miniblocks> <miniblocks>
         let (container, encoded_len) = zigzag_leb128::encode(min_delta);
         buffer.extend_from_slice(&container[..encoded_len]);
+        buffer.extend_from_slice(&num_bits);
+
+        block
+            .chunks(values_per_mini_block)
+            .zip(num_bits.iter())
+            .for_each(|(mini_block, num_bits)| {
+                // a partial mini-block is padded with zeros
+                packed.iter_mut().for_each(|x| *x = 0);
+                mini_block
+                    .iter()
+                    .zip(packed.iter_mut())
+                    .for_each(|(delta, packed)| *packed = delta.wrapping_sub(min_delta) as u64);
+                write_miniblock(buffer, *num_bits as usize, &packed);
+            });
 
-        // one miniblock => 1 byte
-        buffer.push(num_bits as u8);
-        write_miniblock(buffer, num_bits as usize, deltas);
+        if consumed < block_size {
+            break;
+        }
     }
 }
 
-fn write_miniblock(buffer: &mut Vec<u8>, num_bits: usize, deltas: [u64; 128]) {
+fn write_miniblock(buffer: &mut Vec<u8>, num_bits: usize, deltas: &[u64]) {
     if num_bits > 0 {
         let start = buffer.len();
 
-        // bitpack encode all (deltas.len = 128 which is a multiple of 32)
-        let bytes_needed = start + ceil8(deltas.len() * num_bits);
+        // bitpacking writes whole packs of 64 values
+        let bytes_needed = start + ceil8(deltas.len().div_ceil(64) * 64 * num_bits);
         buffer.resize(bytes_needed, 0);
-        bitpacked::encode(deltas.as_ref(), num_bits, &mut buffer[start..]);
+        bitpacked::encode(deltas, num_bits, &mut buffer[start..]);
 
         let bytes_needed = start + ceil8(deltas.len() * num_bits);
         buffer.truncate(bytes_needed);
@@ -83,16 +221,16 @@ mod tests {
 
     #[test]
     fn constant_delta() {
-        // header: [128, 1, 1, 5, 2]:
+        // header: [128, 1, 4, 5, 2]:
         //  block size: 128    <=u> 128, 1
-        //  mini-blocks: 1     <=u> 1
+        //  mini-blocks: 4     <=u> 4
         //  elements: 5        <=u> 5
         //  first_value: 2     <=z> 1
         // block1: [2, 0, 0, 0, 0]
         //  min_delta: 1        <=z> 2
-        //  bitwidth: 0
+        //  bitwidths: [0, 0, 0, 0]
         let data = 1..=5;
-        let expected = vec![128u8, 1, 1, 5, 2, 2, 0];
+        let expected = vec![128u8, 1, 4, 5, 2, 2, 0, 0, 0, 0];
 
         let mut buffer = vec![];
         encode(data, &mut buffer);
@@ -105,21 +243,70 @@ mod tests {
         let data = vec![1, 2, 3, 4, 5, 1];
         // header: [128, 1, 4, 6, 2]
         //  block size: 128    <=u> 128, 1
-        //  mini-blocks: 1     <=u> 1
+        //  mini-blocks: 4     <=u> 4
         //  elements: 6        <=u> 5
         //  first_value: 2     <=z> 1
-        // block1: [7, 3, 253, 255]
+        // block1: [7, 3, 0, 0, 0, ...]
         //  min_delta: -4        <=z> 7
-        //  bitwidth: 3
+        //  bitwidths: [3, 0, 0, 0]
         //  values: [5, 5, 5, 5, 0] <=b> [
         //      0b01101101
         //      0b00001011
         // ]
+        let mut expected = vec![128u8, 1, 4, 6, 2, 7, 3, 0, 0, 0, 0b01101101, 0b00001011];
+        expected.extend(std::iter::repeat(0).take(32 * 3 / 8 - 2)); // 32 values, 3 bits, 2 already used
+
+        let mut buffer = vec![];
+        encode(data.into_iter(), &mut buffer);
+        assert_eq!(expected, buffer);
+    }
+
+    #[test]
+    fn single_mini_block() -> Result<(), Error> {
+        let data = vec![1, 2, 3, 4, 5, 1];
+        // same as `negative_min_delta`, but with a single mini-block of 128 values
         let mut expected = vec![128u8, 1, 1, 6, 2, 7, 3, 0b01101101, 0b00001011];
         expected.extend(std::iter::repeat(0).take(128 * 3 / 8 - 2)); // 128 values, 3 bits, 2 already used
 
+        let options = EncoderOptions {
+            block_size: 128,
+            num_mini_blocks: 1,
+        };
         let mut buffer = vec![];
-        encode(data.into_iter(), &mut buffer);
+        encode_with_options(data.into_iter(), &mut buffer, options)?;
+        assert_eq!(expected, buffer);
+        Ok(())
+    }
+
+    #[test]
+    fn mini_block_bit_widths() {
+        // deltas: 32 x 1, then 32 x 8
+        let data = (0..=32).chain((1..=32).map(|x| 32 + x * 8));
+
+        let mut buffer = vec![];
+        encode(data, &mut buffer);
+
+        // header: [128, 1, 4, 65, 0]
+        // block1: min_delta: 1 <=z> 2
+        let mut expected = vec![128u8, 1, 4, 65, 0, 2];
+        // bitwidths: [0, 3, 0, 0]: the last two mini-blocks are unused
+        expected.extend([0, 3, 0, 0]);
+        // mini-block 1 is not written: its deltas are all equal to min_delta
+        // 32 3-bit values of 7 for mini-block 2 (12 bytes)
+        expected.extend(std::iter::repeat(255).take(12));
         assert_eq!(expected, buffer);
     }
+
+    #[test]
+    fn invalid_options() {
+        let mut buffer = vec![];
+        for (block_size, num_mini_blocks) in [(100, 1), (0, 1), (128, 0), (128, 8), (256, 3)] {
+            let options = EncoderOptions {
+                block_size,
+                num_mini_blocks,
+            };
+            assert!(encode_with_options(std::iter::empty(), &mut buffer, options).is_err());
+        }
+        assert!(buffer.is_empty());
+    }
 }
@@ -2,7 +2,9 @@ mod decoder;
 mod encoder;
 
 pub use decoder::Decoder;
-pub use encoder::encode;
+pub use encoder::{
+    encode, encode_i32, encode_i32_with_options, encode_with_options, EncoderOptions,
+};
 
 #[cfg(test)]
 mod tests {
@@ -88,4 +90,106 @@ mod tests {
         assert_eq!(iter.consumed_bytes(), len);
         Ok(())
     }
+
+    #[test]
+    fn wrapping_i64() -> Result<(), Error> {
+        let data = vec![i64::MIN, i64::MAX, 0, i64::MIN, -1, i64::MAX];
+
+        let mut buffer = vec![];
+        encode(data.clone().into_iter(), &mut buffer);
+        let iter = Decoder::try_new(&buffer)?;
+
+        let result = iter.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(result, data);
+        Ok(())
+    }
+
+    #[test]
+    fn i32() -> Result<(), Error> {
+        let mut data = vec![i32::MIN, i32::MAX, 0, i32::MIN, -1, i32::MAX];
+        data.extend((0..300).map(|x| x * 1_000_003));
+
+        let mut buffer = vec![];
+        encode_i32(data.clone().into_iter(), &mut buffer);
+        let iter = Decoder::try_new(&buffer)?;
+
+        let result = iter
+            .map(|x| x.map(|x| x as i32))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(result, data);
+        Ok(())
+    }
+
+    #[test]
+    fn options() -> Result<(), Error> {
+        let data = (0..1000).map(|x| (x * x) % 577 - 200).collect::<Vec<i64>>();
+
+        for (block_size, num_mini_blocks) in [(128, 1), (128, 4), (256, 8), (512, 2)] {
+            let options = EncoderOptions {
+                block_size,
+                num_mini_blocks,
+            };
+            let mut buffer = vec![];
+            encode_with_options(data.clone().into_iter(), &mut buffer, options)?;
+            let mut iter = Decoder::try_new(&buffer)?;
+
+            let result = iter.by_ref().collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(result, data);
+            assert_eq!(iter.consumed_bytes(), buffer.len());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn more_mini_blocks_are_smaller() {
+        // small deltas with an occasional large one
+        let data = (0..1024).map(|x| if x % 128 == 0 { 1 << 20 } else { x });
+
+        let mut one = vec![];
+        let options = EncoderOptions {
+            block_size: 128,
+            num_mini_blocks: 1,
+        };
+        encode_with_options(data.clone(), &mut one, options).unwrap();
+
+        let mut four = vec![];
+        encode(data, &mut four);
+        assert!(four.len() < one.len());
+    }
+
+    #[test]
+    fn skip() -> Result<(), Error> {
+        // mini-blocks of constant deltas and of varying deltas, across multiple blocks
+        let data = (0..600i64)
+            .map(|x| {
+                if (x / 32) % 3 == 0 {
+                    x * 3
+                } else {
+                    (x * x) % 101
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut buffer = vec![];
+        encode(data.clone().into_iter(), &mut buffer);
+
+        for n in [0, 1, 31, 32, 33, 127, 128, 129, 300, 599, 600, 1000] {
+            let mut decoder = Decoder::try_new(&buffer)?;
+            let sum = decoder.skip_and_sum(n)?;
+            assert_eq!(sum, data.iter().take(n).sum::<i64>());
+            assert_eq!(decoder.size_hint().0, data.len().saturating_sub(n));
+
+            let result = decoder.by_ref().collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(result, data.iter().skip(n).copied().collect::<Vec<_>>());
+            assert_eq!(decoder.consumed_bytes(), buffer.len());
+        }
+
+        let mut decoder = Decoder::try_new(&buffer)?;
+        let result = std::iter::from_fn(|| decoder.nth(6)).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            result,
+            data.iter().skip(6).step_by(7).copied().collect::<Vec<_>>()
+        );
+        Ok(())
+    }
 }
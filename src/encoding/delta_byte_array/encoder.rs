@@ -16,7 +16,7 @@ pub fn encode<'a, I: Iterator<Item = &'a [u8]> + Clone>(iterator: I, buffer: &mu
                 .enumerate()
                 // find first difference
                 .find_map(|(length, (lhs, rhs))| (lhs != rhs).then_some(length))
-                .unwrap_or(previous.len());
+                .unwrap_or_else(|| previous.len().min(item.len()));
             previous = item;
 
             sum_lengths += item.len() - prefix_length;
@@ -30,4 +30,27 @@ mod tests {
         assert_eq!(values, b"Helloicopter");
         Ok(())
     }
+
+    #[test]
+    fn prefix_of_previous() -> Result<(), Error> {
+        // "Hel" is a prefix of "Hello" and of "Help"
+        let data = vec![b"Hello".as_ref(), b"Hel", b"Help"];
+        let mut buffer = vec![];
+        encode(data.clone().into_iter(), &mut buffer);
+
+        let mut decoder = Decoder::try_new(&buffer)?;
+        let prefixes = decoder.by_ref().collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(prefixes, vec![0, 3, 3]);
+
+        // move to the lengths
+        let mut decoder = decoder.into_lengths()?;
+
+        let lengths = decoder.by_ref().collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(lengths, vec![5, 0, 1]);
+
+        // move to the values
+        let values = decoder.values();
+        assert_eq!(values, b"Hellop");
+        Ok(())
+    }
 }
@@ -45,6 +45,20 @@ impl<'a> Decoder<'a> {
         })
     }
 
+    /// Skips the lengths of `n` values, or of all remaining values if there are less than `n`,
+    /// returning the total length of the skipped values.
+    /// The skipped values are still part of [`Decoder::values`].
+    ///
+    /// This is not named `skip` since [`Iterator::skip`] would take precedence over it.
+    pub fn skip_values(&mut self, n: usize) -> Result<usize, Error> {
+        let length: u32 = self.lengths.skip_and_sum(n)?.try_into()?;
+        self.total_length = self
+            .total_length
+            .checked_add(length)
+            .ok_or_else(|| Error::oos("The total length of the values must fit in a u32"))?;
+        Ok(length as usize)
+    }
+
     /// Consumes this decoder and returns the slice of concatenated values.
     /// # Panics
     /// This function panics if this iterator has not been fully consumed.
@@ -78,4 +92,15 @@ impl<'a> Iterator for Decoder<'a> {
             None => None,
         }
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if let Err(e) = self.skip_values(n) {
+            return Some(Err(e));
+        }
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lengths.size_hint()
+    }
 }
@@ -48,4 +48,35 @@ mod tests {
         assert_eq!(result, expected_values.as_str().as_bytes());
         Ok(())
     }
+
+    #[test]
+    fn skip() -> Result<(), Error> {
+        let data = (0..200).map(|i| format!("a{}", i)).collect::<Vec<_>>();
+        let expected_values = data.join("");
+        let expected_lengths = data.iter().map(|x| x.len() as i32).collect::<Vec<_>>();
+
+        let mut buffer = vec![];
+        encode(data.iter(), &mut buffer);
+
+        let mut iter = Decoder::try_new(&buffer)?;
+        let skipped = iter.skip_values(150)?;
+        assert_eq!(skipped, data[..150].iter().map(|x| x.len()).sum::<usize>());
+
+        let result = iter.by_ref().collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(result, &expected_lengths[150..]);
+
+        let result = iter.into_values();
+        assert_eq!(result, expected_values.as_bytes());
+        Ok(())
+    }
+
+    #[test]
+    fn skip_negative_lengths() {
+        // lengths whose sum is negative are out of spec
+        let mut buffer = vec![];
+        super::super::delta_bitpacked::encode([1i64, -3].into_iter(), &mut buffer);
+
+        let mut iter = Decoder::try_new(&buffer).unwrap();
+        assert!(iter.skip_values(2).is_err());
+    }
 }
@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use crate::encoding::{hybrid_rle, Encoding};
+use crate::error::{Error, Result};
+use crate::metadata::Descriptor;
+use crate::page::{
+    DataPage, DataPageHeader, DataPageHeaderV1, DataPageHeaderV2, DictPage, Page, Version,
+};
+use crate::schema::types::PhysicalType;
+use crate::types::NativeType;
+
+/// Options of the dictionary encoders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DictionaryOptions {
+    /// The maximum size in bytes of the dictionary page. Values that would make the
+    /// dictionary larger than this are rejected by the encoder.
+    pub max_dictionary_size: usize,
+    /// The maximum number of values (including nulls) of each data page
+    pub max_page_values: usize,
+    /// The version of the data pages
+    pub version: Version,
+}
+
+impl Default for DictionaryOptions {
+    fn default() -> Self {
+        Self {
+            max_dictionary_size: 1024 * 1024,
+            max_page_values: 20_000,
+            version: Version::V1,
+        }
+    }
+}
+
+/// The type-erased state shared by all dictionary encoders.
+#[derive(Debug)]
+struct Encoder {
+    descriptor: Descriptor,
+    options: DictionaryOptions,
+    /// whether the values are prefixed by their length in the dictionary page (i.e. `BYTE_ARRAY`)
+    length_prefixed: bool,
+    /// maps every value of the dictionary to its index
+    indices: HashMap<Vec<u8>, u32>,
+    /// the PLAIN-encoded dictionary
+    dictionary: Vec<u8>,
+    /// the indices of the non-null values of the current data page
+    keys: Vec<u32>,
+    /// the validity of the values of the current data page
+    validity: Vec<bool>,
+    pages: Vec<DataPage>,
+}
+
+impl Encoder {
+    fn try_new(
+        descriptor: Descriptor,
+        options: DictionaryOptions,
+        length_prefixed: bool,
+    ) -> Result<Self> {
+        if descriptor.max_rep_level > 0 || descriptor.max_def_level > 1 {
+            return Err(Error::FeatureNotSupported(
+                "Dictionary-encoding of nested columns".to_string(),
+            ));
+        }
+        if options.max_page_values == 0 {
+            return Err(Error::InvalidParameter(
+                "Data pages must hold at least one value".to_string(),
+            ));
+        }
+        Ok(Self {
+            descriptor,
+            options,
+            length_prefixed,
+            indices: HashMap::new(),
+            dictionary: vec![],
+            keys: vec![],
+            validity: vec![],
+            pages: vec![],
+        })
+    }
+
+    fn push(&mut self, value: Option<&[u8]>) -> Result<bool> {
+        if let Some(value) = value {
+            let key = if let Some(key) = self.indices.get(value) {
+                *key
+            } else {
+                let prefix = if self.length_prefixed { 4 } else { 0 };
+                if self.dictionary.len() + prefix + value.len() > self.options.max_dictionary_size {
+                    return Ok(false);
+                }
+                let key = self.indices.len() as u32;
+                if self.length_prefixed {
+                    self.dictionary
+                        .extend_from_slice(&(value.len() as u32).to_le_bytes());
+                }
+                self.dictionary.extend_from_slice(value);
+                self.indices.insert(value.to_vec(), key);
+                key
+            };
+            self.keys.push(key);
+            self.validity.push(true);
+        } else {
+            if self.descriptor.max_def_level == 0 {
+                return Err(Error::InvalidParameter(
+                    "Required columns cannot contain nulls".to_string(),
+                ));
+            }
+            self.validity.push(false);
+        }
+
+        if self.validity.len() == self.options.max_page_values {
+            self.flush_page()?;
+        }
+        Ok(true)
+    }
+
+    /// Encodes the values pushed since the last data page into a new data page.
+    fn flush_page(&mut self) -> Result<()> {
+        if self.validity.is_empty() {
+            return Ok(());
+        }
+        let num_values = self.validity.len();
+        let num_nulls = num_values - self.keys.len();
+
+        let mut buffer = vec![];
+        let is_optional = self.descriptor.max_def_level > 0;
+        if is_optional {
+            match self.options.version {
+                Version::V1 => {
+                    // the length of the def levels is only known after encoding them
+                    buffer.extend_from_slice(&[0; 4]);
+                    hybrid_rle::encode_bool(&mut buffer, self.validity.iter().copied())?;
+                    let length = (buffer.len() - 4) as u32;
+                    buffer[..4].copy_from_slice(&length.to_le_bytes());
+                }
+                Version::V2 => {
+                    hybrid_rle::encode_bool(&mut buffer, self.validity.iter().copied())?;
+                }
+            }
+        }
+        let definition_levels_byte_length = buffer.len();
+
+        // SPEC: the bit width used to encode the entry ids stored as 1 byte (max bit width = 32),
+        // SPEC: followed by the values encoded using RLE/Bit packed described above (with the given bit width).
+        let max_key = self.indices.len().saturating_sub(1) as u32;
+        let num_bits = (32 - max_key.leading_zeros()).max(1);
+        buffer.push(num_bits as u8);
+        hybrid_rle::encode_u32(&mut buffer, self.keys.iter().copied(), num_bits)?;
+
+        let header = match self.options.version {
+            Version::V1 => DataPageHeader::V1(DataPageHeaderV1 {
+                num_values: num_values as i32,
+                encoding: Encoding::RleDictionary.into(),
+                definition_level_encoding: Encoding::Rle.into(),
+                repetition_level_encoding: Encoding::Rle.into(),
+                statistics: None,
+            }),
+            Version::V2 => DataPageHeader::V2(DataPageHeaderV2 {
+                num_values: num_values as i32,
+                num_nulls: num_nulls as i32,
+                num_rows: num_values as i32,
+                encoding: Encoding::RleDictionary.into(),
+                definition_levels_byte_length: definition_levels_byte_length as i32,
+                repetition_levels_byte_length: 0,
+                is_compressed: Some(true),
+                statistics: None,
+            }),
+        };
+
+        self.pages.push(DataPage::new(
+            header,
+            buffer,
+            self.descriptor.clone(),
+            Some(num_values),
+        ));
+        self.keys.clear();
+        self.validity.clear();
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Vec<Page>> {
+        self.flush_page()?;
+
+        let dict = DictPage::new(self.dictionary, self.indices.len(), false);
+        Ok(std::iter::once(Page::Dict(dict))
+            .chain(self.pages.into_iter().map(Page::Data))
+            .collect())
+    }
+}
+
+fn check_physical_type(descriptor: &Descriptor, expected: PhysicalType) -> Result<()> {
+    let physical_type = descriptor.primitive_type.physical_type;
+    if physical_type != expected {
+        return Err(Error::InvalidParameter(format!(
+            "The dictionary encoder of {:?} cannot encode a column of physical type {:?}",
+            expected, physical_type
+        )));
+    }
+    Ok(())
+}
+
+/// Dictionary encoder of [`NativeType`].
+#[derive(Debug)]
+pub struct NativeEncoder<T: NativeType> {
+    encoder: Encoder,
+    phantom: PhantomData<T>,
+}
+
+impl<T: NativeType> NativeEncoder<T> {
+    /// Returns a new [`NativeEncoder`].
+    /// # Errors
+    /// Errors iff the physical type of `descriptor` is not `T`, or the column is nested.
+    pub fn try_new(descriptor: Descriptor, options: DictionaryOptions) -> Result<Self> {
+        check_physical_type(&descriptor, T::TYPE)?;
+        Ok(Self {
+            encoder: Encoder::try_new(descriptor, options, false)?,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Pushes a value to the encoder, returning whether it was accepted.
+    /// A value is rejected when adding it to the dictionary would make it larger than
+    /// [`DictionaryOptions::max_dictionary_size`]; the caller should then [`Self::finish`] this
+    /// encoder and encode the rejected and remaining values with `PLAIN`.
+    /// # Errors
+    /// Errors iff `value` is null and the column is required.
+    pub fn push(&mut self, value: Option<T>) -> Result<bool> {
+        let value = value.map(|x| x.to_le_bytes());
+        self.encoder.push(value.as_ref().map(|x| x.as_ref()))
+    }
+
+    /// Returns the dictionary page followed by the data pages of the values pushed.
+    pub fn finish(self) -> Result<Vec<Page>> {
+        self.encoder.finish()
+    }
+}
+
+/// Dictionary encoder of `BYTE_ARRAY`.
+#[derive(Debug)]
+pub struct BinaryEncoder {
+    encoder: Encoder,
+}
+
+impl BinaryEncoder {
+    /// Returns a new [`BinaryEncoder`].
+    /// # Errors
+    /// Errors iff the physical type of `descriptor` is not `BYTE_ARRAY`, or the column is nested.
+    pub fn try_new(descriptor: Descriptor, options: DictionaryOptions) -> Result<Self> {
+        check_physical_type(&descriptor, PhysicalType::ByteArray)?;
+        Ok(Self {
+            encoder: Encoder::try_new(descriptor, options, true)?,
+        })
+    }
+
+    /// Pushes a value to the encoder, returning whether it was accepted.
+    /// See [`NativeEncoder::push`] for details.
+    pub fn push(&mut self, value: Option<&[u8]>) -> Result<bool> {
+        self.encoder.push(value)
+    }
+
+    /// Returns the dictionary page followed by the data pages of the values pushed.
+    pub fn finish(self) -> Result<Vec<Page>> {
+        self.encoder.finish()
+    }
+}
+
+/// Dictionary encoder of `FIXED_LEN_BYTE_ARRAY`.
+#[derive(Debug)]
+pub struct FixedLenEncoder {
+    encoder: Encoder,
+    size: usize,
+}
+
+impl FixedLenEncoder {
+    /// Returns a new [`FixedLenEncoder`].
+    /// # Errors
+    /// Errors iff the physical type of `descriptor` is not `FIXED_LEN_BYTE_ARRAY`, or the column is nested.
+    pub fn try_new(descriptor: Descriptor, options: DictionaryOptions) -> Result<Self> {
+        let size = if let PhysicalType::FixedLenByteArray(size) =
+            descriptor.primitive_type.physical_type
+        {
+            size
+        } else {
+            return Err(Error::InvalidParameter(format!(
+                "The dictionary encoder of FixedLenByteArray cannot encode a column of physical type {:?}",
+                descriptor.primitive_type.physical_type
+            )));
+        };
+        Ok(Self {
+            encoder: Encoder::try_new(descriptor, options, false)?,
+            size,
+        })
+    }
+
+    /// Pushes a value to the encoder, returning whether it was accepted.
+    /// See [`NativeEncoder::push`] for details.
+    /// # Errors
+    /// Errors iff `value` is null and the column is required, or its length is not the column's size.
+    pub fn push(&mut self, value: Option<&[u8]>) -> Result<bool> {
+        if let Some(value) = value {
+            if value.len() != self.size {
+                return Err(Error::InvalidParameter(format!(
+                    "Values of this column must have {} bytes (got {})",
+                    self.size,
+                    value.len()
+                )));
+            }
+        }
+        self.encoder.push(value)
+    }
+
+    /// Returns the dictionary page followed by the data pages of the values pushed.
+    pub fn finish(self) -> Result<Vec<Page>> {
+        self.encoder.finish()
+    }
+}
@@ -0,0 +1,188 @@
+// See https://github.com/apache/parquet-format/blob/master/Encodings.md#dictionary-encoding-plain_dictionary--2-and-rle_dictionary--8
+mod encoder;
+
+pub use encoder::{BinaryEncoder, DictionaryOptions, FixedLenEncoder, NativeEncoder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::encoding::hybrid_rle::HybridRleDecoder;
+    use crate::error::{Error, Result};
+    use crate::metadata::Descriptor;
+    use crate::page::Version;
+    use crate::page::{split_buffer, DataPage, DictPage, Page};
+    use crate::schema::types::{PhysicalType, PrimitiveType};
+
+    fn descriptor(physical_type: PhysicalType, max_def_level: i16) -> Descriptor {
+        Descriptor {
+            primitive_type: PrimitiveType::from_physical("a".to_string(), physical_type),
+            max_def_level,
+            max_rep_level: 0,
+        }
+    }
+
+    /// Returns the indices of the data page, `None` for nulls
+    fn decode_indices(page: &DataPage) -> Result<Vec<Option<u32>>> {
+        let (_, def, values) = split_buffer(page)?;
+        let mut indices =
+            HybridRleDecoder::try_new(&values[1..], values[0] as u32, page.num_values())?;
+        if page.descriptor.max_def_level == 0 {
+            return indices.map(|x| x.map(Some)).collect();
+        }
+        HybridRleDecoder::try_new(def, 1, page.num_values())?
+            .map(|is_valid| {
+                if is_valid? == 1 {
+                    indices.next().transpose()
+                } else {
+                    Ok(None)
+                }
+            })
+            .collect()
+    }
+
+    fn split_pages(pages: Vec<Page>) -> (DictPage, Vec<DataPage>) {
+        let mut pages = pages.into_iter();
+        let dict = match pages.next() {
+            Some(Page::Dict(dict)) => dict,
+            _ => panic!("the first page must be a dictionary page"),
+        };
+        let pages = pages
+            .map(|page| match page {
+                Page::Data(page) => page,
+                Page::Dict(_) => panic!("only the first page can be a dictionary page"),
+            })
+            .collect();
+        (dict, pages)
+    }
+
+    fn native(version: Version) -> Result<()> {
+        let options = DictionaryOptions {
+            max_page_values: 3,
+            version,
+            ..Default::default()
+        };
+        let mut encoder =
+            NativeEncoder::<i32>::try_new(descriptor(PhysicalType::Int32, 1), options)?;
+        for value in [Some(10), None, Some(20), Some(10), Some(30), None, Some(20)] {
+            assert!(encoder.push(value)?);
+        }
+        let (dict, pages) = split_pages(encoder.finish()?);
+
+        assert_eq!(dict.num_values, 3);
+        let expected = [10i32, 20, 30]
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect::<Vec<_>>();
+        assert_eq!(dict.buffer, expected);
+
+        assert_eq!(pages.len(), 3);
+        let indices = pages
+            .iter()
+            .map(decode_indices)
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(
+            indices,
+            vec![
+                vec![Some(0), None, Some(1)],
+                vec![Some(0), Some(2), None],
+                vec![Some(1)]
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn native_v1() -> Result<()> {
+        native(Version::V1)
+    }
+
+    #[test]
+    fn native_v2() -> Result<()> {
+        native(Version::V2)
+    }
+
+    #[test]
+    fn binary() -> Result<()> {
+        let mut encoder = BinaryEncoder::try_new(
+            descriptor(PhysicalType::ByteArray, 0),
+            DictionaryOptions::default(),
+        )?;
+        for value in [b"aa".as_ref(), b"b", b"aa", b"", b"b"] {
+            assert!(encoder.push(Some(value))?);
+        }
+        let (dict, pages) = split_pages(encoder.finish()?);
+
+        assert_eq!(dict.num_values, 3);
+        assert_eq!(
+            dict.buffer,
+            vec![2, 0, 0, 0, b'a', b'a', 1, 0, 0, 0, b'b', 0, 0, 0, 0]
+        );
+
+        assert_eq!(pages.len(), 1);
+        assert_eq!(
+            decode_indices(&pages[0])?,
+            vec![Some(0), Some(1), Some(0), Some(2), Some(1)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fixed_len() -> Result<()> {
+        let mut encoder = FixedLenEncoder::try_new(
+            descriptor(PhysicalType::FixedLenByteArray(2), 1),
+            DictionaryOptions::default(),
+        )?;
+        for value in [Some(b"ab".as_ref()), None, Some(b"cd"), Some(b"ab")] {
+            assert!(encoder.push(value)?);
+        }
+        assert!(matches!(
+            encoder.push(Some(b"abc")),
+            Err(Error::InvalidParameter(_))
+        ));
+        let (dict, pages) = split_pages(encoder.finish()?);
+
+        assert_eq!(dict.num_values, 2);
+        assert_eq!(dict.buffer, b"abcd".to_vec());
+        assert_eq!(
+            decode_indices(&pages[0])?,
+            vec![Some(0), None, Some(1), Some(0)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn size_limit() -> Result<()> {
+        let options = DictionaryOptions {
+            max_dictionary_size: 8,
+            ..Default::default()
+        };
+        let mut encoder =
+            NativeEncoder::<i32>::try_new(descriptor(PhysicalType::Int32, 0), options)?;
+        assert!(encoder.push(Some(1))?);
+        assert!(encoder.push(Some(2))?);
+        // a third value would make the dictionary 12 bytes long
+        assert!(!encoder.push(Some(3))?);
+        // values already in the dictionary are still accepted
+        assert!(encoder.push(Some(1))?);
+
+        let (dict, pages) = split_pages(encoder.finish()?);
+        assert_eq!(dict.num_values, 2);
+        assert_eq!(decode_indices(&pages[0])?, vec![Some(0), Some(1), Some(0)]);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() {
+        let options = DictionaryOptions::default();
+        assert!(
+            NativeEncoder::<i64>::try_new(descriptor(PhysicalType::Int32, 0), options).is_err()
+        );
+        assert!(BinaryEncoder::try_new(descriptor(PhysicalType::Int32, 0), options).is_err());
+        assert!(FixedLenEncoder::try_new(descriptor(PhysicalType::ByteArray, 0), options).is_err());
+
+        let mut encoder =
+            NativeEncoder::<i32>::try_new(descriptor(PhysicalType::Int32, 0), options).unwrap();
+        assert!(encoder.push(None).is_err());
+    }
+}
@@ -3,93 +3,164 @@ use crate::encoding::{ceil8, uleb128};
 
 use std::io::Write;
 
-use super::bitpacked_encode;
-
-/// RLE-hybrid encoding of `u32`. This currently only yields bitpacked values.
+/// RLE-hybrid encoding of `u32`.
+/// # Implementation
+/// Runs of repeated values are RLE-encoded whenever this is smaller than bit-packing them;
+/// all other values are bit-packed.
 pub fn encode_u32<W: Write, I: Iterator<Item = u32>>(
     writer: &mut W,
     iterator: I,
     num_bits: u32,
 ) -> std::io::Result<()> {
-    let num_bits = num_bits as u8;
-    // the length of the iterator.
-    let length = iterator.size_hint().1.unwrap();
-
-    // write the length + indicator
-    let mut header = ceil8(length) as u64;
-    header <<= 1;
-    header |= 1; // it is bitpacked => first bit is set
-    let mut container = [0; 10];
-    let used = uleb128::encode(header, &mut container);
-    writer.write_all(&container[..used])?;
-
-    bitpacked_encode_u32(writer, iterator, num_bits as usize)?;
-
-    Ok(())
+    let mut encoder = RunEncoder::new(writer, num_bits as usize);
+    for value in iterator {
+        encoder.push(value)?;
+    }
+    encoder.finish()
 }
 
-const U32_BLOCK_LEN: usize = 32;
-
-fn bitpacked_encode_u32<W: Write, I: Iterator<Item = u32>>(
+/// RLE-hybrid encoding of `bool`, i.e. with a bit-width of 1.
+pub fn encode_bool<W: Write, I: Iterator<Item = bool>>(
     writer: &mut W,
-    mut iterator: I,
-    num_bits: usize,
+    iterator: I,
 ) -> std::io::Result<()> {
-    // the length of the iterator.
-    let length = iterator.size_hint().1.unwrap();
+    encode_u32(writer, iterator.map(|x| x as u32), 1)
+}
 
-    let chunks = length / U32_BLOCK_LEN;
-    let remainder = length - chunks * U32_BLOCK_LEN;
-    let mut buffer = [0u32; U32_BLOCK_LEN];
+/// The number of values in a group. Bit-packed runs are composed of whole groups.
+const GROUP_LEN: usize = 8;
 
-    let compressed_chunk_size = ceil8(U32_BLOCK_LEN * num_bits);
+const U32_BLOCK_LEN: usize = 32;
 
-    for _ in 0..chunks {
-        iterator
-            .by_ref()
-            .take(U32_BLOCK_LEN)
-            .zip(buffer.iter_mut())
-            .for_each(|(item, buf)| *buf = item);
+/// Writes the header of a run to `writer`.
+fn write_header<W: Write>(writer: &mut W, header: u64) -> std::io::Result<()> {
+    let mut container = [0; 10];
+    let used = uleb128::encode(header, &mut container);
+    writer.write_all(&container[..used])
+}
 
-        let mut packed = [0u8; 4 * U32_BLOCK_LEN];
-        bitpacked::encode_pack::<u32>(&buffer, num_bits, packed.as_mut());
-        writer.write_all(&packed[..compressed_chunk_size])?;
+/// Stateful encoder that splits a sequence of values into RLE and bit-packed runs.
+struct RunEncoder<'a, W: Write> {
+    writer: &'a mut W,
+    num_bits: usize,
+    /// The packed blocks of the current bit-packed run
+    packed: Vec<u8>,
+    /// The values of the current bit-packed run that were not yet packed
+    buffer: [u32; U32_BLOCK_LEN],
+    buffer_len: usize,
+    /// The number of values in the current bit-packed run
+    num_values: usize,
+    /// The last value pushed and the number of times it was repeated
+    run: Option<(u32, usize)>,
+}
+
+impl<'a, W: Write> RunEncoder<'a, W> {
+    fn new(writer: &'a mut W, num_bits: usize) -> Self {
+        Self {
+            writer,
+            num_bits,
+            packed: vec![],
+            buffer: [0; U32_BLOCK_LEN],
+            buffer_len: 0,
+            num_values: 0,
+            run: None,
+        }
     }
 
-    if remainder != 0 {
-        let compressed_remainder_size = ceil8(remainder * num_bits);
-        iterator
-            .by_ref()
-            .take(remainder)
-            .zip(buffer.iter_mut())
-            .for_each(|(item, buf)| *buf = item);
+    #[inline]
+    fn push(&mut self, value: u32) -> std::io::Result<()> {
+        match &mut self.run {
+            Some((current, length)) if *current == value => {
+                *length += 1;
+                Ok(())
+            }
+            _ => {
+                let previous = self.run.replace((value, 1));
+                self.flush_run(previous)
+            }
+        }
+    }
 
-        let mut packed = [0u8; 4 * U32_BLOCK_LEN];
-        bitpacked::encode_pack(&buffer, num_bits, packed.as_mut());
-        writer.write_all(&packed[..compressed_remainder_size])?;
-    };
-    Ok(())
-}
+    fn finish(mut self) -> std::io::Result<()> {
+        let run = self.run.take();
+        self.flush_run(run)?;
+        self.flush_bitpacked()
+    }
 
-/// the bitpacked part of the encoder.
-pub fn encode_bool<W: Write, I: Iterator<Item = bool>>(
-    writer: &mut W,
-    iterator: I,
-) -> std::io::Result<()> {
-    // the length of the iterator.
-    let length = iterator.size_hint().1.unwrap();
+    /// Whether a run of `length` repeated values is smaller when RLE-encoded than when bit-packed.
+    /// Interrupting a bit-packed run requires an extra header, that is accounted as one byte.
+    fn rle_is_smaller(&self, length: usize) -> bool {
+        let mut container = [0; 10];
+        let header = uleb128::encode((length as u64) << 1, &mut container);
+        let rle = header + ceil8(self.num_bits) + 1;
+        rle < ceil8(length * self.num_bits)
+    }
 
-    // write the length + indicator
-    let mut header = ceil8(length) as u64;
-    header <<= 1;
-    header |= 1; // it is bitpacked => first bit is set
-    let mut container = [0; 10];
-    let used = uleb128::encode(header, &mut container);
+    /// Writes a run of repeated values, either as an RLE run or as part of the current bit-packed run.
+    fn flush_run(&mut self, run: Option<(u32, usize)>) -> std::io::Result<()> {
+        let (value, mut length) = if let Some(run) = run {
+            run
+        } else {
+            return Ok(());
+        };
+
+        // bit-packed runs are composed of whole groups: complete the current one first
+        let remainder = self.num_values % GROUP_LEN;
+        if remainder > 0 {
+            let fill = std::cmp::min(length, GROUP_LEN - remainder);
+            self.push_bitpacked(value, fill);
+            length -= fill;
+        }
+        if length == 0 {
+            return Ok(());
+        }
+
+        if self.rle_is_smaller(length) {
+            self.flush_bitpacked()?;
+            write_header(self.writer, (length as u64) << 1)?;
+            let bytes = value.to_le_bytes();
+            self.writer.write_all(&bytes[..ceil8(self.num_bits)])
+        } else {
+            self.push_bitpacked(value, length);
+            Ok(())
+        }
+    }
 
-    writer.write_all(&container[..used])?;
+    /// Appends `length` repetitions of `value` to the current bit-packed run.
+    fn push_bitpacked(&mut self, value: u32, length: usize) {
+        for _ in 0..length {
+            self.buffer[self.buffer_len] = value;
+            self.buffer_len += 1;
+            if self.buffer_len == U32_BLOCK_LEN {
+                self.pack_buffer();
+            }
+        }
+        self.num_values += length;
+    }
+
+    /// Packs the buffered values into `packed`. Only the bytes required by them are used.
+    fn pack_buffer(&mut self) {
+        let mut packed = [0u8; 4 * U32_BLOCK_LEN];
+        bitpacked::encode_pack(&self.buffer[..self.buffer_len], self.num_bits, &mut packed);
+        self.packed
+            .extend_from_slice(&packed[..ceil8(self.buffer_len * self.num_bits)]);
+        self.buffer_len = 0;
+    }
 
-    // encode the iterator
-    bitpacked_encode(writer, iterator)
+    /// Writes the current bit-packed run, if any. A trailing incomplete group is zero-padded.
+    fn flush_bitpacked(&mut self) -> std::io::Result<()> {
+        if self.num_values == 0 {
+            return Ok(());
+        }
+        self.pack_buffer();
+
+        // it is bitpacked => first bit is set
+        write_header(self.writer, (ceil8(self.num_values) as u64) << 1 | 1)?;
+        self.writer.write_all(&self.packed)?;
+        self.packed.clear();
+        self.num_values = 0;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -165,4 +236,50 @@ mod tests {
         assert_eq!(expected, vec);
         Ok(())
     }
+
+    #[test]
+    fn bool_rle() -> std::io::Result<()> {
+        let mut vec = vec![];
+        encode_bool(&mut vec, std::iter::repeat(true).take(1000))?;
+
+        // 1000 << 1 as uleb128, followed by the value
+        assert_eq!(vec, vec![0b11010000, 0b00001111, 1]);
+        Ok(())
+    }
+
+    #[test]
+    fn u32_rle() -> std::io::Result<()> {
+        let mut vec = vec![];
+        encode_u32(&mut vec, std::iter::repeat(3).take(100), 10)?;
+
+        // 100 << 1 as uleb128, followed by the value in 2 bytes
+        assert_eq!(vec, vec![0b11001000, 0b00000001, 3, 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn u32_mixed() -> std::io::Result<()> {
+        let values = [1, 2, 3]
+            .into_iter()
+            .chain(std::iter::repeat(4).take(37))
+            .chain([5, 6]);
+
+        let mut vec = vec![];
+        encode_u32(&mut vec, values, 4)?;
+
+        // the run of 4 first completes the group of 8 values; the remaining 32 are RLE-encoded
+        let expected = vec![
+            (1 << 1 | 1),
+            0b0010_0001,
+            0b0100_0011,
+            0b0100_0100,
+            0b0100_0100,
+            32 << 1,
+            4,
+            (1 << 1 | 1),
+            0b0110_0101,
+        ];
+        assert_eq!(vec, expected);
+        Ok(())
+    }
 }
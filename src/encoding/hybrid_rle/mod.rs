@@ -24,7 +24,8 @@ pub enum HybridEncoded<'a> {
 enum State<'a> {
     None,
     Bitpacked(bitpacked::Decoder<'a, u32>),
-    Rle(std::iter::Take<std::iter::Repeat<u32>>),
+    /// A value and its remaining number of repetitions
+    Rle(u32, usize),
     // Add a special branch for a single value to
     // adhere to the strong law of small numbers.
     Single(Option<u32>),
@@ -56,7 +57,7 @@ fn read_next<'a, 'b>(decoder: &'b mut Decoder<'a>, remaining: usize) -> Result<S
             if additional == 1 {
                 State::Single(Some(value))
             } else {
-                State::Rle(std::iter::repeat(value).take(additional))
+                State::Rle(value, additional)
             }
         }
         None => State::None,
@@ -75,6 +76,71 @@ impl<'a> HybridRleDecoder<'a> {
             remaining: num_values,
         })
     }
+
+    /// Skips `n` values, or all remaining values if there are less than `n`.
+    /// Runs that are fully skipped are not decoded.
+    ///
+    /// This is not named `skip` since [`Iterator::skip`] would take precedence over it.
+    pub fn skip_values(&mut self, n: usize) -> Result<(), Error> {
+        let mut n = n.min(self.remaining);
+        while n > 0 {
+            let skipped = match &mut self.state {
+                State::Single(opt_val) => opt_val.take().map_or(0, |_| 1),
+                State::Bitpacked(decoder) => {
+                    let skipped = n.min(decoder.size_hint().0);
+                    decoder.skip_values(skipped);
+                    skipped
+                }
+                State::Rle(_, length) => {
+                    let skipped = n.min(*length);
+                    *length -= skipped;
+                    skipped
+                }
+                State::None => n,
+            };
+            if skipped == 0 {
+                self.state = read_next(&mut self.decoder, self.remaining)?;
+            }
+            n -= skipped;
+            self.remaining -= skipped;
+        }
+        Ok(())
+    }
+
+    /// Decodes up to `out.len()` values into `out`, returning the number of values decoded.
+    /// # Implementation
+    /// RLE runs are written with [`slice::fill`] and bitpacked runs are unpacked
+    /// directly into `out` (see [`bitpacked::Decoder::decode_into`]).
+    pub fn decode_into(&mut self, out: &mut [u32]) -> Result<usize, Error> {
+        let length = out.len().min(self.remaining);
+        let mut written = 0;
+        while written < length {
+            let out = &mut out[written..length];
+            let decoded = match &mut self.state {
+                State::Single(opt_val) => opt_val.take().map_or(0, |value| {
+                    out[0] = value;
+                    1
+                }),
+                State::Bitpacked(decoder) => decoder.decode_into(out),
+                State::Rle(value, remaining) => {
+                    let decoded = out.len().min(*remaining);
+                    out[..decoded].fill(*value);
+                    *remaining -= decoded;
+                    decoded
+                }
+                State::None => {
+                    out.fill(0);
+                    out.len()
+                }
+            };
+            if decoded == 0 {
+                self.state = read_next(&mut self.decoder, self.remaining)?;
+            }
+            written += decoded;
+            self.remaining -= decoded;
+        }
+        Ok(length)
+    }
 }
 
 impl<'a> Iterator for HybridRleDecoder<'a> {
@@ -91,7 +157,10 @@ impl<'a> Iterator for HybridRleDecoder<'a> {
                 opt_val.take()
             }
             State::Bitpacked(decoder) => decoder.next(),
-            State::Rle(iter) => iter.next(),
+            State::Rle(value, length) => (*length > 0).then(|| {
+                *length -= 1;
+                *value
+            }),
             State::None => Some(0),
         };
         if let Some(result) = result {
@@ -108,6 +177,13 @@ impl<'a> Iterator for HybridRleDecoder<'a> {
         }
     }
 
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if let Err(e) = self.skip_values(n) {
+            return Some(Err(e));
+        }
+        self.next()
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         (self.remaining, Some(self.remaining))
     }
@@ -117,6 +193,7 @@ impl<'a> ExactSizeIterator for HybridRleDecoder<'a> {}
 
 #[cfg(test)]
 mod tests {
+    use super::super::ceil8;
     use super::*;
 
     #[test]
@@ -136,6 +213,87 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn roundtrip_runs() -> Result<(), Error> {
+        let mut buffer = vec![];
+        let num_bits = 3u32;
+
+        let data = (0..1000u32)
+            .map(|x| if x % 100 < 60 { 7 } else { x % 5 })
+            .collect::<Vec<_>>();
+
+        encode_u32(&mut buffer, data.iter().cloned(), num_bits).unwrap();
+        assert!(buffer.len() < ceil8(data.len() * num_bits as usize));
+
+        let decoder = HybridRleDecoder::try_new(&buffer, num_bits, data.len())?;
+
+        let result = decoder.collect::<Result<Vec<_>, _>>()?;
+
+        assert_eq!(result, data);
+        Ok(())
+    }
+
+    #[test]
+    fn skip() -> Result<(), Error> {
+        let mut buffer = vec![];
+        let num_bits = 3u32;
+
+        // mixes RLE runs, bitpacked runs and single-valued runs
+        let data = (0..1000u32)
+            .map(|x| if x % 100 < 60 { 7 } else { x % 5 })
+            .chain(std::iter::once(1))
+            .collect::<Vec<_>>();
+        encode_u32(&mut buffer, data.iter().cloned(), num_bits).unwrap();
+
+        for n in [0, 1, 8, 59, 60, 61, 250, 999, 1000, 1001, 2000] {
+            let mut decoder = HybridRleDecoder::try_new(&buffer, num_bits, data.len())?;
+            decoder.skip_values(n)?;
+            assert_eq!(decoder.size_hint().0, data.len().saturating_sub(n));
+            let result = decoder.collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(result, data.iter().skip(n).copied().collect::<Vec<_>>());
+        }
+
+        // skipping repeatedly is equivalent to `nth`
+        let mut decoder = HybridRleDecoder::try_new(&buffer, num_bits, data.len())?;
+        let result = std::iter::from_fn(|| decoder.nth(9)).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            result,
+            data.iter().skip(9).step_by(10).copied().collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn decode_into() -> Result<(), Error> {
+        let mut buffer = vec![];
+        let num_bits = 3u32;
+
+        let data = (0..1000u32)
+            .map(|x| if x % 100 < 60 { 7 } else { x % 5 })
+            .chain(std::iter::once(1))
+            .collect::<Vec<_>>();
+        encode_u32(&mut buffer, data.iter().cloned(), num_bits).unwrap();
+
+        for chunk in [1, 7, 32, 100, 2000] {
+            let mut decoder = HybridRleDecoder::try_new(&buffer, num_bits, data.len())?;
+            let mut result = vec![];
+            let mut out = vec![0; chunk];
+            loop {
+                let decoded = decoder.decode_into(&mut out)?;
+                if decoded == 0 {
+                    break;
+                }
+                result.extend_from_slice(&out[..decoded]);
+                // interleave with the iterator
+                if let Some(value) = decoder.next() {
+                    result.push(value?);
+                }
+            }
+            assert_eq!(result, data);
+        }
+        Ok(())
+    }
+
     #[test]
     fn pyarrow_integration() -> Result<(), Error> {
         // data encoded from pyarrow representing (0..1000)
@@ -0,0 +1,117 @@
+//! Decodes the deprecated [BIT_PACKED](https://github.com/apache/parquet-format/blob/master/Encodings.md#bit-packed-deprecated-bit_packed--4)
+//! encoding, still found in the definition and repetition levels of files written by old
+//! versions of parquet-mr and Impala.
+//!
+//! Contrarily to the bitpacked runs of [`super::hybrid_rle`], values are packed from the most
+//! significant bit of each byte to the least significant bit.
+use crate::error::Error;
+
+use super::ceil8;
+
+/// Returns the number of bytes of `length` values encoded with `num_bits`.
+#[inline]
+pub fn encoded_length(length: usize, num_bits: usize) -> usize {
+    ceil8(length * num_bits)
+}
+
+/// An [`Iterator`] of [`u32`] decoded from a `BIT_PACKED`-encoded slice of bytes.
+#[derive(Debug, Clone)]
+pub struct Decoder<'a> {
+    packed: &'a [u8],
+    num_bits: usize,
+    position: usize, // in number of items
+    length: usize,   // in number of items
+}
+
+impl<'a> Decoder<'a> {
+    /// Returns a [`Decoder`] of `length` values encoded in `packed` with `num_bits`.
+    /// # Errors
+    /// Errors iff `num_bits > 32` or `packed` has less than `length` values.
+    pub fn try_new(packed: &'a [u8], num_bits: usize, length: usize) -> Result<Self, Error> {
+        if num_bits > 32 {
+            return Err(Error::oos("BIT_PACKED requires num_bits <= 32"));
+        }
+        if packed.len() < encoded_length(length, num_bits) {
+            return Err(Error::oos(format!(
+                "Decoding {length} BIT_PACKED items with a number of bits {num_bits} requires at least {} bytes.",
+                encoded_length(length, num_bits)
+            )));
+        }
+        Ok(Self {
+            packed,
+            num_bits,
+            position: 0,
+            length,
+        })
+    }
+
+    #[inline]
+    fn get(&self, index: usize) -> u32 {
+        let start = index * self.num_bits;
+        (start..start + self.num_bits).fold(0u32, |value, bit| {
+            let is_set = (self.packed[bit / 8] >> (7 - bit % 8)) & 1;
+            (value << 1) | is_set as u32
+        })
+    }
+}
+
+impl<'a> Iterator for Decoder<'a> {
+    type Item = u32;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position == self.length {
+            return None;
+        }
+        let value = self.get(self.position);
+        self.position += 1;
+        Some(value)
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.position = self.position.saturating_add(n).min(self.length);
+        self.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.length - self.position;
+        (remaining, Some(remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spec() -> Result<(), Error> {
+        // the example of the spec: 0-7 with bit width 3
+        let data = [0b00000101, 0b00111001, 0b01110111];
+
+        let decoded = Decoder::try_new(&data, 3, 8)?.collect::<Vec<_>>();
+        assert_eq!(decoded, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+
+        let mut decoder = Decoder::try_new(&data, 3, 8)?;
+        assert_eq!(decoder.nth(5), Some(5));
+        assert_eq!(decoder.size_hint(), (2, Some(2)));
+        assert_eq!(decoder.nth(2), None);
+        Ok(())
+    }
+
+    #[test]
+    fn msb_first() -> Result<(), Error> {
+        let data = [0b10110000, 0b1_0000000];
+
+        let decoded = Decoder::try_new(&data, 1, 9)?.collect::<Vec<_>>();
+        assert_eq!(decoded, vec![1, 0, 1, 1, 0, 0, 0, 0, 1]);
+        Ok(())
+    }
+
+    #[test]
+    fn too_short() {
+        assert!(Decoder::try_new(&[0, 0], 3, 6).is_err());
+        assert!(Decoder::try_new(&[0, 0], 33, 0).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+//! Encodes and decodes fixed-width values according to
+//! [Plain](https://github.com/apache/parquet-format/blob/master/Encodings.md#plain-plain--0).
+//! See [`super::plain_byte_array`] for `BYTE_ARRAY`.
+use crate::error::Error;
+use crate::types::{decode as decode_native, NativeType};
+
+use super::ceil8;
+
+/// Returns the number of values of type `T` in the PLAIN-encoded `values`.
+/// # Errors
+/// Errors iff `values.len()` is not a multiple of the size of `T`.
+pub fn num_values<T: NativeType>(values: &[u8]) -> Result<usize, Error> {
+    let size = std::mem::size_of::<T>();
+    let num_values = values.len() / size;
+    if num_values * size != values.len() {
+        return Err(Error::oos(
+            "A primitive page data's len must be a multiple of the type",
+        ));
+    }
+    Ok(num_values)
+}
+
+/// PLAIN-encodes `values` into `buffer`.
+pub fn encode<T: NativeType>(values: &[T], buffer: &mut Vec<u8>) {
+    buffer.reserve(std::mem::size_of_val(values));
+    values
+        .iter()
+        .for_each(|value| buffer.extend_from_slice(value.to_le_bytes().as_ref()));
+}
+
+/// Decodes the first `out.len()` values of the PLAIN-encoded `values` into `out`.
+/// # Errors
+/// Errors iff `values` has less than `out.len()` values.
+pub fn decode<T: NativeType>(values: &[u8], out: &mut [T]) -> Result<(), Error> {
+    let size = std::mem::size_of::<T>();
+    let values = out
+        .len()
+        .checked_mul(size)
+        .and_then(|length| values.get(..length))
+        .ok_or_else(|| Error::oos("The page has less PLAIN-encoded values than declared"))?;
+
+    values
+        .chunks_exact(size)
+        .zip(out.iter_mut())
+        .for_each(|(chunk, out)| *out = decode_native(chunk));
+    Ok(())
+}
+
+/// PLAIN-encodes booleans into `buffer`, one bit per value (LSB first).
+pub fn encode_bool(values: &[bool], buffer: &mut Vec<u8>) {
+    buffer.reserve(ceil8(values.len()));
+    values.chunks(8).for_each(|chunk| {
+        let byte = chunk
+            .iter()
+            .enumerate()
+            .fold(0u8, |byte, (i, is_set)| byte | ((*is_set as u8) << i));
+        buffer.push(byte)
+    });
+}
+
+/// Decodes the first `out.len()` booleans of the PLAIN-encoded `values` into `out`.
+/// # Errors
+/// Errors iff `values` has less than `out.len()` values.
+pub fn decode_bool(values: &[u8], out: &mut [bool]) -> Result<(), Error> {
+    if values.len() < ceil8(out.len()) {
+        return Err(Error::oos(
+            "The page has less PLAIN-encoded values than declared",
+        ));
+    }
+    out.iter_mut()
+        .enumerate()
+        .for_each(|(i, out)| *out = values[i / 8] & (1 << (i % 8)) != 0);
+    Ok(())
+}
+
+/// PLAIN-encodes `values`, a sequence of concatenated items of `size` bytes each (e.g. the values
+/// of a `FIXED_LEN_BYTE_ARRAY` column), into `buffer`.
+/// # Errors
+/// Errors iff `size` is zero or `values.len()` is not a multiple of `size`.
+pub fn encode_fixed_len(values: &[u8], size: usize, buffer: &mut Vec<u8>) -> Result<(), Error> {
+    if size == 0 || values.len() / size * size != values.len() {
+        return Err(Error::InvalidParameter(format!(
+            "The values' length ({}) must be a multiple of the item size ({})",
+            values.len(),
+            size
+        )));
+    }
+    buffer.extend_from_slice(values);
+    Ok(())
+}
+
+/// Decodes the first items of `size` bytes of the PLAIN-encoded `values` into `out`, whose
+/// length must be a multiple of `size`.
+/// # Errors
+/// Errors iff `size` is zero, `out.len()` is not a multiple of `size`, or `values` has less
+/// than `out.len() / size` items.
+pub fn decode_fixed_len(values: &[u8], size: usize, out: &mut [u8]) -> Result<(), Error> {
+    if size == 0 || out.len() / size * size != out.len() {
+        return Err(Error::InvalidParameter(format!(
+            "The output's length ({}) must be a multiple of the item size ({})",
+            out.len(),
+            size
+        )));
+    }
+    let values = values
+        .get(..out.len())
+        .ok_or_else(|| Error::oos("The page has less PLAIN-encoded values than declared"))?;
+    out.copy_from_slice(values);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn native() -> Result<(), Error> {
+        let data = vec![1i32, -2, i32::MAX, i32::MIN];
+        let mut buffer = vec![];
+        encode(&data, &mut buffer);
+        assert_eq!(buffer.len(), 16);
+        assert_eq!(&buffer[..8], &[1, 0, 0, 0, 254, 255, 255, 255]);
+        assert_eq!(num_values::<i32>(&buffer)?, 4);
+
+        let mut result = vec![0; 4];
+        decode(&buffer, &mut result)?;
+        assert_eq!(result, data);
+
+        // a prefix is also valid
+        let mut result = vec![0; 2];
+        decode(&buffer, &mut result)?;
+        assert_eq!(result, &data[..2]);
+        Ok(())
+    }
+
+    #[test]
+    fn native_f64() -> Result<(), Error> {
+        let data = vec![1.5f64, -0.0, f64::INFINITY];
+        let mut buffer = vec![];
+        encode(&data, &mut buffer);
+
+        let mut result = vec![0.0; 3];
+        decode(&buffer, &mut result)?;
+        assert_eq!(result, data);
+        Ok(())
+    }
+
+    #[test]
+    fn native_invalid() {
+        let buffer = vec![0u8; 7];
+        assert!(num_values::<i32>(&buffer).is_err());
+        let mut result = vec![0i64; 1];
+        assert!(decode(&buffer, &mut result).is_err());
+    }
+
+    #[test]
+    fn bool() -> Result<(), Error> {
+        let data = vec![
+            true, false, true, true, false, false, false, false, true, true,
+        ];
+        let mut buffer = vec![];
+        encode_bool(&data, &mut buffer);
+        assert_eq!(buffer, vec![0b00001101, 0b00000011]);
+
+        let mut result = vec![false; data.len()];
+        decode_bool(&buffer, &mut result)?;
+        assert_eq!(result, data);
+
+        let mut result = vec![false; 17];
+        assert!(decode_bool(&buffer, &mut result).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn fixed_len() -> Result<(), Error> {
+        let data = b"aabbcc";
+        let mut buffer = vec![];
+        encode_fixed_len(data, 2, &mut buffer)?;
+        assert_eq!(buffer, data);
+        assert!(encode_fixed_len(data, 4, &mut buffer).is_err());
+        assert!(encode_fixed_len(data, 0, &mut buffer).is_err());
+
+        let mut result = vec![0; 4];
+        decode_fixed_len(&buffer, 2, &mut result)?;
+        assert_eq!(result, b"aabb");
+        assert!(decode_fixed_len(&buffer, 3, &mut result).is_err());
+
+        let mut result = vec![0; 8];
+        assert!(decode_fixed_len(&buffer, 2, &mut result).is_err());
+        Ok(())
+    }
+}
@@ -15,10 +15,12 @@ pub enum Feature {
     Lz4,
     /// Zstd compression and decompression
     Zstd,
+    /// Lzo compression and decompression
+    Lzo,
 }
 
 /// Errors generated by this crate
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum Error {
     /// When the parquet file is known to be out of spec.
@@ -31,6 +33,8 @@ pub enum Error {
     InvalidParameter(String),
     /// When decoding or decompressing, the page would allocate more memory than allowed
     WouldOverAllocate,
+    /// When reading or decompressing, the [`crate::read::MemoryBudget`] has not enough memory available
+    MemoryBudgetExceeded(String),
 }
 
 impl Error {
@@ -63,6 +67,9 @@ impl std::fmt::Display for Error {
             Error::WouldOverAllocate => {
                 write!(fmt, "Operation would exceed memory use threshold")
             }
+            Error::MemoryBudgetExceeded(message) => {
+                write!(fmt, "Memory budget exceeded: {}", message)
+            }
         }
     }
 }
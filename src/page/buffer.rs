@@ -0,0 +1,208 @@
+use std::sync::Arc;
+
+/// A reference-counted, immutable byte buffer, e.g. a whole parquet file in memory.
+///
+/// Cloning and slicing it is cheap: slices share the same memory, which is released
+/// once the last slice is dropped.
+#[derive(Clone)]
+pub struct SharedBuffer {
+    data: Arc<dyn AsRef<[u8]> + Send + Sync>,
+    offset: usize,
+    length: usize,
+}
+
+impl std::fmt::Debug for SharedBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedBuffer")
+            .field("offset", &self.offset)
+            .field("length", &self.length)
+            .finish()
+    }
+}
+
+impl SharedBuffer {
+    /// Returns a new [`SharedBuffer`] over all bytes of `data`, e.g. a [`Vec<u8>`].
+    pub fn new<T: AsRef<[u8]> + Send + Sync + 'static>(data: T) -> Self {
+        Self::from_arc(Arc::new(data))
+    }
+
+    /// Returns a new [`SharedBuffer`] over all bytes of `data`, sharing it with its other owners.
+    pub fn from_arc(data: Arc<dyn AsRef<[u8]> + Send + Sync>) -> Self {
+        let length = (*data).as_ref().len();
+        Self {
+            data,
+            offset: 0,
+            length,
+        }
+    }
+
+    /// The number of bytes of this buffer
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Whether this buffer has no bytes
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// The bytes of this buffer
+    pub fn as_slice(&self) -> &[u8] {
+        &(*self.data).as_ref()[self.offset..self.offset + self.length]
+    }
+
+    /// Returns the `length` bytes starting at `offset` of this buffer, sharing its memory,
+    /// or [`None`] if they are out of bounds.
+    pub fn slice(&self, offset: usize, length: usize) -> Option<Self> {
+        let end = offset.checked_add(length)?;
+        (end <= self.length).then(|| Self {
+            data: self.data.clone(),
+            offset: self.offset + offset,
+            length,
+        })
+    }
+}
+
+impl std::ops::Deref for SharedBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for SharedBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl From<Vec<u8>> for SharedBuffer {
+    fn from(data: Vec<u8>) -> Self {
+        Self::new(data)
+    }
+}
+
+impl From<Arc<Vec<u8>>> for SharedBuffer {
+    fn from(data: Arc<Vec<u8>>) -> Self {
+        Self::from_arc(data)
+    }
+}
+
+/// The buffer of a page: either owned, or a slice of a [`SharedBuffer`] when the page was
+/// read without copying it (see [`crate::read::MemoryPageReader`]).
+#[derive(Debug, Clone)]
+pub enum PageBuffer {
+    /// A buffer owned by the page, that can be re-used by the next pages
+    Owned(Vec<u8>),
+    /// A buffer sharing the memory of a [`SharedBuffer`]
+    Shared(SharedBuffer),
+}
+
+impl PageBuffer {
+    /// The bytes of this buffer
+    pub fn as_slice(&self) -> &[u8] {
+        match self {
+            Self::Owned(buffer) => buffer,
+            Self::Shared(buffer) => buffer,
+        }
+    }
+
+    /// Whether this buffer shares the memory of a [`SharedBuffer`]
+    pub fn is_shared(&self) -> bool {
+        matches!(self, Self::Shared(_))
+    }
+
+    /// Returns a mutable reference to the owned buffer, copying a shared buffer first.
+    pub fn to_mut(&mut self) -> &mut Vec<u8> {
+        if let Self::Shared(buffer) = self {
+            *self = Self::Owned(buffer.to_vec());
+        }
+        match self {
+            Self::Owned(buffer) => buffer,
+            Self::Shared(_) => unreachable!(),
+        }
+    }
+
+    /// Returns the owned buffer, copying a shared buffer.
+    pub fn into_vec(self) -> Vec<u8> {
+        match self {
+            Self::Owned(buffer) => buffer,
+            Self::Shared(buffer) => buffer.to_vec(),
+        }
+    }
+
+    /// Takes the owned buffer to re-use it, leaving this buffer empty. A shared buffer is
+    /// released instead of copied, and an empty [`Vec<u8>`] is returned.
+    pub fn take_owned(&mut self) -> Vec<u8> {
+        match std::mem::take(self) {
+            Self::Owned(buffer) => buffer,
+            Self::Shared(_) => vec![],
+        }
+    }
+}
+
+impl Default for PageBuffer {
+    fn default() -> Self {
+        Self::Owned(vec![])
+    }
+}
+
+impl std::ops::Deref for PageBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for PageBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl From<Vec<u8>> for PageBuffer {
+    fn from(buffer: Vec<u8>) -> Self {
+        Self::Owned(buffer)
+    }
+}
+
+impl From<SharedBuffer> for PageBuffer {
+    fn from(buffer: SharedBuffer) -> Self {
+        Self::Shared(buffer)
+    }
+}
+
+impl PartialEq for PageBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for PageBuffer {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared() {
+        let data = SharedBuffer::new((0..10u8).collect::<Vec<_>>());
+        let slice = data.slice(2, 5).unwrap();
+        assert_eq!(slice.as_slice(), &[2, 3, 4, 5, 6]);
+        assert_eq!(slice.slice(3, 2).unwrap().as_slice(), &[5, 6]);
+        assert!(slice.slice(3, 3).is_none());
+        assert!(data.slice(usize::MAX, 2).is_none());
+
+        let mut buffer = PageBuffer::from(slice);
+        assert!(buffer.is_shared());
+        assert_eq!(buffer.take_owned(), Vec::<u8>::new());
+        assert!(buffer.is_empty());
+
+        let mut buffer = PageBuffer::from(data.slice(0, 2).unwrap());
+        buffer.to_mut().push(2);
+        assert!(!buffer.is_shared());
+        assert_eq!(buffer, PageBuffer::from(vec![0, 1, 2]));
+    }
+}
@@ -1,17 +1,20 @@
+mod buffer;
+
 use std::sync::Arc;
 
 pub use crate::thrift_format::{
     DataPageHeader as DataPageHeaderV1, DataPageHeaderV2, PageHeader as ParquetPageHeader,
 };
+pub use buffer::{PageBuffer, SharedBuffer};
 
 use crate::indexes::Interval;
-pub use crate::parquet_bridge::{DataPageHeaderExt, PageType};
+pub use crate::parquet_bridge::{DataPageHeaderExt, PageType, Version};
 
-use crate::compression::Compression;
-use crate::encoding::{get_length, Encoding};
+use crate::compression::{Compression, ZstdDictionary};
+use crate::encoding::{get_bit_width, get_length, legacy_bitpacked, Encoding};
 use crate::error::{Error, Result};
 use crate::metadata::Descriptor;
-
+use crate::read::MemoryReservation;
 use crate::statistics::{deserialize_statistics, Statistics};
 
 /// A [`CompressedDataPage`] is compressed, encoded representation of a Parquet data page.
@@ -19,10 +22,13 @@ use crate::statistics::{deserialize_statistics, Statistics};
 #[derive(Debug)]
 pub struct CompressedDataPage {
     pub(crate) header: DataPageHeader,
-    pub(crate) buffer: Vec<u8>,
+    pub(crate) buffer: PageBuffer,
     pub(crate) compression: Compression,
     uncompressed_page_size: usize,
     pub(crate) descriptor: Descriptor,
+    zstd_dictionary: Option<Arc<ZstdDictionary>>,
+    // the memory of this page, released when the page is dropped
+    reservation: Option<MemoryReservation>,
 
     // The offset and length in rows
     pub(crate) selected_rows: Option<Vec<Interval>>,
@@ -40,7 +46,7 @@ impl CompressedDataPage {
     ) -> Self {
         Self::new_read(
             header,
-            buffer,
+            buffer.into(),
             compression,
             uncompressed_page_size,
             descriptor,
@@ -51,7 +57,7 @@ impl CompressedDataPage {
     /// Returns a new [`CompressedDataPage`].
     pub(crate) fn new_read(
         header: DataPageHeader,
-        buffer: Vec<u8>,
+        buffer: PageBuffer,
         compression: Compression,
         uncompressed_page_size: usize,
         descriptor: Descriptor,
@@ -64,6 +70,8 @@ impl CompressedDataPage {
             uncompressed_page_size,
             descriptor,
             selected_rows,
+            zstd_dictionary: None,
+            reservation: None,
         }
     }
 
@@ -86,6 +94,27 @@ impl CompressedDataPage {
         self.compression
     }
 
+    /// Whether the data of this page is compressed, i.e. its compression is not
+    /// [`Compression::Uncompressed`] and, for V2 pages, its header does not set `is_compressed = false`.
+    pub fn is_compressed(&self) -> bool {
+        let is_compressed = match &self.header {
+            DataPageHeader::V1(_) => true,
+            // When is_compressed flag is missing the page is considered compressed
+            DataPageHeader::V2(header) => header.is_compressed.unwrap_or(true),
+        };
+        is_compressed && self.compression != Compression::Uncompressed
+    }
+
+    /// The zstd dictionary this page is compressed with, if any.
+    pub fn zstd_dictionary(&self) -> Option<&Arc<ZstdDictionary>> {
+        self.zstd_dictionary.as_ref()
+    }
+
+    /// Sets the zstd dictionary this page is compressed with.
+    pub fn set_zstd_dictionary(&mut self, zstd_dictionary: Option<Arc<ZstdDictionary>>) {
+        self.zstd_dictionary = zstd_dictionary;
+    }
+
     /// the rows to be selected by this page.
     /// When `None`, all rows are to be considered.
     pub fn selected_rows(&self) -> Option<&[Interval]> {
@@ -136,7 +165,7 @@ impl DataPageHeader {
 #[derive(Debug, Clone)]
 pub struct DataPage {
     pub(super) header: DataPageHeader,
-    pub(super) buffer: Vec<u8>,
+    pub(super) buffer: PageBuffer,
     pub descriptor: Descriptor,
     pub selected_rows: Option<Vec<Interval>>,
 }
@@ -150,7 +179,7 @@ impl DataPage {
     ) -> Self {
         Self::new_read(
             header,
-            buffer,
+            buffer.into(),
             descriptor,
             rows.map(|x| vec![Interval::new(0, x)]),
         )
@@ -158,7 +187,7 @@ impl DataPage {
 
     pub(crate) fn new_read(
         header: DataPageHeader,
-        buffer: Vec<u8>,
+        buffer: PageBuffer,
         descriptor: Descriptor,
         selected_rows: Option<Vec<Interval>>,
     ) -> Self {
@@ -186,8 +215,15 @@ impl DataPage {
 
     /// Returns a mutable reference to the internal buffer.
     /// Useful to recover the buffer after the page has been decoded.
+    /// A buffer shared with other pages (see [`DataPage::is_shared`]) is copied first.
     pub fn buffer_mut(&mut self) -> &mut Vec<u8> {
-        &mut self.buffer
+        self.buffer.to_mut()
+    }
+
+    /// Whether the buffer of this page shares the memory of a [`SharedBuffer`], i.e.
+    /// the page was read without copying it.
+    pub fn is_shared(&self) -> bool {
+        self.buffer.is_shared()
     }
 
     pub fn num_values(&self) -> usize {
@@ -244,10 +280,18 @@ pub enum Page {
 impl Page {
     pub(crate) fn buffer(&mut self) -> &mut Vec<u8> {
         match self {
-            Self::Data(page) => &mut page.buffer,
+            Self::Data(page) => page.buffer.to_mut(),
             Self::Dict(page) => &mut page.buffer,
         }
     }
+
+    /// Takes the buffer of this page to re-use it, without copying shared buffers.
+    pub(crate) fn take_buffer(&mut self) -> Vec<u8> {
+        match self {
+            Self::Data(page) => page.buffer.take_owned(),
+            Self::Dict(page) => std::mem::take(&mut page.buffer),
+        }
+    }
 }
 
 /// A [`CompressedPage`] is a compressed, encoded representation of a Parquet page. It holds actual data
@@ -260,7 +304,7 @@ pub enum CompressedPage {
 }
 
 impl CompressedPage {
-    pub(crate) fn buffer(&mut self) -> &mut Vec<u8> {
+    pub(crate) fn buffer(&mut self) -> &mut PageBuffer {
         match self {
             CompressedPage::Data(page) => &mut page.buffer,
             CompressedPage::Dict(page) => &mut page.buffer,
@@ -274,6 +318,46 @@ impl CompressedPage {
         }
     }
 
+    /// Whether the data of this page is compressed (see [`CompressedDataPage::is_compressed`]).
+    pub(crate) fn is_compressed(&self) -> bool {
+        match self {
+            CompressedPage::Data(page) => page.is_compressed(),
+            CompressedPage::Dict(page) => page.compression() != Compression::Uncompressed,
+        }
+    }
+
+    /// The zstd dictionary this page is compressed with, if any.
+    pub fn zstd_dictionary(&self) -> Option<&Arc<ZstdDictionary>> {
+        match self {
+            CompressedPage::Data(page) => page.zstd_dictionary(),
+            CompressedPage::Dict(page) => page.zstd_dictionary(),
+        }
+    }
+
+    /// Sets the zstd dictionary this page is compressed with.
+    pub fn set_zstd_dictionary(&mut self, zstd_dictionary: Option<Arc<ZstdDictionary>>) {
+        match self {
+            CompressedPage::Data(page) => page.set_zstd_dictionary(zstd_dictionary),
+            CompressedPage::Dict(page) => page.set_zstd_dictionary(zstd_dictionary),
+        }
+    }
+
+    /// Ties `reservation` to this page, so that it is released when the page is dropped.
+    pub(crate) fn set_reservation(&mut self, reservation: Option<MemoryReservation>) {
+        match self {
+            CompressedPage::Data(page) => page.reservation = reservation,
+            CompressedPage::Dict(page) => page.reservation = reservation,
+        }
+    }
+
+    /// Takes the reservation of this page, e.g. to tie it to the page decompressed from it.
+    pub(crate) fn take_reservation(&mut self) -> Option<MemoryReservation> {
+        match self {
+            CompressedPage::Data(page) => page.reservation.take(),
+            CompressedPage::Dict(page) => page.reservation.take(),
+        }
+    }
+
     pub(crate) fn num_values(&self) -> usize {
         match self {
             CompressedPage::Data(page) => page.num_values(),
@@ -294,6 +378,13 @@ impl CompressedPage {
             CompressedPage::Dict(page) => page.uncompressed_page_size,
         }
     }
+
+    pub(crate) fn compressed_size(&self) -> usize {
+        match self {
+            CompressedPage::Data(page) => page.buffer.len(),
+            CompressedPage::Dict(page) => page.buffer.len(),
+        }
+    }
 }
 
 /// An uncompressed, encoded dictionary page.
@@ -317,11 +408,14 @@ impl DictPage {
 /// A compressed, encoded dictionary page.
 #[derive(Debug)]
 pub struct CompressedDictPage {
-    pub(crate) buffer: Vec<u8>,
+    pub(crate) buffer: PageBuffer,
     compression: Compression,
     pub(crate) num_values: usize,
     pub(crate) uncompressed_page_size: usize,
     pub is_sorted: bool,
+    zstd_dictionary: Option<Arc<ZstdDictionary>>,
+    // the memory of this page, released when the page is dropped
+    reservation: Option<MemoryReservation>,
 }
 
 impl CompressedDictPage {
@@ -331,6 +425,22 @@ impl CompressedDictPage {
         uncompressed_page_size: usize,
         num_values: usize,
         is_sorted: bool,
+    ) -> Self {
+        Self::new_read(
+            buffer.into(),
+            compression,
+            uncompressed_page_size,
+            num_values,
+            is_sorted,
+        )
+    }
+
+    pub(crate) fn new_read(
+        buffer: PageBuffer,
+        compression: Compression,
+        uncompressed_page_size: usize,
+        num_values: usize,
+        is_sorted: bool,
     ) -> Self {
         Self {
             buffer,
@@ -338,6 +448,8 @@ impl CompressedDictPage {
             uncompressed_page_size,
             num_values,
             is_sorted,
+            zstd_dictionary: None,
+            reservation: None,
         }
     }
 
@@ -345,55 +457,93 @@ impl CompressedDictPage {
     pub fn compression(&self) -> Compression {
         self.compression
     }
+
+    /// The zstd dictionary this page is compressed with, if any.
+    pub fn zstd_dictionary(&self) -> Option<&Arc<ZstdDictionary>> {
+        self.zstd_dictionary.as_ref()
+    }
+
+    /// Sets the zstd dictionary this page is compressed with.
+    pub fn set_zstd_dictionary(&mut self, zstd_dictionary: Option<Arc<ZstdDictionary>>) {
+        self.zstd_dictionary = zstd_dictionary;
+    }
+}
+
+/// The layout of the definition or repetition levels of a v1 page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LevelsLayout {
+    /// `RLE`-encoded levels, prefixed by their number of bytes (4 bytes, little endian)
+    Rle,
+    /// Levels encoded with the deprecated `BIT_PACKED` encoding. These are not prefixed
+    /// by their length, which is the number of bytes declared here.
+    BitPacked(usize),
+}
+
+impl LevelsLayout {
+    /// Returns the [`LevelsLayout`] of `num_values` levels up to `max_level` encoded with `encoding`.
+    /// # Errors
+    /// Errors iff `encoding` is neither `RLE` nor `BIT_PACKED`.
+    pub fn try_new(encoding: Encoding, num_values: usize, max_level: i16) -> Result<Self> {
+        match encoding {
+            Encoding::Rle => Ok(Self::Rle),
+            Encoding::BitPacked => Ok(Self::BitPacked(legacy_bitpacked::encoded_length(
+                num_values,
+                get_bit_width(max_level) as usize,
+            ))),
+            other => Err(Error::oos(format!(
+                "Levels must be encoded with RLE or BIT_PACKED (got {:?})",
+                other
+            ))),
+        }
+    }
+}
+
+/// Splits `buffer` into the levels with `layout` and the remaining of the buffer.
+fn split_levels_v1<'a>(
+    buffer: &'a [u8],
+    layout: Option<LevelsLayout>,
+    name: &str,
+) -> Result<(&'a [u8], &'a [u8])> {
+    let error = || {
+        Error::oos(format!(
+            "The number of bytes declared in v1 {name} levels is higher than the page size"
+        ))
+    };
+    let (start, length) = match layout {
+        None => return Ok((&[] as &[u8], buffer)),
+        Some(LevelsLayout::Rle) => (4, get_length(buffer).ok_or_else(error)?),
+        Some(LevelsLayout::BitPacked(length)) => (0, length),
+    };
+    Ok((
+        buffer.get(start..start + length).ok_or_else(error)?,
+        buffer.get(start + length..).ok_or_else(error)?,
+    ))
 }
 
 /// Splits the page buffer into 3 slices corresponding to (encoded rep levels, encoded def levels, encoded values) for v1 pages.
+/// The levels are `RLE`-encoded; see [`split_buffer_v1_with_layout`] for other layouts.
 #[inline]
 pub fn split_buffer_v1(
     buffer: &[u8],
     has_rep: bool,
     has_def: bool,
 ) -> Result<(&[u8], &[u8], &[u8])> {
-    let (rep, buffer) = if has_rep {
-        let level_buffer_length = get_length(buffer).ok_or_else(|| {
-            Error::oos("The number of bytes declared in v1 rep levels is higher than the page size")
-        })?;
-        (
-            buffer.get(4..4 + level_buffer_length).ok_or_else(|| {
-                Error::oos(
-                    "The number of bytes declared in v1 rep levels is higher than the page size",
-                )
-            })?,
-            buffer.get(4 + level_buffer_length..).ok_or_else(|| {
-                Error::oos(
-                    "The number of bytes declared in v1 rep levels is higher than the page size",
-                )
-            })?,
-        )
-    } else {
-        (&[] as &[u8], buffer)
-    };
-
-    let (def, buffer) = if has_def {
-        let level_buffer_length = get_length(buffer).ok_or_else(|| {
-            Error::oos("The number of bytes declared in v1 rep levels is higher than the page size")
-        })?;
-        (
-            buffer.get(4..4 + level_buffer_length).ok_or_else(|| {
-                Error::oos(
-                    "The number of bytes declared in v1 def levels is higher than the page size",
-                )
-            })?,
-            buffer.get(4 + level_buffer_length..).ok_or_else(|| {
-                Error::oos(
-                    "The number of bytes declared in v1 def levels is higher than the page size",
-                )
-            })?,
-        )
-    } else {
-        (&[] as &[u8], buffer)
-    };
+    split_buffer_v1_with_layout(
+        buffer,
+        has_rep.then_some(LevelsLayout::Rle),
+        has_def.then_some(LevelsLayout::Rle),
+    )
+}
 
+/// Splits the page buffer into 3 slices corresponding to (encoded rep levels, encoded def levels, encoded values) for v1 pages
+/// whose levels have the layouts `rep` and `def` (`None` when the page has no such levels).
+pub fn split_buffer_v1_with_layout(
+    buffer: &[u8],
+    rep: Option<LevelsLayout>,
+    def: Option<LevelsLayout>,
+) -> Result<(&[u8], &[u8], &[u8])> {
+    let (rep, buffer) = split_levels_v1(buffer, rep, "rep")?;
+    let (def, buffer) = split_levels_v1(buffer, def, "def")?;
     Ok((rep, def, buffer))
 }
 
@@ -413,11 +563,30 @@ pub fn split_buffer_v2(
 /// Splits the page buffer into 3 slices corresponding to (encoded rep levels, encoded def levels, encoded values).
 pub fn split_buffer(page: &DataPage) -> Result<(&[u8], &[u8], &[u8])> {
     match page.header() {
-        DataPageHeader::V1(_) => split_buffer_v1(
-            page.buffer(),
-            page.descriptor.max_rep_level > 0,
-            page.descriptor.max_def_level > 0,
-        ),
+        DataPageHeader::V1(header) => {
+            let num_values = page.num_values();
+            let max_rep_level = page.descriptor.max_rep_level;
+            let max_def_level = page.descriptor.max_def_level;
+            let rep = (max_rep_level > 0)
+                .then(|| {
+                    LevelsLayout::try_new(
+                        header.repetition_level_encoding(),
+                        num_values,
+                        max_rep_level,
+                    )
+                })
+                .transpose()?;
+            let def = (max_def_level > 0)
+                .then(|| {
+                    LevelsLayout::try_new(
+                        header.definition_level_encoding(),
+                        num_values,
+                        max_def_level,
+                    )
+                })
+                .transpose()?;
+            split_buffer_v1_with_layout(page.buffer(), rep, def)
+        }
         DataPageHeader::V2(header) => {
             let def_level_buffer_length: usize = header.definition_levels_byte_length.try_into()?;
             let rep_level_buffer_length: usize = header.repetition_levels_byte_length.try_into()?;
@@ -12,6 +12,22 @@ use crate::error::Error;
 #[cfg(feature = "serde_types")]
 use serde::{Deserialize, Serialize};
 
+/// The parquet version to use
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Version {
+    V1,
+    V2,
+}
+
+impl From<Version> for i32 {
+    fn from(version: Version) -> Self {
+        match version {
+            Version::V1 => 1,
+            Version::V2 => 2,
+        }
+    }
+}
+
 /// The repetition of a parquet field
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
 #[cfg_attr(feature = "serde_types", derive(Deserialize, Serialize))]
@@ -123,6 +139,22 @@ impl From<CompressionOptions> for Compression {
     }
 }
 
+impl From<Compression> for CompressionOptions {
+    /// Returns the [`CompressionOptions`] of `value` with the default compression level.
+    fn from(value: Compression) -> Self {
+        match value {
+            Compression::Uncompressed => CompressionOptions::Uncompressed,
+            Compression::Snappy => CompressionOptions::Snappy,
+            Compression::Gzip => CompressionOptions::Gzip(None),
+            Compression::Lzo => CompressionOptions::Lzo,
+            Compression::Brotli => CompressionOptions::Brotli(None),
+            Compression::Lz4 => CompressionOptions::Lz4,
+            Compression::Zstd => CompressionOptions::Zstd(None),
+            Compression::Lz4Raw => CompressionOptions::Lz4Raw,
+        }
+    }
+}
+
 impl From<CompressionOptions> for CompressionCodec {
     fn from(codec: CompressionOptions) -> Self {
         match codec {
@@ -0,0 +1,289 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use crate::error::{Error, Result};
+
+#[derive(Debug, Default)]
+struct State {
+    used: usize,
+    // tasks waiting for memory to be released, by id of the reservation they resize
+    wakers: Vec<(usize, Waker)>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    limit: usize,
+    state: Mutex<State>,
+    // the id of the next reservation
+    next_id: AtomicUsize,
+}
+
+/// A budget of memory shared by readers and decompressors, e.g. of all columns read
+/// by a query.
+///
+/// Contrarily to `max_page_size`, which limits the size of single pages, the budget limits
+/// the memory used by all pages being read and decompressed at the same time.
+/// Readers and decompressors tie a [`MemoryReservation`] of the budget to every page they
+/// return, released when the page is dropped. [`crate::read::PageReader`],
+/// [`crate::read::IndexedPageReader`] and [`crate::read::Decompressor`] error with
+/// [`Error::MemoryBudgetExceeded`] when the budget is exhausted, while async page streams
+/// wait until memory is released.
+///
+/// The budget is cheap to clone: clones share the same memory.
+#[derive(Debug, Clone)]
+pub struct MemoryBudget {
+    inner: Arc<Inner>,
+}
+
+impl MemoryBudget {
+    /// Returns a new [`MemoryBudget`] of `limit` bytes.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                limit,
+                state: Mutex::new(State::default()),
+                next_id: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// The number of bytes of this budget
+    pub fn limit(&self) -> usize {
+        self.inner.limit
+    }
+
+    /// The number of bytes currently reserved from this budget
+    pub fn used(&self) -> usize {
+        self.inner.state.lock().unwrap().used
+    }
+
+    /// The number of bytes that can currently be reserved from this budget
+    pub fn available(&self) -> usize {
+        self.limit() - self.used()
+    }
+
+    /// Returns an empty [`MemoryReservation`] of this budget, to be resized as needed.
+    pub fn reservation(&self) -> MemoryReservation {
+        MemoryReservation {
+            budget: self.clone(),
+            size: 0,
+            id: self.inner.next_id.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// Reserves `size` bytes from this budget.
+    /// # Errors
+    /// Errors iff less than `size` bytes are available.
+    pub fn try_reserve(&self, size: usize) -> Result<MemoryReservation> {
+        let mut reservation = self.reservation();
+        reservation.try_resize(size)?;
+        Ok(reservation)
+    }
+
+    /// Reserves `additional` bytes. When they are not available, `waker` is registered
+    /// to be woken when memory is released and `false` is returned; without `waker`, it errors.
+    /// A reservation has at most one registered waker, the one of its last resize.
+    fn grow(&self, additional: usize, waker: Option<(usize, &Waker)>) -> Result<bool> {
+        let mut state = self.inner.state.lock().unwrap();
+        let available = self.inner.limit - state.used;
+        if additional <= available {
+            state.used += additional;
+            return Ok(true);
+        }
+        if let Some((id, waker)) = waker {
+            match state.wakers.iter_mut().find(|(waiting, _)| *waiting == id) {
+                Some((_, registered)) if registered.will_wake(waker) => {}
+                Some((_, registered)) => *registered = waker.clone(),
+                None => state.wakers.push((id, waker.clone())),
+            }
+            Ok(false)
+        } else {
+            Err(Error::MemoryBudgetExceeded(format!(
+                "reserving {} bytes requires more than the {} bytes available of the budget of {} bytes",
+                additional, available, self.inner.limit
+            )))
+        }
+    }
+
+    fn release(&self, size: usize) {
+        if size == 0 {
+            return;
+        }
+        let wakers = {
+            let mut state = self.inner.state.lock().unwrap();
+            state.used -= size;
+            std::mem::take(&mut state.wakers)
+        };
+        wakers.into_iter().for_each(|(_, waker)| waker.wake());
+    }
+}
+
+/// Memory reserved from a [`MemoryBudget`], released back to the budget when dropped.
+#[derive(Debug)]
+pub struct MemoryReservation {
+    budget: MemoryBudget,
+    size: usize,
+    id: usize,
+}
+
+impl MemoryReservation {
+    /// The number of bytes of this reservation
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The budget of this reservation
+    pub fn budget(&self) -> &MemoryBudget {
+        &self.budget
+    }
+
+    /// Resizes this reservation to `size` bytes, reserving from or releasing to its budget.
+    /// # Errors
+    /// Errors iff the budget does not have enough bytes available, in which case the
+    /// reservation is unchanged.
+    pub fn try_resize(&mut self, size: usize) -> Result<()> {
+        if size > self.size {
+            self.budget.grow(size - self.size, None)?;
+        } else {
+            self.budget.release(self.size - size);
+        }
+        self.size = size;
+        Ok(())
+    }
+
+    /// Returns a [`Future`] resizing this reservation to `size` bytes which, contrarily to
+    /// [`MemoryReservation::try_resize`], waits until enough memory is available.
+    /// # Errors
+    /// The future errors iff `size` is larger than the limit of the budget.
+    pub fn resize(&mut self, size: usize) -> Resize<'_> {
+        Resize {
+            reservation: self,
+            size,
+        }
+    }
+
+    /// Releases all bytes of this reservation to its budget.
+    pub fn free(&mut self) {
+        self.budget.release(self.size);
+        self.size = 0;
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.free();
+        // the waker of a pending resize that was dropped
+        let mut state = self.budget.inner.state.lock().unwrap();
+        state.wakers.retain(|(id, _)| *id != self.id);
+    }
+}
+
+/// A [`Future`] resizing a [`MemoryReservation`], returned by [`MemoryReservation::resize`].
+#[derive(Debug)]
+pub struct Resize<'a> {
+    reservation: &'a mut MemoryReservation,
+    size: usize,
+}
+
+impl Future for Resize<'_> {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let size = self.size;
+        let reservation = &mut *self.reservation;
+        if size <= reservation.size {
+            return Poll::Ready(reservation.try_resize(size));
+        }
+        let limit = reservation.budget.limit();
+        if size > limit {
+            // waiting is pointless: the reservation can never fit
+            return Poll::Ready(Err(Error::MemoryBudgetExceeded(format!(
+                "reserving {} bytes requires more than the budget of {} bytes",
+                size, limit
+            ))));
+        }
+        match reservation
+            .budget
+            .grow(size - reservation.size, Some((reservation.id, cx.waker())))
+        {
+            Ok(true) => {
+                reservation.size = size;
+                Poll::Ready(Ok(()))
+            }
+            Ok(false) => Poll::Pending,
+            Err(e) => Poll::Ready(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserve() -> Result<()> {
+        let budget = MemoryBudget::new(100);
+        let mut a = budget.try_reserve(60)?;
+        assert_eq!(budget.used(), 60);
+
+        assert!(matches!(
+            budget.try_reserve(50),
+            Err(Error::MemoryBudgetExceeded(_))
+        ));
+        let b = budget.try_reserve(40)?;
+        assert_eq!(budget.available(), 0);
+
+        assert!(a.try_resize(70).is_err());
+        assert_eq!(a.size(), 60);
+        a.try_resize(10)?;
+        assert_eq!(budget.used(), 50);
+
+        drop(b);
+        a.free();
+        assert_eq!(budget.used(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn resize_waits() -> Result<()> {
+        let budget = MemoryBudget::new(100);
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        let a = budget.try_reserve(80)?;
+        let mut b = budget.reservation();
+        {
+            let mut resize = b.resize(50);
+            assert!(Pin::new(&mut resize).poll(&mut cx).is_pending());
+            drop(a);
+            assert!(matches!(
+                Pin::new(&mut resize).poll(&mut cx),
+                Poll::Ready(Ok(()))
+            ));
+        }
+        assert_eq!(b.size(), 50);
+
+        // re-polling a pending resize does not register more wakers
+        let _a = budget.try_reserve(50)?;
+        let mut c = budget.reservation();
+        {
+            let mut resize = c.resize(10);
+            for _ in 0..10 {
+                assert!(Pin::new(&mut resize).poll(&mut cx).is_pending());
+            }
+            assert_eq!(budget.inner.state.lock().unwrap().wakers.len(), 1);
+        }
+        drop(c);
+        assert!(budget.inner.state.lock().unwrap().wakers.is_empty());
+
+        // larger than the limit: waiting is pointless
+        let mut resize = b.resize(101);
+        assert!(matches!(
+            Pin::new(&mut resize).poll(&mut cx),
+            Poll::Ready(Err(_))
+        ));
+        Ok(())
+    }
+}
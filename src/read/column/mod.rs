@@ -6,12 +6,19 @@ use crate::metadata::{ColumnChunkMetaData, RowGroupMetaData};
 use crate::page::CompressedPage;
 use crate::schema::types::ParquetType;
 
-use super::{get_field_columns, get_page_iterator, PageFilter, PageReader};
+use super::{
+    get_field_columns, get_page_iterator, MemoryBudget, MemoryReservation, PageFilter,
+    PageIterator, PageReader,
+};
 
+mod reader;
 #[cfg(feature = "async")]
 #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
 mod stream;
 
+pub(crate) use reader::column_reader;
+pub use reader::{get_column_reader, ColumnBatch, ColumnChunksReader, ColumnReader};
+
 /// Returns a [`ColumnIterator`] of column chunks corresponding to `field`.
 ///
 /// Contrarily to [`get_page_iterator`] that returns a single iterator of pages, this iterator
@@ -169,6 +176,59 @@ impl MutStreamingIterator for ReadColumnIterator {
     }
 }
 
+/// An iterator of the [`CompressedPage`]s of the column chunks of a [`MutStreamingIterator`]
+/// such as [`ColumnIterator`], one column chunk after the other.
+///
+/// It is a [`PageIterator`] when the pages of every column chunk are, so that it can be
+/// decompressed by a [`super::Decompressor`].
+pub struct ColumnPages<I> {
+    columns: Option<I>,
+}
+
+impl<I> ColumnPages<I> {
+    /// Returns a new [`ColumnPages`] of the column chunks of `columns`.
+    pub fn new(columns: I) -> Self {
+        Self {
+            columns: Some(columns),
+        }
+    }
+}
+
+impl<I, P> Iterator for ColumnPages<I>
+where
+    I: MutStreamingIterator<Item = (P, ColumnChunkMetaData), Error = Error>,
+    P: Iterator<Item = Result<CompressedPage, Error>>,
+{
+    type Item = Result<CompressedPage, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((pages, _)) = self.columns.as_mut()?.get() {
+                if let Some(page) = pages.next() {
+                    return Some(page);
+                }
+            }
+            match self.columns.take().unwrap().advance() {
+                Ok(State::Some(columns)) => self.columns = Some(columns),
+                Ok(State::Finished(_)) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+impl<I, P> PageIterator for ColumnPages<I>
+where
+    I: MutStreamingIterator<Item = (P, ColumnChunkMetaData), Error = Error>,
+    P: PageIterator,
+{
+    fn swap_buffer(&mut self, buffer: &mut Vec<u8>) {
+        if let Some((pages, _)) = self.columns.as_mut().and_then(|columns| columns.get()) {
+            pages.swap_buffer(buffer)
+        }
+    }
+}
+
 /// Reads all columns that are part of the parquet field `field_name`
 /// # Implementation
 /// This operation is IO-bounded `O(C)` where C is the number of columns associated to
@@ -200,6 +260,24 @@ where
     Ok(chunk)
 }
 
+/// Reads a column chunk into memory like [`read_column`], reserving its `compressed_size`
+/// from `budget` beforehand. The memory is released to `budget` when the returned
+/// [`MemoryReservation`] is dropped.
+/// # Errors
+/// Errors with [`Error::MemoryBudgetExceeded`] when `budget` has not enough memory available.
+pub fn read_column_with_budget<R>(
+    reader: &mut R,
+    column: &ColumnChunkMetaData,
+    budget: &MemoryBudget,
+) -> Result<(Vec<u8>, MemoryReservation), Error>
+where
+    R: Read + Seek,
+{
+    let (_, length) = column.byte_range();
+    let reservation = budget.try_reserve(length as usize)?;
+    read_column(reader, column).map(|chunk| (chunk, reservation))
+}
+
 #[cfg(feature = "async")]
 #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
-pub use stream::{read_column_async, read_columns_async};
+pub use stream::{read_column_async, read_column_async_with_budget, read_columns_async};
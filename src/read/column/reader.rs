@@ -0,0 +1,471 @@
+use std::io::{Read, Seek};
+
+use crate::deserialize::ColumnValue;
+use crate::encoding::get_bit_width;
+use crate::encoding::{hybrid_rle::HybridRleDecoder, legacy_bitpacked, Encoding};
+use crate::error::{Error, Result};
+use crate::indexes::Interval;
+use crate::metadata::{Descriptor, RowGroupMetaData};
+use crate::page::{split_buffer, DataPage, Page};
+use crate::FallibleStreamingIterator;
+
+use super::super::{Decompressor, PageFilter};
+use super::{ColumnIterator, ColumnPages};
+
+/// A batch of entries of a column read by [`ColumnReader`].
+///
+/// Every entry has a definition level and a repetition level, which are only stored when the
+/// maximum definition level (resp. repetition level) of the column is larger than 0.
+/// Only entries whose definition level is the maximum definition level have a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnBatch<T> {
+    /// The values of the entries whose definition level is the maximum definition level
+    pub values: Vec<T>,
+    /// The definition levels of the entries
+    pub def_levels: Vec<u32>,
+    /// The repetition levels of the entries
+    pub rep_levels: Vec<u32>,
+}
+
+impl<T> Default for ColumnBatch<T> {
+    fn default() -> Self {
+        Self {
+            values: vec![],
+            def_levels: vec![],
+            rep_levels: vec![],
+        }
+    }
+}
+
+impl<T> ColumnBatch<T> {
+    /// The number of entries of this batch
+    pub fn len(&self) -> usize {
+        if !self.def_levels.is_empty() {
+            self.def_levels.len()
+        } else if !self.rep_levels.is_empty() {
+            self.rep_levels.len()
+        } else {
+            self.values.len()
+        }
+    }
+
+    /// Whether this batch has no entries
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A [`ColumnReader`] of the column chunks of a column in the row groups of a file
+pub type ColumnChunksReader<R, T> = ColumnReader<Decompressor<ColumnPages<ColumnIterator<R>>>, T>;
+
+/// Returns a [`ColumnReader`] of the `column`-th column of `row_groups`, reading its column
+/// chunks one row group after the other.
+/// # Errors
+/// Errors iff there are no row groups, a row group has no `column`-th column, or the values
+/// of the column cannot be read as `T`.
+pub fn get_column_reader<R: Read + Seek, T: ColumnValue>(
+    reader: R,
+    row_groups: &[RowGroupMetaData],
+    column: usize,
+    page_filter: Option<PageFilter>,
+    max_page_size: usize,
+    batch_size: usize,
+) -> Result<ColumnChunksReader<R, T>> {
+    let descriptor = row_groups
+        .first()
+        .and_then(|row_group| row_group.columns().get(column))
+        .map(|column| column.descriptor().descriptor.clone())
+        .ok_or_else(|| {
+            Error::InvalidParameter(format!(
+                "Column {} cannot be read from the first row group",
+                column
+            ))
+        })?;
+    column_reader(
+        reader,
+        row_groups,
+        column,
+        &descriptor,
+        page_filter,
+        max_page_size,
+        batch_size,
+    )
+}
+
+/// Returns a [`ColumnReader`] of the `column`-th column of `row_groups`, of `descriptor`.
+pub(crate) fn column_reader<R: Read + Seek, T: ColumnValue>(
+    reader: R,
+    row_groups: &[RowGroupMetaData],
+    column: usize,
+    descriptor: &Descriptor,
+    page_filter: Option<PageFilter>,
+    max_page_size: usize,
+    batch_size: usize,
+) -> Result<ColumnChunksReader<R, T>> {
+    let columns = row_groups
+        .iter()
+        .map(|row_group| {
+            row_group.columns().get(column).cloned().ok_or_else(|| {
+                Error::InvalidParameter(format!(
+                    "The row group has {} columns but column {} was requested",
+                    row_group.columns().len(),
+                    column
+                ))
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let columns = ColumnIterator::new(reader, columns, page_filter, vec![], max_page_size);
+    let pages = Decompressor::new(ColumnPages::new(columns), vec![]);
+    ColumnReader::try_new(pages, descriptor, batch_size)
+}
+
+/// An [`Iterator`] of [`ColumnBatch`]es of the values of a column of physical type `T`
+/// (see [`ColumnValue`]) and of their definition and repetition levels.
+///
+/// It reads the decompressed pages of a column, e.g. of every column chunk of the column
+/// (see [`ColumnPages`]) from a [`Decompressor`] or a [`super::super::BasicDecompressor`],
+/// whose codecs and memory budget it therefore uses. It decodes their dictionary page and
+/// their values of every supported encoding. Pages with selected rows (see
+/// [`crate::read::IndexedPageReader`]) only yield the entries of the selected rows.
+///
+/// Batches have `batch_size` entries, except the last one. Batches of columns with
+/// repetition levels only end at the end of a record and may therefore have more entries.
+pub struct ColumnReader<I, T: ColumnValue> {
+    pages: Option<I>,
+    batch_size: usize,
+    descriptor: Descriptor,
+    dict: Option<Vec<T>>,
+    // decoded entries not yet yielded
+    pending: ColumnBatch<T>,
+}
+
+impl<I, T> ColumnReader<I, T>
+where
+    I: FallibleStreamingIterator<Item = Page, Error = Error>,
+    T: ColumnValue,
+{
+    /// Returns a new [`ColumnReader`] of the `pages` of the column of `descriptor` in batches
+    /// of `batch_size` entries (at least one).
+    /// # Errors
+    /// Errors iff the values of the column cannot be read as `T`.
+    pub fn try_new(pages: I, descriptor: &Descriptor, batch_size: usize) -> Result<Self> {
+        let physical_type = &descriptor.primitive_type.physical_type;
+        if !T::is_compatible(physical_type) {
+            return Err(Error::InvalidParameter(format!(
+                "Values of physical type {:?} cannot be read as {}",
+                physical_type,
+                std::any::type_name::<T>()
+            )));
+        }
+        Ok(Self {
+            pages: Some(pages),
+            batch_size: batch_size.max(1),
+            descriptor: descriptor.clone(),
+            dict: None,
+            pending: ColumnBatch::default(),
+        })
+    }
+
+    /// Decodes the next page into `pending`. Returns `false` when all pages have been read.
+    fn read_page(&mut self) -> Result<bool> {
+        let page = match self.pages.as_mut() {
+            Some(pages) => pages.next()?,
+            None => None,
+        };
+        match page {
+            Some(Page::Data(page)) => {
+                if page.descriptor != self.descriptor {
+                    return Err(Error::InvalidParameter(
+                        "All pages read by a ColumnReader must be of the same column".to_string(),
+                    ));
+                }
+                decode_data_page(page, self.dict.as_deref(), &mut self.pending)?;
+            }
+            Some(Page::Dict(page)) => {
+                let mut dict = Vec::with_capacity(page.num_values);
+                T::decode(
+                    &page.buffer,
+                    Encoding::Plain,
+                    &self.descriptor.primitive_type.physical_type,
+                    page.num_values,
+                    &mut dict,
+                )?;
+                self.dict = Some(dict);
+            }
+            None => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// The number of pending entries to yield next, if enough are pending.
+    fn batch_length(&self) -> Option<usize> {
+        let rep_levels = &self.pending.rep_levels;
+        if self.pending.len() < self.batch_size {
+            None
+        } else if rep_levels.is_empty() {
+            Some(self.batch_size)
+        } else {
+            // a batch ends before the first entry of a record
+            rep_levels[self.batch_size..]
+                .iter()
+                .position(|level| *level == 0)
+                .map(|position| self.batch_size + position)
+        }
+    }
+
+    /// Takes the first `length` pending entries.
+    fn take(&mut self, length: usize) -> ColumnBatch<T> {
+        let max_def_level = self.descriptor.max_def_level as u32;
+        let pending = &mut self.pending;
+        let num_values = if pending.def_levels.is_empty() {
+            length.min(pending.values.len())
+        } else {
+            pending.def_levels[..length]
+                .iter()
+                .filter(|level| **level == max_def_level)
+                .count()
+        };
+        let values = pending.values.split_off(num_values);
+        let def_levels = pending
+            .def_levels
+            .split_off(length.min(pending.def_levels.len()));
+        let rep_levels = pending
+            .rep_levels
+            .split_off(length.min(pending.rep_levels.len()));
+        ColumnBatch {
+            values: std::mem::replace(&mut pending.values, values),
+            def_levels: std::mem::replace(&mut pending.def_levels, def_levels),
+            rep_levels: std::mem::replace(&mut pending.rep_levels, rep_levels),
+        }
+    }
+}
+
+impl<I, T> Iterator for ColumnReader<I, T>
+where
+    I: FallibleStreamingIterator<Item = Page, Error = Error>,
+    T: ColumnValue,
+{
+    type Item = Result<ColumnBatch<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(length) = self.batch_length() {
+                return Some(Ok(self.take(length)));
+            }
+            match self.read_page() {
+                Ok(true) => continue,
+                Ok(false) => {
+                    let length = self.pending.len();
+                    return (length > 0).then(|| Ok(self.take(length)));
+                }
+                Err(e) => {
+                    // the reader cannot resume after an error
+                    self.pages = None;
+                    self.pending = ColumnBatch::default();
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+/// Decodes the entries of `page` into `pending`, only those of its selected rows if any.
+fn decode_data_page<T: ColumnValue>(
+    page: &DataPage,
+    dict: Option<&[T]>,
+    pending: &mut ColumnBatch<T>,
+) -> Result<()> {
+    if let Some(selected_rows) = page.selected_rows() {
+        let mut decoded = ColumnBatch::default();
+        decode_page(page, dict, &mut decoded)?;
+        let max_def_level = page.descriptor.max_def_level as u32;
+        extend_selected(decoded, selected_rows, max_def_level, pending)
+    } else {
+        decode_page(page, dict, pending)
+    }
+}
+
+/// Decodes `num_values` levels up to `max_level` encoded with `encoding` into `levels`.
+fn decode_levels(
+    encoded: &[u8],
+    encoding: Encoding,
+    max_level: i16,
+    num_values: usize,
+    levels: &mut Vec<u32>,
+) -> Result<()> {
+    let bit_width = get_bit_width(max_level);
+    let length = levels.len();
+    if encoding == Encoding::BitPacked {
+        levels.extend(legacy_bitpacked::Decoder::try_new(
+            encoded,
+            bit_width as usize,
+            num_values,
+        )?);
+    } else {
+        for level in HybridRleDecoder::try_new(encoded, bit_width, num_values)? {
+            levels.push(level?);
+        }
+    }
+    if levels.len() - length != num_values {
+        return Err(Error::oos(
+            "The page has less levels than its number of values",
+        ));
+    }
+    Ok(())
+}
+
+/// Decodes the levels and values of `page` into `decoded`.
+fn decode_page<T: ColumnValue>(
+    page: &DataPage,
+    dict: Option<&[T]>,
+    decoded: &mut ColumnBatch<T>,
+) -> Result<()> {
+    let descriptor = &page.descriptor;
+    let num_values = page.num_values();
+    let (rep_levels, def_levels, values) = split_buffer(page)?;
+
+    if descriptor.max_rep_level > 0 {
+        decode_levels(
+            rep_levels,
+            page.repetition_level_encoding(),
+            descriptor.max_rep_level,
+            num_values,
+            &mut decoded.rep_levels,
+        )?;
+    }
+    let num_values = if descriptor.max_def_level > 0 {
+        let start = decoded.def_levels.len();
+        decode_levels(
+            def_levels,
+            page.definition_level_encoding(),
+            descriptor.max_def_level,
+            num_values,
+            &mut decoded.def_levels,
+        )?;
+        let max_def_level = descriptor.max_def_level as u32;
+        decoded.def_levels[start..]
+            .iter()
+            .filter(|level| **level == max_def_level)
+            .count()
+    } else {
+        num_values
+    };
+
+    match page.encoding() {
+        Encoding::PlainDictionary | Encoding::RleDictionary => {
+            let dict = dict.ok_or_else(|| {
+                Error::oos("A dictionary-encoded page must be preceded by a dictionary page")
+            })?;
+            // SPEC: the bit width used to encode the entry ids stored as 1 byte (max bit width = 32),
+            // SPEC: followed by the values encoded using RLE/Bit packed described above (with the given bit width).
+            let (bit_width, indices) = values
+                .split_first()
+                .ok_or_else(|| Error::oos("A dictionary-encoded page must have a bit width"))?;
+            if *bit_width > 32 {
+                return Err(Error::oos(
+                    "Bit width of dictionary pages cannot be larger than 32",
+                ));
+            }
+            let indices = HybridRleDecoder::try_new(indices, *bit_width as u32, num_values)?;
+            decoded.values.reserve(num_values);
+            for index in indices {
+                let value = dict.get(index? as usize).ok_or_else(|| {
+                    Error::oos("A dictionary index is larger than the dictionary")
+                })?;
+                decoded.values.push(value.clone());
+            }
+            Ok(())
+        }
+        encoding => T::decode(
+            values,
+            encoding,
+            &descriptor.primitive_type.physical_type,
+            num_values,
+            &mut decoded.values,
+        ),
+    }
+}
+
+/// Extends `batch` with the entries of `decoded` that belong to the rows of `selected_rows`.
+fn extend_selected<T>(
+    decoded: ColumnBatch<T>,
+    selected_rows: &[Interval],
+    max_def_level: u32,
+    batch: &mut ColumnBatch<T>,
+) -> Result<()> {
+    let length = decoded.len();
+    let mut values = decoded.values.into_iter();
+    let mut intervals = selected_rows.iter().peekable();
+    let mut row = 0;
+    for index in 0..length {
+        if index > 0 && decoded.rep_levels.get(index).copied().unwrap_or(0) == 0 {
+            row += 1;
+        }
+        while intervals
+            .peek()
+            .is_some_and(|interval| interval.start + interval.length <= row)
+        {
+            intervals.next();
+        }
+        let is_selected = intervals
+            .peek()
+            .is_some_and(|interval| interval.start <= row);
+
+        let def_level = decoded.def_levels.get(index).copied();
+        if def_level.unwrap_or(max_def_level) == max_def_level {
+            let value = values.next().ok_or_else(|| {
+                Error::oos("The page has less values than declared by its definition levels")
+            })?;
+            if is_selected {
+                batch.values.push(value);
+            }
+        }
+        if is_selected {
+            batch.def_levels.extend(def_level);
+            batch.rep_levels.extend(decoded.rep_levels.get(index));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selected_rows() -> Result<()> {
+        // rows: [1, 2], [], [3], null
+        let decoded = ColumnBatch {
+            values: vec![1, 2, 3],
+            def_levels: vec![2, 2, 1, 2, 0],
+            rep_levels: vec![0, 1, 0, 0, 0],
+        };
+        let mut batch = ColumnBatch::default();
+        extend_selected(
+            decoded.clone(),
+            &[Interval::new(0, 1), Interval::new(2, 2)],
+            2,
+            &mut batch,
+        )?;
+        assert_eq!(
+            batch,
+            ColumnBatch {
+                values: vec![1, 2, 3],
+                def_levels: vec![2, 2, 2, 0],
+                rep_levels: vec![0, 1, 0, 0],
+            }
+        );
+
+        let mut batch = ColumnBatch::default();
+        extend_selected(decoded, &[Interval::new(1, 1)], 2, &mut batch)?;
+        assert_eq!(
+            batch,
+            ColumnBatch {
+                values: vec![],
+                def_levels: vec![1],
+                rep_levels: vec![0],
+            }
+        );
+        Ok(())
+    }
+}
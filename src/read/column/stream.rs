@@ -3,7 +3,7 @@ use futures::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
 
 use crate::error::Error;
 use crate::metadata::ColumnChunkMetaData;
-use crate::read::get_field_columns;
+use crate::read::{get_field_columns, MemoryBudget, MemoryReservation};
 
 /// Reads a single column chunk into memory asynchronously
 pub async fn read_column_async<'b, R, F>(
@@ -24,6 +24,25 @@ where
     Result::Ok(chunk)
 }
 
+/// Reads a single column chunk into memory asynchronously like [`read_column_async`],
+/// waiting until its `compressed_size` can be reserved from `budget`. The memory is released
+/// to `budget` when the returned [`MemoryReservation`] is dropped.
+pub async fn read_column_async_with_budget<'b, R, F>(
+    factory: F,
+    meta: &ColumnChunkMetaData,
+    budget: &MemoryBudget,
+) -> Result<(Vec<u8>, MemoryReservation), Error>
+where
+    R: AsyncRead + AsyncSeek + Send + Unpin,
+    F: Fn() -> BoxFuture<'b, std::io::Result<R>>,
+{
+    let (_, length) = meta.byte_range();
+    let mut reservation = budget.reservation();
+    reservation.resize(length as usize).await?;
+    let chunk = read_column_async(factory, meta).await?;
+    Ok((chunk, reservation))
+}
+
 /// Reads all columns that are part of the parquet field `field_name`
 /// # Implementation
 /// This operation is IO-bounded `O(C)` where C is the number of columns associated to
@@ -1,15 +1,29 @@
 use parquet_format_safe::DataPageHeaderV2;
 use streaming_decompression;
 
-use crate::compression::{self, Compression};
+use crate::compression::{self, CodecRegistry, Compression, ZstdDictionary};
 use crate::error::{Error, Result};
-use crate::page::{CompressedPage, DataPage, DataPageHeader, DictPage, Page};
+use crate::page::{CompressedPage, DataPage, DataPageHeader, DictPage, Page, PageBuffer};
 use crate::FallibleStreamingIterator;
 
 use super::page::PageIterator;
+use super::{MemoryBudget, MemoryReservation};
 
-fn decompress_v1(compressed: &[u8], compression: Compression, buffer: &mut [u8]) -> Result<()> {
-    compression::decompress(compression, compressed, buffer)
+/// Decompresses with the codec of `compression` in `registry`, or with `zstd_dictionary`
+/// when decompressing zstd.
+fn decompress_v1(
+    compressed: &[u8],
+    compression: Compression,
+    buffer: &mut [u8],
+    registry: &CodecRegistry,
+    zstd_dictionary: Option<&ZstdDictionary>,
+) -> Result<()> {
+    match (zstd_dictionary, compression) {
+        (Some(dictionary), Compression::Zstd) => {
+            compression::decompress_with_zstd_dictionary(dictionary, compressed, buffer)
+        }
+        _ => registry.decompress(compression, compressed, buffer),
+    }
 }
 
 fn decompress_v2(
@@ -17,47 +31,43 @@ fn decompress_v2(
     page_header: &DataPageHeaderV2,
     compression: Compression,
     buffer: &mut [u8],
+    registry: &CodecRegistry,
+    zstd_dictionary: Option<&ZstdDictionary>,
 ) -> Result<()> {
-    // When processing data page v2, depending on enabled compression for the
-    // page, we should account for uncompressed data ('offset') of
-    // repetition and definition levels.
-    //
-    // We always use 0 offset for other pages other than v2, `true` flag means
-    // that compression will be applied if decompressor is defined
+    // When processing data page v2, we should account for the uncompressed data ('offset')
+    // of repetition and definition levels.
+    // Pages with `is_compressed = false` are not decompressed (see `decompress_buffer`).
     let offset = (page_header.definition_levels_byte_length
         + page_header.repetition_levels_byte_length) as usize;
-    // When is_compressed flag is missing the page is considered compressed
-    let can_decompress = page_header.is_compressed.unwrap_or(true);
-
-    if can_decompress {
-        if offset > buffer.len() || offset > compressed.len() {
-            return Err(Error::OutOfSpec(
-                "V2 Page Header reported incorrect offset to compressed data".to_string(),
-            ));
-        }
 
-        (buffer[..offset]).copy_from_slice(&compressed[..offset]);
-
-        compression::decompress(compression, &compressed[offset..], &mut buffer[offset..])?;
-    } else {
-        if buffer.len() != compressed.len() {
-            return Err(Error::OutOfSpec(
-                "V2 Page Header reported incorrect decompressed size".to_string(),
-            ));
-        }
-        buffer.copy_from_slice(compressed);
+    if offset > buffer.len() || offset > compressed.len() {
+        return Err(Error::OutOfSpec(
+            "V2 Page Header reported incorrect offset to compressed data".to_string(),
+        ));
     }
-    Ok(())
+
+    (buffer[..offset]).copy_from_slice(&compressed[..offset]);
+
+    decompress_v1(
+        &compressed[offset..],
+        compression,
+        &mut buffer[offset..],
+        registry,
+        zstd_dictionary,
+    )
 }
 
-/// decompresses a [`CompressedDataPage`] into `buffer`.
-/// If the page is un-compressed, `buffer` is swapped instead.
+/// decompresses a [`CompressedDataPage`] into `buffer` with the codecs of `registry`.
+/// Pages with a zstd dictionary are decompressed with it.
+/// If the page is un-compressed (including V2 pages with `is_compressed = false`),
+/// `buffer` is swapped instead (copying the buffer of the page first if it is shared).
 /// Returns whether the page was decompressed.
 pub fn decompress_buffer(
     compressed_page: &mut CompressedPage,
     buffer: &mut Vec<u8>,
+    registry: &CodecRegistry,
 ) -> Result<bool> {
-    if compressed_page.compression() != Compression::Uncompressed {
+    if compressed_page.is_compressed() {
         // prepare the compression buffer
         let read_size = compressed_page.uncompressed_size();
 
@@ -72,30 +82,46 @@ pub fn decompress_buffer(
         } else {
             buffer.truncate(read_size);
         }
+
         match compressed_page {
-            CompressedPage::Data(compressed_page) => match compressed_page.header() {
-                DataPageHeader::V1(_) => {
-                    decompress_v1(&compressed_page.buffer, compressed_page.compression, buffer)?
+            CompressedPage::Data(compressed_page) => {
+                let zstd_dictionary = compressed_page.zstd_dictionary().map(|x| x.as_ref());
+                match compressed_page.header() {
+                    DataPageHeader::V1(_) => decompress_v1(
+                        &compressed_page.buffer,
+                        compressed_page.compression,
+                        buffer,
+                        registry,
+                        zstd_dictionary,
+                    )?,
+                    DataPageHeader::V2(header) => decompress_v2(
+                        &compressed_page.buffer,
+                        header,
+                        compressed_page.compression,
+                        buffer,
+                        registry,
+                        zstd_dictionary,
+                    )?,
                 }
-                DataPageHeader::V2(header) => decompress_v2(
-                    &compressed_page.buffer,
-                    header,
-                    compressed_page.compression,
-                    buffer,
-                )?,
-            },
-            CompressedPage::Dict(page) => decompress_v1(&page.buffer, page.compression(), buffer)?,
+            }
+            CompressedPage::Dict(page) => decompress_v1(
+                &page.buffer,
+                page.compression(),
+                buffer,
+                registry,
+                page.zstd_dictionary().map(|x| x.as_ref()),
+            )?,
         }
         Ok(true)
     } else {
         // page.buffer is already decompressed => swap it with `buffer`, making `page.buffer` the
         // decompression buffer and `buffer` the decompressed buffer
-        std::mem::swap(compressed_page.buffer(), buffer);
+        std::mem::swap(compressed_page.buffer().to_mut(), buffer);
         Ok(false)
     }
 }
 
-fn create_page(compressed_page: CompressedPage, buffer: Vec<u8>) -> Page {
+fn create_page(compressed_page: CompressedPage, buffer: PageBuffer) -> Page {
     match compressed_page {
         CompressedPage::Data(page) => Page::Data(DataPage::new_read(
             page.header,
@@ -104,7 +130,8 @@ fn create_page(compressed_page: CompressedPage, buffer: Vec<u8>) -> Page {
             page.selected_rows,
         )),
         CompressedPage::Dict(page) => Page::Dict(DictPage {
-            buffer,
+            // dictionary pages own their buffer: shared buffers are copied
+            buffer: buffer.into_vec(),
             num_values: page.num_values,
             is_sorted: page.is_sorted,
         }),
@@ -112,25 +139,45 @@ fn create_page(compressed_page: CompressedPage, buffer: Vec<u8>) -> Page {
 }
 
 /// Decompresses the page, using `buffer` for decompression.
-/// If `page.buffer.len() == 0`, there was no decompression and the buffer was moved.
-/// Else, decompression took place.
-pub fn decompress(mut compressed_page: CompressedPage, buffer: &mut Vec<u8>) -> Result<Page> {
-    decompress_buffer(&mut compressed_page, buffer)?;
-    Ok(create_page(compressed_page, std::mem::take(buffer)))
+/// If the page is un-compressed, its buffer is moved to the new page (without copying it,
+/// also when it is shared) and `buffer` is left untouched.
+/// Else, decompression took place and `buffer` is moved to the new page.
+pub fn decompress(compressed_page: CompressedPage, buffer: &mut Vec<u8>) -> Result<Page> {
+    decompress_with_registry(compressed_page, buffer, &CodecRegistry::default())
+}
+
+/// Decompresses the page like [`decompress`], with the codecs of `registry`.
+pub fn decompress_with_registry(
+    mut compressed_page: CompressedPage,
+    buffer: &mut Vec<u8>,
+    registry: &CodecRegistry,
+) -> Result<Page> {
+    let buffer = if compressed_page.is_compressed() {
+        decompress_buffer(&mut compressed_page, buffer, registry)?;
+        std::mem::take(buffer).into()
+    } else {
+        std::mem::take(compressed_page.buffer())
+    };
+    Ok(create_page(compressed_page, buffer))
 }
 
 fn decompress_reuse<P: PageIterator>(
     mut compressed_page: CompressedPage,
     iterator: &mut P,
     buffer: &mut Vec<u8>,
+    registry: &CodecRegistry,
 ) -> Result<(Page, bool)> {
-    let was_decompressed = decompress_buffer(&mut compressed_page, buffer)?;
+    let was_decompressed = compressed_page.is_compressed();
 
-    if was_decompressed {
-        iterator.swap_buffer(compressed_page.buffer())
+    let buffer = if was_decompressed {
+        decompress_buffer(&mut compressed_page, buffer, registry)?;
+        iterator.swap_buffer(&mut compressed_page.buffer().take_owned());
+        std::mem::take(buffer).into()
+    } else {
+        std::mem::take(compressed_page.buffer())
     };
 
-    let new_page = create_page(compressed_page, std::mem::take(buffer));
+    let new_page = create_page(compressed_page, buffer);
 
     Ok((new_page, was_decompressed))
 }
@@ -141,7 +188,7 @@ fn decompress_reuse<P: PageIterator>(
 /// > `PageReader(a)`, `CompressedPage(b)`, `Decompressor(c)`, `DecompressedPage(d)`
 /// ### un-compressed pages:
 /// > page iter: `a` is swapped with `b`
-/// > decompress iter: `b` is swapped with `d`, `b` is swapped with `a`
+/// > decompress iter: `b` is moved to `d`, (next iteration): `d` is swapped with `a`
 /// therefore:
 /// * `PageReader` has its buffer back
 /// * `Decompressor`'s buffer is un-used
@@ -161,10 +208,21 @@ fn decompress_reuse<P: PageIterator>(
 /// * `PageReader` has its buffer back
 /// * `Decompressor` has its buffer back
 /// * `DecompressedPage` has an empty buffer
+/// ### shared pages:
+/// > page iter: `b` shares the memory of `a` (e.g. [`crate::read::MemoryPageReader`])
+///
+/// therefore, there are no buffers to swap: `b` does not own a buffer and
+/// [`PageIterator::swap_buffer`] is a no-op:
+/// * un-compressed pages: `b` is moved to `d` without copying it
+/// * compressed pages: `b` is decompressed into `c` and `c` is moved to `d`, as above
 pub struct Decompressor<P: PageIterator> {
     iter: P,
     buffer: Vec<u8>,
+    registry: CodecRegistry,
+    budget: Option<MemoryBudget>,
     current: Option<Page>,
+    // the memory of `current`, released when `current` is recycled
+    reservation: Option<MemoryReservation>,
     was_decompressed: bool,
 }
 
@@ -174,11 +232,30 @@ impl<P: PageIterator> Decompressor<P> {
         Self {
             iter,
             buffer,
+            registry: CodecRegistry::default(),
+            budget: None,
             current: None,
+            reservation: None,
             was_decompressed: false,
         }
     }
 
+    /// Sets the [`CodecRegistry`] used to decompress pages.
+    pub fn with_registry(mut self, registry: CodecRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Reserves the memory to decompress every page from `budget`, erroring with
+    /// [`Error::MemoryBudgetExceeded`] when not enough memory is available.
+    /// The memory of a decompressed page is held until the page is recycled, i.e. until the
+    /// next page is decompressed. Un-compressed pages are not copied and instead keep the
+    /// memory reserved by the page reader, if any.
+    pub fn with_memory_budget(mut self, budget: MemoryBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
     /// Returns two buffers: the first buffer corresponds to the page buffer,
     /// the second to the decompression buffer.
     pub fn into_buffers(mut self) -> (Vec<u8>, Vec<u8>) {
@@ -195,19 +272,28 @@ impl<P: PageIterator> FallibleStreamingIterator for Decompressor<P> {
     fn advance(&mut self) -> Result<()> {
         if let Some(page) = self.current.as_mut() {
             if self.was_decompressed {
-                self.buffer = std::mem::take(page.buffer());
+                self.buffer = page.take_buffer();
             } else {
-                self.iter.swap_buffer(page.buffer());
+                self.iter.swap_buffer(&mut page.take_buffer());
             }
         }
+        self.reservation = None;
 
         let next = self
             .iter
             .next()
             .map(|x| {
-                x.and_then(|x| {
+                x.and_then(|mut x| {
+                    // un-compressed pages are not copied to the decompression buffer: the page
+                    // decompressed from them holds their memory instead
+                    self.reservation = match &self.budget {
+                        Some(budget) if x.is_compressed() => {
+                            Some(budget.try_reserve(x.uncompressed_size())?)
+                        }
+                        _ => x.take_reservation(),
+                    };
                     let (page, was_decompressed) =
-                        decompress_reuse(x, &mut self.iter, &mut self.buffer)?;
+                        decompress_reuse(x, &mut self.iter, &mut self.buffer, &self.registry)?;
                     self.was_decompressed = was_decompressed;
                     Ok(page)
                 })
@@ -222,18 +308,10 @@ impl<P: PageIterator> FallibleStreamingIterator for Decompressor<P> {
     }
 }
 
-type _Decompressor<I> = streaming_decompression::Decompressor<
-    CompressedPage,
-    Page,
-    fn(CompressedPage, &mut Vec<u8>) -> Result<Page>,
-    Error,
-    I,
->;
-
 impl streaming_decompression::Compressed for CompressedPage {
     #[inline]
     fn is_compressed(&self) -> bool {
-        self.compression() != Compression::Uncompressed
+        CompressedPage::is_compressed(self)
     }
 }
 
@@ -250,7 +328,11 @@ impl streaming_decompression::Decompressed for Page {
 /// is re-used across pages, so that a single allocation is required.
 /// If the pages are not compressed, the internal buffer is not used.
 pub struct BasicDecompressor<I: Iterator<Item = Result<CompressedPage>>> {
-    iter: _Decompressor<I>,
+    iter: I,
+    buffer: Vec<u8>,
+    registry: CodecRegistry,
+    current: Option<Page>,
+    was_decompressed: bool,
 }
 
 impl<I> BasicDecompressor<I>
@@ -260,13 +342,24 @@ where
     /// Returns a new [`BasicDecompressor`].
     pub fn new(iter: I, buffer: Vec<u8>) -> Self {
         Self {
-            iter: _Decompressor::new(iter, buffer, decompress),
+            iter,
+            buffer,
+            registry: CodecRegistry::default(),
+            current: None,
+            was_decompressed: false,
         }
     }
 
+    /// Sets the [`CodecRegistry`] used to decompress pages.
+    pub fn with_registry(mut self, registry: CodecRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
     /// Returns its internal buffer, consuming itself.
-    pub fn into_inner(self) -> Vec<u8> {
-        self.iter.into_inner()
+    pub fn into_inner(mut self) -> Vec<u8> {
+        self.buffer.clear(); // not leak information
+        self.buffer
     }
 }
 
@@ -278,10 +371,31 @@ where
     type Error = Error;
 
     fn advance(&mut self) -> Result<()> {
-        self.iter.advance()
+        if let Some(page) = self.current.as_mut() {
+            if self.was_decompressed {
+                self.buffer = page.take_buffer();
+            }
+        }
+
+        let next = self
+            .iter
+            .next()
+            .map(|x| {
+                x.and_then(|x| {
+                    self.was_decompressed = x.is_compressed();
+                    decompress_with_registry(x, &mut self.buffer, &self.registry)
+                })
+            })
+            .transpose()?;
+        self.current = next;
+        Ok(())
     }
 
     fn get(&self) -> Option<&Self::Item> {
-        self.iter.get()
+        self.current.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
     }
 }
@@ -1,22 +1,45 @@
+mod budget;
 mod column;
 mod compression;
 mod indexes;
 pub mod levels;
 mod metadata;
 mod page;
+mod planner;
+mod predicate;
+mod read_at;
+mod record;
 #[cfg(feature = "async")]
 mod stream;
 
 use std::io::{Read, Seek, SeekFrom};
 use std::sync::Arc;
 
+pub use budget::{MemoryBudget, MemoryReservation, Resize};
 pub use column::*;
-pub use compression::{decompress, BasicDecompressor, Decompressor};
+pub use compression::{decompress, decompress_with_registry, BasicDecompressor, Decompressor};
 pub use metadata::{deserialize_metadata, read_metadata, read_metadata_with_size};
+pub use page::{
+    dictionary_page_location, IndexedPageReader, MemoryPageReader, PageFilter, PageIterator,
+    PageMetaData, PageReader,
+};
 #[cfg(feature = "async")]
 #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
-pub use page::{get_page_stream, get_page_stream_from_column_start};
-pub use page::{IndexedPageReader, PageFilter, PageIterator, PageMetaData, PageReader};
+pub use page::{
+    get_indexed_page_stream, get_indexed_page_stream_with_budget,
+    get_indexed_page_stream_with_page_meta, get_page_stream, get_page_stream_from_column_start,
+    get_page_stream_with_budget,
+};
+pub use planner::{
+    CoalesceOptions, FetchedRanges, FetchedReader, RangeFetch, ReadPlanner, SeekFetch,
+};
+pub use predicate::{
+    prune, read_dictionary_filter, read_dictionary_filter_with_registry, read_dictionary_page,
+    read_dictionary_page_with_registry, DictionaryFilter, DictionaryMatches, Predicate,
+    RowGroupIndexes, RowGroupSelection,
+};
+pub use read_at::{ReadAt, ReadAtCursor};
+pub use record::{get_record_reader, RecordReader, Row, Value};
 
 #[cfg(feature = "async")]
 #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
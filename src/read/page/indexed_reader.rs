@@ -11,6 +11,11 @@ use crate::{
     parquet_bridge::Compression,
 };
 
+use std::sync::Arc;
+
+use crate::compression::ZstdDictionary;
+use crate::read::{MemoryBudget, MemoryReservation};
+
 use super::reader::{finish_page, read_page_header, PageMetaData};
 
 #[derive(Debug, Clone, Copy)]
@@ -28,6 +33,7 @@ pub struct IndexedPageReader<R: Read + Seek> {
 
     column_start: u64,
     compression: Compression,
+    zstd_dictionary: Result<Option<Arc<ZstdDictionary>>, Error>,
 
     // used to deserialize dictionary pages and attach the descriptor to every read page
     descriptor: Descriptor,
@@ -41,6 +47,9 @@ pub struct IndexedPageReader<R: Read + Seek> {
     pages: VecDeque<FilteredPage>,
 
     state: State,
+
+    // The budget the memory of every read page is reserved from
+    budget: Option<MemoryBudget>,
 }
 
 fn read_page<R: Read + Seek>(
@@ -59,17 +68,17 @@ fn read_page<R: Read + Seek>(
     reader.by_ref().take(length as u64).read_to_end(buffer)?;
 
     // deserialize [header]
-    let mut reader = Cursor::new(buffer);
-    let page_header = read_page_header(&mut reader, 1024 * 1024)?;
-    let header_size = reader.seek(SeekFrom::Current(0)).unwrap() as usize;
-    let buffer = reader.into_inner();
-
-    // copy [data]
-    data.clear();
-    data.extend_from_slice(&buffer[header_size..]);
+    let mut cursor = Cursor::new(buffer.as_slice());
+    let page_header = read_page_header(&mut cursor, 1024 * 1024)?;
+    let header_size = cursor.position() as usize;
+
+    // move [data] to `data` without copying it, re-using `data` for the next page
+    buffer.drain(..header_size);
+    std::mem::swap(buffer, data);
     Ok(page_header)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn read_dict_page<R: Read + Seek>(
     reader: &mut R,
     start: u64,
@@ -77,11 +86,19 @@ fn read_dict_page<R: Read + Seek>(
     buffer: &mut Vec<u8>,
     data: &mut Vec<u8>,
     compression: Compression,
+    zstd_dictionary: &Result<Option<Arc<ZstdDictionary>>, Error>,
     descriptor: &Descriptor,
 ) -> Result<CompressedDictPage, Error> {
     let page_header = read_page(reader, start, length, buffer, data)?;
 
-    let page = finish_page(page_header, data, compression, descriptor, None)?;
+    let page = finish_page(
+        page_header,
+        std::mem::take(data).into(),
+        compression,
+        zstd_dictionary,
+        descriptor,
+        None,
+    )?;
     if let CompressedPage::Dict(page) = page {
         Ok(page)
     } else {
@@ -91,6 +108,14 @@ fn read_dict_page<R: Read + Seek>(
     }
 }
 
+/// Returns the location `(start, length)` of the dictionary page of a column chunk starting at
+/// `column_start`, given all its `pages` (e.g. from [`crate::indexes::select_pages`]):
+/// a dictionary page exists iff the first data page is not at the start of the column.
+pub fn dictionary_page_location(column_start: u64, pages: &[FilteredPage]) -> Option<(u64, usize)> {
+    let length = (pages.first()?.start - column_start) as usize;
+    (length > 0).then_some((column_start, length))
+}
+
 impl<R: Read + Seek> IndexedPageReader<R> {
     /// Returns a new [`IndexedPageReader`].
     pub fn new(
@@ -116,14 +141,32 @@ impl<R: Read + Seek> IndexedPageReader<R> {
             reader,
             column_start: column.column_start,
             compression: column.compression,
+            zstd_dictionary: column.zstd_dictionary,
             descriptor: column.descriptor,
             buffer,
             data_buffer,
             pages,
             state: State::MaybeDict,
+            budget: None,
         }
     }
 
+    /// Reserves the memory of every page read from `budget`, erroring with
+    /// [`Error::MemoryBudgetExceeded`] when not enough memory is available.
+    /// The memory of a page is held by the page and released when it is dropped.
+    pub fn with_memory_budget(mut self, budget: MemoryBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Reserves the memory to read a page of `length` bytes.
+    fn reserve(&self, length: usize) -> Result<Option<MemoryReservation>, Error> {
+        self.budget
+            .as_ref()
+            .map(|budget| budget.try_reserve(length))
+            .transpose()
+    }
+
     /// consumes self into the reader and the two internal buffers
     pub fn into_inner(self) -> (R, Vec<u8>, Vec<u8>) {
         (self.reader, self.buffer, self.data_buffer)
@@ -135,33 +178,30 @@ impl<R: Read + Seek> IndexedPageReader<R> {
         length: usize,
         selected_rows: Vec<Interval>,
     ) -> Result<CompressedPage, Error> {
+        let reservation = self.reserve(length)?;
         // it will be read - take buffer
         let mut data = std::mem::take(&mut self.data_buffer);
 
         let page_header = read_page(&mut self.reader, start, length, &mut self.buffer, &mut data)?;
 
-        finish_page(
+        let mut page = finish_page(
             page_header,
-            &mut data,
+            data.into(),
             self.compression,
+            &self.zstd_dictionary,
             &self.descriptor,
             Some(selected_rows),
-        )
+        )?;
+        page.set_reservation(reservation);
+        Ok(page)
     }
 
     fn read_dict(&mut self) -> Option<Result<CompressedPage, Error>> {
-        // a dictionary page exists iff the first data page is not at the start of
-        // the column
-        let (start, length) = match self.pages.get(0) {
-            Some(page) => {
-                let length = (page.start - self.column_start) as usize;
-                if length > 0 {
-                    (self.column_start, length)
-                } else {
-                    return None;
-                }
-            }
-            None => return None,
+        let (start, length) =
+            dictionary_page_location(self.column_start, self.pages.make_contiguous())?;
+        let reservation = match self.reserve(length) {
+            Ok(reservation) => reservation,
+            Err(e) => return Some(Err(e)),
         };
 
         // it will be read - take buffer
@@ -174,9 +214,14 @@ impl<R: Read + Seek> IndexedPageReader<R> {
             &mut self.buffer,
             &mut data,
             self.compression,
+            &self.zstd_dictionary,
             &self.descriptor,
         );
-        Some(maybe_page.map(CompressedPage::Dict))
+        Some(maybe_page.map(|page| {
+            let mut page = CompressedPage::Dict(page);
+            page.set_reservation(reservation);
+            page
+        }))
     }
 }
 
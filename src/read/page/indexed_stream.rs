@@ -0,0 +1,183 @@
+use std::io::{Cursor, SeekFrom};
+
+use async_stream::try_stream;
+use futures::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, Stream};
+
+use crate::error::{Error, Result};
+use crate::indexes::FilteredPage;
+use crate::metadata::ColumnChunkMetaData;
+use crate::page::{CompressedPage, ParquetPageHeader};
+use crate::read::{MemoryBudget, MemoryReservation};
+
+use super::reader::{finish_page, read_page_header, PageMetaData};
+
+/// Reads the page at `start` of `length` bytes, `[header][data]`, to `buffer`
+/// and moves its `[data]` to `data`.
+async fn read_page<R: AsyncRead + AsyncSeek + Unpin + Send>(
+    reader: &mut R,
+    start: u64,
+    length: usize,
+    buffer: &mut Vec<u8>,
+    data: &mut Vec<u8>,
+) -> Result<ParquetPageHeader> {
+    // seek to the page
+    reader.seek(SeekFrom::Start(start)).await?;
+
+    // read [header][data] to buffer
+    buffer.clear();
+    buffer.try_reserve(length)?;
+    reader.take(length as u64).read_to_end(buffer).await?;
+
+    // deserialize [header]
+    let mut cursor = Cursor::new(buffer.as_slice());
+    let page_header = read_page_header(&mut cursor, 1024 * 1024)?;
+    let header_size = cursor.position() as usize;
+
+    // move [data] to `data` without copying it, re-using `data` for the next page
+    buffer.drain(..header_size);
+    std::mem::swap(buffer, data);
+    Ok(page_header)
+}
+
+/// Returns a stream of the [`CompressedPage`]s of `column` selected by `pages`, the async
+/// equivalent of [`super::IndexedPageReader`].
+///
+/// `dictionary_page` is the location `(start, length)` of the dictionary page of the column,
+/// if any (see [`super::dictionary_page_location`]); it is yielded first. Pages without selected
+/// rows are not read, and the yielded data pages have [`Some`]
+/// [`crate::page::CompressedDataPage::selected_rows()`].
+pub fn get_indexed_page_stream<'a, R: AsyncRead + AsyncSeek + Unpin + Send>(
+    reader: &'a mut R,
+    column: &ColumnChunkMetaData,
+    pages: Vec<FilteredPage>,
+    dictionary_page: Option<(u64, usize)>,
+    buffer: Vec<u8>,
+    data_buffer: Vec<u8>,
+) -> impl Stream<Item = Result<CompressedPage>> + 'a {
+    get_indexed_page_stream_with_page_meta(
+        reader,
+        column.into(),
+        pages,
+        dictionary_page,
+        buffer,
+        data_buffer,
+    )
+}
+
+/// Returns a stream of the [`CompressedPage`]s selected by `pages` like
+/// [`get_indexed_page_stream`], reserving the memory of every page from `budget`.
+/// When the budget is exhausted, the stream waits until enough memory is released
+/// before reading the next page.
+/// The memory of a page is held by the page and released when it is dropped, e.g. once
+/// decompressed.
+pub fn get_indexed_page_stream_with_budget<'a, R: AsyncRead + AsyncSeek + Unpin + Send>(
+    reader: &'a mut R,
+    column: &ColumnChunkMetaData,
+    pages: Vec<FilteredPage>,
+    dictionary_page: Option<(u64, usize)>,
+    buffer: Vec<u8>,
+    data_buffer: Vec<u8>,
+    budget: MemoryBudget,
+) -> impl Stream<Item = Result<CompressedPage>> + 'a {
+    _get_indexed_page_stream(
+        reader,
+        column.into(),
+        pages,
+        dictionary_page,
+        buffer,
+        data_buffer,
+        Some(budget),
+    )
+}
+
+/// Returns a stream of the [`CompressedPage`]s selected by `pages` like
+/// [`get_indexed_page_stream`], with [`PageMetaData`].
+pub fn get_indexed_page_stream_with_page_meta<R: AsyncRead + AsyncSeek + Unpin + Send>(
+    reader: &mut R,
+    column: PageMetaData,
+    pages: Vec<FilteredPage>,
+    dictionary_page: Option<(u64, usize)>,
+    buffer: Vec<u8>,
+    data_buffer: Vec<u8>,
+) -> impl Stream<Item = Result<CompressedPage>> + '_ {
+    _get_indexed_page_stream(
+        reader,
+        column,
+        pages,
+        dictionary_page,
+        buffer,
+        data_buffer,
+        None,
+    )
+}
+
+/// Reserves `length` bytes from `budget`, waiting until they are available.
+async fn reserve(
+    budget: Option<&MemoryBudget>,
+    length: usize,
+) -> Result<Option<MemoryReservation>> {
+    Ok(match budget {
+        Some(budget) => {
+            let mut reservation = budget.reservation();
+            reservation.resize(length).await?;
+            Some(reservation)
+        }
+        None => None,
+    })
+}
+
+fn _get_indexed_page_stream<R: AsyncRead + AsyncSeek + Unpin + Send>(
+    reader: &mut R,
+    column: PageMetaData,
+    pages: Vec<FilteredPage>,
+    dictionary_page: Option<(u64, usize)>,
+    mut buffer: Vec<u8>,
+    mut data_buffer: Vec<u8>,
+    budget: Option<MemoryBudget>,
+) -> impl Stream<Item = Result<CompressedPage>> + '_ {
+    let PageMetaData {
+        compression,
+        descriptor,
+        zstd_dictionary,
+        ..
+    } = column;
+    try_stream! {
+        if let Some((start, length)) = dictionary_page {
+            let reservation = reserve(budget.as_ref(), length).await?;
+            let page_header = read_page(reader, start, length, &mut buffer, &mut data_buffer).await?;
+            let mut page = finish_page(
+                page_header,
+                std::mem::take(&mut data_buffer).into(),
+                compression,
+                &zstd_dictionary,
+                &descriptor,
+                None,
+            )?;
+            if !matches!(page, CompressedPage::Dict(_)) {
+                Err(Error::oos(
+                    "The first page is not a dictionary page but it should",
+                ))?
+            }
+            page.set_reservation(reservation);
+            yield page;
+        }
+
+        for page in pages {
+            if page.selected_rows.is_empty() {
+                continue
+            }
+            let reservation = reserve(budget.as_ref(), page.length).await?;
+            let page_header = read_page(reader, page.start, page.length, &mut buffer, &mut data_buffer).await?;
+            let mut page = finish_page(
+                page_header,
+                std::mem::take(&mut data_buffer).into(),
+                compression,
+                &zstd_dictionary,
+                &descriptor,
+                Some(page.selected_rows),
+            )?;
+            page.set_reservation(reservation);
+            yield page;
+        }
+    }
+}
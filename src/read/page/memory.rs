@@ -0,0 +1,139 @@
+use std::convert::TryInto;
+use std::sync::Arc;
+
+use crate::compression::{Compression, ZstdDictionary};
+use crate::error::{Error, Result};
+use crate::metadata::{ColumnChunkMetaData, Descriptor};
+use crate::page::{CompressedPage, SharedBuffer};
+
+use super::reader::{finish_page, get_page_header, read_page_header};
+use super::{PageFilter, PageIterator, PageMetaData};
+
+/// A fallible [`Iterator`] of [`CompressedPage`] over a column chunk of a parquet file held
+/// in memory by a [`SharedBuffer`].
+///
+/// Contrarily to [`super::PageReader`], pages are not copied: their buffers share the memory
+/// of the [`SharedBuffer`]. Un-compressed data pages decompressed by
+/// [`crate::read::Decompressor`] or [`crate::read::BasicDecompressor`] keep sharing it, so that
+/// reading them involves no copy at all.
+pub struct MemoryPageReader {
+    // The whole file
+    data: SharedBuffer,
+
+    // The offset of the next page in `data`
+    offset: u64,
+
+    compression: Compression,
+
+    zstd_dictionary: Result<Option<Arc<ZstdDictionary>>>,
+
+    // The number of values we have seen so far.
+    seen_num_values: i64,
+
+    // The number of total values in this column chunk.
+    total_num_values: i64,
+
+    pages_filter: PageFilter,
+
+    descriptor: Descriptor,
+
+    // Maximum page size (compressed or uncompressed)
+    max_page_size: usize,
+}
+
+impl MemoryPageReader {
+    /// Returns a new [`MemoryPageReader`] of the pages of `column` in `data`, the whole file.
+    pub fn new(
+        data: SharedBuffer,
+        column: &ColumnChunkMetaData,
+        pages_filter: PageFilter,
+        max_page_size: usize,
+    ) -> Self {
+        Self::new_with_page_meta(data, column.into(), pages_filter, max_page_size)
+    }
+
+    /// Create a a new [`MemoryPageReader`] with [`PageMetaData`].
+    pub fn new_with_page_meta(
+        data: SharedBuffer,
+        reader_meta: PageMetaData,
+        pages_filter: PageFilter,
+        max_page_size: usize,
+    ) -> Self {
+        Self {
+            data,
+            offset: reader_meta.column_start,
+            compression: reader_meta.compression,
+            zstd_dictionary: reader_meta.zstd_dictionary,
+            seen_num_values: 0,
+            total_num_values: reader_meta.num_values,
+            pages_filter,
+            descriptor: reader_meta.descriptor,
+            max_page_size,
+        }
+    }
+
+    /// Returns the buffer of this reader
+    pub fn into_inner(self) -> SharedBuffer {
+        self.data
+    }
+
+    fn read_page(&mut self) -> Result<CompressedPage> {
+        let offset: usize = self.offset.try_into()?;
+        let mut reader = self
+            .data
+            .get(offset..)
+            .ok_or_else(|| Error::oos("The column chunk starts after the end of the buffer"))?;
+        let remaining = reader.len();
+        let page_header = read_page_header(&mut reader, self.max_page_size)?;
+        let header_size = remaining - reader.len();
+
+        self.seen_num_values += get_page_header(&page_header)?
+            .map(|x| x.num_values() as i64)
+            .unwrap_or_default();
+
+        let read_size: usize = page_header.compressed_page_size.try_into()?;
+        if read_size > self.max_page_size {
+            return Err(Error::WouldOverAllocate);
+        }
+
+        let start = offset + header_size;
+        let data = self
+            .data
+            .slice(start, read_size)
+            .ok_or_else(|| Error::oos("The page header reported the wrong page size"))?;
+        self.offset = (start + read_size) as u64;
+
+        finish_page(
+            page_header,
+            data.into(),
+            self.compression,
+            &self.zstd_dictionary,
+            &self.descriptor,
+            None,
+        )
+    }
+}
+
+impl PageIterator for MemoryPageReader {
+    fn swap_buffer(&mut self, _: &mut Vec<u8>) {
+        // pages share the memory of `data`: there is no buffer to re-use
+    }
+}
+
+impl Iterator for MemoryPageReader {
+    type Item = Result<CompressedPage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.seen_num_values < self.total_num_values {
+            let page = self.read_page();
+            if let Ok(CompressedPage::Data(page)) = &page {
+                // check if we should filter it (only valid for data pages)
+                if !(self.pages_filter)(&self.descriptor, page.header()) {
+                    continue;
+                }
+            }
+            return Some(page);
+        }
+        None
+    }
+}
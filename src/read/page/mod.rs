@@ -1,11 +1,15 @@
 mod indexed_reader;
+#[cfg(feature = "async")]
+mod indexed_stream;
+mod memory;
 mod reader;
 #[cfg(feature = "async")]
 mod stream;
 
 use crate::{error::Error, page::CompressedPage};
 
-pub use indexed_reader::IndexedPageReader;
+pub use indexed_reader::{dictionary_page_location, IndexedPageReader};
+pub use memory::MemoryPageReader;
 pub use reader::{PageFilter, PageMetaData, PageReader};
 
 pub trait PageIterator: Iterator<Item = Result<CompressedPage, Error>> {
@@ -14,4 +18,11 @@ pub trait PageIterator: Iterator<Item = Result<CompressedPage, Error>> {
 
 #[cfg(feature = "async")]
 #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
-pub use stream::{get_page_stream, get_page_stream_from_column_start};
+pub use stream::{get_page_stream, get_page_stream_from_column_start, get_page_stream_with_budget};
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub use indexed_stream::{
+    get_indexed_page_stream, get_indexed_page_stream_with_budget,
+    get_indexed_page_stream_with_page_meta,
+};
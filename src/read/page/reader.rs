@@ -3,18 +3,19 @@ use std::{io::Read, sync::Arc};
 
 use parquet_format_safe::thrift::protocol::TCompactInputProtocol;
 
-use crate::compression::Compression;
+use crate::compression::{Compression, ZstdDictionary};
 use crate::error::{Error, Result};
 use crate::indexes::Interval;
 use crate::metadata::{ColumnChunkMetaData, Descriptor};
 
 use crate::page::{
-    CompressedDataPage, CompressedDictPage, CompressedPage, DataPageHeader, PageType,
+    CompressedDataPage, CompressedDictPage, CompressedPage, DataPageHeader, PageBuffer, PageType,
     ParquetPageHeader,
 };
 use crate::parquet_bridge::Encoding;
 
 use super::PageIterator;
+use crate::read::MemoryBudget;
 
 /// This meta is a small part of [`ColumnChunkMetaData`].
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -27,6 +28,9 @@ pub struct PageMetaData {
     pub compression: Compression,
     /// The descriptor of this parquet column
     pub descriptor: Descriptor,
+    // The zstd dictionary of this column chunk, or the error loading it from the metadata,
+    // raised when reading the first zstd page
+    pub(crate) zstd_dictionary: Result<Option<Arc<ZstdDictionary>>>,
 }
 
 impl PageMetaData {
@@ -42,8 +46,25 @@ impl PageMetaData {
             num_values,
             compression,
             descriptor,
+            zstd_dictionary: Ok(None),
         }
     }
+
+    /// Returns this [`PageMetaData`] with the zstd dictionary of its column chunk.
+    pub fn with_zstd_dictionary(mut self, zstd_dictionary: Option<Arc<ZstdDictionary>>) -> Self {
+        self.zstd_dictionary = Ok(zstd_dictionary);
+        self
+    }
+
+    /// The zstd dictionary of this column chunk, if any.
+    /// # Errors
+    /// Errors iff the zstd dictionary stored in the metadata of the column chunk is malformed.
+    pub fn zstd_dictionary(&self) -> Result<Option<&Arc<ZstdDictionary>>> {
+        self.zstd_dictionary
+            .as_ref()
+            .map(Option::as_ref)
+            .map_err(Clone::clone)
+    }
 }
 
 impl From<&ColumnChunkMetaData> for PageMetaData {
@@ -53,6 +74,13 @@ impl From<&ColumnChunkMetaData> for PageMetaData {
             num_values: column.num_values(),
             compression: column.compression(),
             descriptor: column.descriptor().descriptor.clone(),
+            zstd_dictionary: column
+                .metadata()
+                .key_value_metadata
+                .as_deref()
+                .map(ZstdDictionary::try_from_key_values)
+                .transpose()
+                .map(|dictionary| dictionary.flatten().map(Arc::new)),
         }
     }
 }
@@ -71,6 +99,8 @@ pub struct PageReader<R: Read> {
 
     compression: Compression,
 
+    zstd_dictionary: Result<Option<Arc<ZstdDictionary>>>,
+
     // The number of values we have seen so far.
     seen_num_values: i64,
 
@@ -86,6 +116,9 @@ pub struct PageReader<R: Read> {
 
     // Maximum page size (compressed or uncompressed) to limit allocations
     max_page_size: usize,
+
+    // The budget the memory of every read page is reserved from
+    budget: Option<MemoryBudget>,
 }
 
 impl<R: Read> PageReader<R> {
@@ -117,14 +150,24 @@ impl<R: Read> PageReader<R> {
             reader,
             total_num_values: reader_meta.num_values,
             compression: reader_meta.compression,
+            zstd_dictionary: reader_meta.zstd_dictionary,
             seen_num_values: 0,
             descriptor: reader_meta.descriptor,
             pages_filter,
             scratch,
             max_page_size,
+            budget: None,
         }
     }
 
+    /// Reserves the memory of every page read from `budget`, erroring with
+    /// [`Error::MemoryBudgetExceeded`] when not enough memory is available.
+    /// The memory of a page is held by the page and released when it is dropped.
+    pub fn with_memory_budget(mut self, budget: MemoryBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
     /// Returns the reader and this Readers' interval buffer
     pub fn into_inner(self) -> (R, Vec<u8>) {
         (self.reader, self.scratch)
@@ -197,6 +240,11 @@ pub(super) fn build_page<R: Read>(
     if read_size > reader.max_page_size {
         return Err(Error::WouldOverAllocate);
     }
+    let reservation = reader
+        .budget
+        .as_ref()
+        .map(|budget| budget.try_reserve(read_size))
+        .transpose()?;
 
     buffer.clear();
     buffer.try_reserve(read_size)?;
@@ -212,26 +260,34 @@ pub(super) fn build_page<R: Read>(
         ));
     }
 
-    finish_page(
+    let mut page = finish_page(
         page_header,
-        buffer,
+        std::mem::take(buffer).into(),
         reader.compression,
+        &reader.zstd_dictionary,
         &reader.descriptor,
         None,
-    )
-    .map(Some)
+    )?;
+    page.set_reservation(reservation);
+    Ok(Some(page))
 }
 
 pub(super) fn finish_page(
     page_header: ParquetPageHeader,
-    data: &mut Vec<u8>,
+    data: PageBuffer,
     compression: Compression,
+    zstd_dictionary: &Result<Option<Arc<ZstdDictionary>>>,
     descriptor: &Descriptor,
     selected_rows: Option<Vec<Interval>>,
 ) -> Result<CompressedPage> {
+    // a malformed zstd dictionary only prevents reading zstd pages
+    let zstd_dictionary = match compression {
+        Compression::Zstd => zstd_dictionary.clone()?,
+        _ => None,
+    };
     let type_ = page_header.type_.try_into()?;
     let uncompressed_page_size = page_header.uncompressed_page_size.try_into()?;
-    match type_ {
+    let mut page = match type_ {
         PageType::DictionaryPage => {
             let dict_header = page_header.dictionary_page_header.as_ref().ok_or_else(|| {
                 Error::oos(
@@ -241,45 +297,51 @@ pub(super) fn finish_page(
             let is_sorted = dict_header.is_sorted.unwrap_or(false);
 
             // move the buffer to `dict_page`
-            let page = CompressedDictPage::new(
-                std::mem::take(data),
+            let page = CompressedDictPage::new_read(
+                data,
                 compression,
                 uncompressed_page_size,
                 dict_header.num_values.try_into()?,
                 is_sorted,
             );
 
-            Ok(CompressedPage::Dict(page))
+            CompressedPage::Dict(page)
         }
         PageType::DataPage => {
             let header = page_header.data_page_header.ok_or_else(|| {
                 Error::oos("The page header type is a v1 data page but the v1 data header is empty")
             })?;
+            let _: Encoding = header.encoding.try_into()?;
+            let _: Encoding = header.repetition_level_encoding.try_into()?;
+            let _: Encoding = header.definition_level_encoding.try_into()?;
 
-            Ok(CompressedPage::Data(CompressedDataPage::new_read(
+            CompressedPage::Data(CompressedDataPage::new_read(
                 DataPageHeader::V1(header),
-                std::mem::take(data),
+                data,
                 compression,
                 uncompressed_page_size,
                 descriptor.clone(),
                 selected_rows,
-            )))
+            ))
         }
         PageType::DataPageV2 => {
             let header = page_header.data_page_header_v2.ok_or_else(|| {
                 Error::oos("The page header type is a v2 data page but the v2 data header is empty")
             })?;
+            let _: Encoding = header.encoding.try_into()?;
 
-            Ok(CompressedPage::Data(CompressedDataPage::new_read(
+            CompressedPage::Data(CompressedDataPage::new_read(
                 DataPageHeader::V2(header),
-                std::mem::take(data),
+                data,
                 compression,
                 uncompressed_page_size,
                 descriptor.clone(),
                 selected_rows,
-            )))
+            ))
         }
-    }
+    };
+    page.set_zstd_dictionary(zstd_dictionary);
+    Ok(page)
 }
 
 pub(super) fn get_page_header(header: &ParquetPageHeader) -> Result<Option<DataPageHeader>> {
@@ -1,14 +1,16 @@
 use std::io::SeekFrom;
+use std::sync::Arc;
 
 use async_stream::try_stream;
 use futures::io::{copy, sink};
 use futures::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, Stream};
 use parquet_format_safe::thrift::protocol::TCompactInputStreamProtocol;
 
-use crate::compression::Compression;
+use crate::compression::{Compression, ZstdDictionary};
 use crate::error::{Error, Result};
 use crate::metadata::{ColumnChunkMetaData, Descriptor};
 use crate::page::{CompressedPage, ParquetPageHeader};
+use crate::read::MemoryBudget;
 
 use super::reader::{finish_page, get_page_header, PageMetaData};
 use super::PageFilter;
@@ -44,10 +46,12 @@ pub async fn get_page_stream_from_column_start<'a, R: AsyncRead + Unpin + Send>(
         reader,
         page_metadata.num_values,
         page_metadata.compression,
+        page_metadata.zstd_dictionary,
         page_metadata.descriptor,
         scratch,
         pages_filter,
         max_header_size,
+        None,
     ))
 }
 
@@ -65,21 +69,57 @@ pub async fn get_page_stream_with_page_meta<RR: AsyncRead + Unpin + Send + Async
         reader,
         page_metadata.num_values,
         page_metadata.compression,
+        page_metadata.zstd_dictionary,
         page_metadata.descriptor,
         scratch,
         pages_filter,
         max_page_size,
+        None,
     ))
 }
 
+/// Returns a stream of compressed data pages like [`get_page_stream`], reserving the memory of
+/// every page from `budget`. When the budget is exhausted, the stream waits until enough
+/// memory is released before reading the next page.
+/// The memory of a page is held by the page and released when it is dropped, e.g. once
+/// decompressed. The stream itself holds no memory while waiting, so that streams sharing
+/// a budget make progress as long as their pages are dropped.
+pub async fn get_page_stream_with_budget<'a, RR: AsyncRead + Unpin + Send + AsyncSeek>(
+    column_metadata: &'a ColumnChunkMetaData,
+    reader: &'a mut RR,
+    scratch: Vec<u8>,
+    pages_filter: PageFilter,
+    max_page_size: usize,
+    budget: MemoryBudget,
+) -> Result<impl Stream<Item = Result<CompressedPage>> + 'a> {
+    let page_metadata: PageMetaData = column_metadata.into();
+    reader
+        .seek(SeekFrom::Start(page_metadata.column_start))
+        .await?;
+    Ok(_get_page_stream(
+        reader,
+        page_metadata.num_values,
+        page_metadata.compression,
+        page_metadata.zstd_dictionary,
+        page_metadata.descriptor,
+        scratch,
+        pages_filter,
+        max_page_size,
+        Some(budget),
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
 fn _get_page_stream<R: AsyncRead + Unpin + Send>(
     reader: &mut R,
     total_num_values: i64,
     compression: Compression,
+    zstd_dictionary: Result<Option<Arc<ZstdDictionary>>>,
     descriptor: Descriptor,
     mut scratch: Vec<u8>,
     pages_filter: PageFilter,
     max_page_size: usize,
+    budget: Option<MemoryBudget>,
 ) -> impl Stream<Item = Result<CompressedPage>> + '_ {
     let mut seen_values = 0i64;
     try_stream! {
@@ -103,6 +143,14 @@ fn _get_page_stream<R: AsyncRead + Unpin + Send>(
             if read_size > max_page_size {
                 Err(Error::WouldOverAllocate)?
             }
+            let reservation = match &budget {
+                Some(budget) => {
+                    let mut reservation = budget.reservation();
+                    reservation.resize(read_size).await?;
+                    Some(reservation)
+                }
+                None => None,
+            };
 
             // followed by the buffer
             scratch.clear();
@@ -117,13 +165,16 @@ fn _get_page_stream<R: AsyncRead + Unpin + Send>(
                 ))?
             }
 
-            yield finish_page(
+            let mut page = finish_page(
                 page_header,
-                &mut scratch,
+                std::mem::take(&mut scratch).into(),
                 compression,
+                &zstd_dictionary,
                 &descriptor,
                 None,
             )?;
+            page.set_reservation(reservation);
+            yield page;
         }
     }
 }
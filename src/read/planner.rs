@@ -0,0 +1,362 @@
+//! A planner of the byte ranges to read from remote storage, where every read
+//! (e.g. an HTTP range request) has a high latency.
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::Arc;
+
+use crate::error::{Error, Result};
+use crate::indexes::{FilteredPage, PageLocation};
+use crate::metadata::ColumnChunkMetaData;
+
+use super::{dictionary_page_location, get_page_iterator, PageFilter, PageReader};
+
+/// Fetches byte ranges of a file, e.g. with HTTP range requests.
+pub trait RangeFetch {
+    /// Fetches the bytes of `ranges`, `(start, length)`, returning one buffer per range.
+    /// The ranges are sorted and do not overlap, so they can be fetched concurrently.
+    fn fetch(&mut self, ranges: &[(u64, u64)]) -> Result<Vec<Vec<u8>>>;
+}
+
+/// A [`RangeFetch`] seeking and reading a [`Read`] + [`Seek`] for every range.
+#[derive(Debug)]
+pub struct SeekFetch<R: Read + Seek>(pub R);
+
+impl<R: Read + Seek> RangeFetch for SeekFetch<R> {
+    fn fetch(&mut self, ranges: &[(u64, u64)]) -> Result<Vec<Vec<u8>>> {
+        ranges
+            .iter()
+            .map(|&(start, length)| {
+                self.0.seek(SeekFrom::Start(start))?;
+                let mut buffer = vec![];
+                buffer.try_reserve(length as usize)?;
+                self.0.by_ref().take(length).read_to_end(&mut buffer)?;
+                Ok(buffer)
+            })
+            .collect()
+    }
+}
+
+/// Options of how [`ReadPlanner`] merges nearby ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CoalesceOptions {
+    /// Ranges separated by at most this number of bytes are merged, reading the gap between them.
+    pub max_gap: u64,
+    /// Ranges are not merged into a range larger than this number of bytes.
+    /// Overlapping ranges are always merged.
+    pub max_size: u64,
+}
+
+impl Default for CoalesceOptions {
+    fn default() -> Self {
+        Self {
+            max_gap: 64 * 1024,
+            max_size: 8 * 1024 * 1024,
+        }
+    }
+}
+
+/// Collects the byte ranges needed to read a projection (column chunks, pages and page indexes),
+/// merges nearby ranges into fewer and larger ones, and fetches them through a [`RangeFetch`].
+///
+/// The fetched ranges are then read via [`FetchedRanges`], e.g. with [`FetchedRanges::get_page_iterator`].
+#[derive(Debug, Clone, Default)]
+pub struct ReadPlanner {
+    ranges: Vec<(u64, u64)>,
+    options: CoalesceOptions,
+}
+
+impl ReadPlanner {
+    /// Returns a new [`ReadPlanner`].
+    pub fn new(options: CoalesceOptions) -> Self {
+        Self {
+            ranges: vec![],
+            options,
+        }
+    }
+
+    /// Adds the range of `length` bytes starting at `start`.
+    pub fn add_range(&mut self, start: u64, length: u64) -> &mut Self {
+        if length > 0 {
+            self.ranges.push((start, length));
+        }
+        self
+    }
+
+    /// Adds the range of the column chunk, [`ColumnChunkMetaData::byte_range`].
+    pub fn add_column(&mut self, column: &ColumnChunkMetaData) -> &mut Self {
+        let (start, length) = column.byte_range();
+        self.add_range(start, length)
+    }
+
+    /// Adds the ranges of the pages of `locations`.
+    pub fn add_page_locations(&mut self, locations: &[PageLocation]) -> &mut Self {
+        for location in locations {
+            self.add_range(location.offset as u64, location.compressed_page_size as u64);
+        }
+        self
+    }
+
+    /// Adds the ranges of the pages of `column` read by [`super::IndexedPageReader`]:
+    /// its dictionary page, if any, and the pages of `pages` with selected rows.
+    pub fn add_filtered_pages(
+        &mut self,
+        column: &ColumnChunkMetaData,
+        pages: &[FilteredPage],
+    ) -> &mut Self {
+        if let Some((start, length)) = dictionary_page_location(column.byte_range().0, pages) {
+            self.add_range(start, length as u64);
+        }
+        for page in pages.iter().filter(|page| !page.selected_rows.is_empty()) {
+            self.add_range(page.start, page.length as u64);
+        }
+        self
+    }
+
+    /// Adds the range of the column index of `column`, if any.
+    pub fn add_column_index(&mut self, column: &ColumnChunkMetaData) -> &mut Self {
+        let chunk = column.column_chunk();
+        if let (Some(offset), Some(length)) = (chunk.column_index_offset, chunk.column_index_length)
+        {
+            self.add_range(offset as u64, length as u64);
+        }
+        self
+    }
+
+    /// Adds the range of the offset index of `column`, if any.
+    pub fn add_offset_index(&mut self, column: &ColumnChunkMetaData) -> &mut Self {
+        let chunk = column.column_chunk();
+        if let (Some(offset), Some(length)) = (chunk.offset_index_offset, chunk.offset_index_length)
+        {
+            self.add_range(offset as u64, length as u64);
+        }
+        self
+    }
+
+    /// Returns the ranges to fetch, `(start, length)`: the added ranges sorted and merged
+    /// according to [`CoalesceOptions`].
+    pub fn plan(&self) -> Vec<(u64, u64)> {
+        let mut ranges = self.ranges.clone();
+        ranges.sort_unstable();
+
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+        for (start, length) in ranges {
+            let end = start + length;
+            if let Some((merged_start, merged_length)) = merged.last_mut() {
+                let merged_end = *merged_start + *merged_length;
+                let new_end = merged_end.max(end);
+                let overlaps = start < merged_end;
+                let is_near = start.saturating_sub(merged_end) <= self.options.max_gap
+                    && new_end - *merged_start <= self.options.max_size;
+                if overlaps || is_near {
+                    *merged_length = new_end - *merged_start;
+                    continue;
+                }
+            }
+            merged.push((start, length));
+        }
+        merged
+    }
+
+    /// Fetches the ranges of [`ReadPlanner::plan`] with `fetcher`.
+    /// # Errors
+    /// Errors iff `fetcher` errors or returns buffers of the wrong length.
+    pub fn fetch<F: RangeFetch>(&self, fetcher: &mut F) -> Result<FetchedRanges> {
+        let plan = self.plan();
+        let buffers = fetcher.fetch(&plan)?;
+        if buffers.len() != plan.len() {
+            return Err(Error::InvalidParameter(format!(
+                "{} ranges were fetched but {} were requested",
+                buffers.len(),
+                plan.len()
+            )));
+        }
+        let ranges = plan
+            .into_iter()
+            .zip(buffers)
+            .map(|((start, length), buffer)| {
+                if buffer.len() as u64 != length {
+                    return Err(Error::oos(format!(
+                        "The range starting at {} has {} bytes but {} were requested",
+                        start,
+                        buffer.len(),
+                        length
+                    )));
+                }
+                Ok((start, buffer.into()))
+            })
+            .collect::<Result<_>>()?;
+        Ok(FetchedRanges { ranges })
+    }
+}
+
+/// The byte ranges of a file fetched by [`ReadPlanner::fetch`].
+#[derive(Debug, Clone)]
+pub struct FetchedRanges {
+    // sorted by start and non-overlapping
+    ranges: Vec<(u64, Arc<[u8]>)>,
+}
+
+impl FetchedRanges {
+    /// The fetched ranges, `(start, length)`
+    pub fn ranges(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.ranges
+            .iter()
+            .map(|(start, buffer)| (*start, buffer.len() as u64))
+    }
+
+    /// Returns the index of the range containing `position`, if any.
+    fn find(&self, position: u64) -> Option<usize> {
+        let index = self
+            .ranges
+            .partition_point(|(start, _)| *start <= position)
+            .checked_sub(1)?;
+        let (start, buffer) = &self.ranges[index];
+        (position < start + buffer.len() as u64).then_some(index)
+    }
+
+    /// Returns the `length` bytes starting at `start`, if they were fetched.
+    pub fn get(&self, start: u64, length: u64) -> Option<&[u8]> {
+        let (range_start, buffer) = &self.ranges[self.find(start)?];
+        let offset = (start - range_start) as usize;
+        buffer.get(offset..offset + length as usize)
+    }
+
+    /// Returns a [`Read`] + [`Seek`] over the fetched ranges, with positions in the file.
+    /// Reading bytes that were not fetched errors.
+    pub fn reader(&self) -> FetchedReader<'_> {
+        FetchedReader {
+            ranges: self,
+            position: 0,
+        }
+    }
+
+    /// Returns a [`PageReader`] of the pages of `column`, read from the fetched ranges
+    /// (see [`super::get_page_iterator`]).
+    /// # Errors
+    /// Errors iff the column chunk was not fetched.
+    pub fn get_page_iterator(
+        &self,
+        column: &ColumnChunkMetaData,
+        pages_filter: Option<PageFilter>,
+        scratch: Vec<u8>,
+        max_page_size: usize,
+    ) -> Result<PageReader<FetchedReader<'_>>> {
+        let (start, length) = column.byte_range();
+        if self.get(start, length).is_none() {
+            return Err(Error::InvalidParameter(
+                "The column chunk was not fetched".to_string(),
+            ));
+        }
+        get_page_iterator(column, self.reader(), pages_filter, scratch, max_page_size)
+    }
+}
+
+/// A [`Read`] + [`Seek`] over [`FetchedRanges`], returned by [`FetchedRanges::reader`].
+#[derive(Debug, Clone)]
+pub struct FetchedReader<'a> {
+    ranges: &'a FetchedRanges,
+    position: u64,
+}
+
+impl<'a> Read for FetchedReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let index = self.ranges.find(self.position).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("The byte at {} was not fetched", self.position),
+            )
+        })?;
+        let (start, buffer) = &self.ranges.ranges[index];
+        let data = &buffer[(self.position - start) as usize..];
+        let length = data.len().min(buf.len());
+        buf[..length].copy_from_slice(&data[..length]);
+        self.position += length as u64;
+        Ok(length)
+    }
+}
+
+impl<'a> Seek for FetchedReader<'a> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(position) => Some(position),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+            SeekFrom::End(_) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Unsupported,
+                    "The length of the file is unknown",
+                ))
+            }
+        };
+        self.position = position.ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Seeking to a negative position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan(ranges: &[(u64, u64)], max_gap: u64, max_size: u64) -> Vec<(u64, u64)> {
+        let mut planner = ReadPlanner::new(CoalesceOptions { max_gap, max_size });
+        for &(start, length) in ranges {
+            planner.add_range(start, length);
+        }
+        planner.plan()
+    }
+
+    #[test]
+    fn coalesce() {
+        let ranges = [(100, 10), (0, 10), (15, 5), (50, 10), (105, 20)];
+        assert_eq!(
+            plan(&ranges, 0, u64::MAX),
+            vec![(0, 10), (15, 5), (50, 10), (100, 25)]
+        );
+        assert_eq!(
+            plan(&ranges, 5, u64::MAX),
+            vec![(0, 20), (50, 10), (100, 25)]
+        );
+        assert_eq!(plan(&ranges, 100, u64::MAX), vec![(0, 125)]);
+        // overlapping ranges are merged regardless of the size
+        assert_eq!(plan(&ranges, 100, 20), vec![(0, 20), (50, 10), (100, 25)]);
+        assert_eq!(plan(&[(0, 0)], 0, 0), vec![]);
+    }
+
+    #[test]
+    fn fetched_reader() -> Result<()> {
+        let file = (0..200u8).collect::<Vec<_>>();
+        let mut planner = ReadPlanner::new(CoalesceOptions {
+            max_gap: 0,
+            max_size: u64::MAX,
+        });
+        planner
+            .add_range(10, 20)
+            .add_range(30, 10)
+            .add_range(100, 50);
+        let ranges = planner.fetch(&mut SeekFetch(std::io::Cursor::new(&file)))?;
+        assert_eq!(
+            ranges.ranges().collect::<Vec<_>>(),
+            vec![(10, 30), (100, 50)]
+        );
+        assert_eq!(ranges.get(35, 5), Some(&file[35..40]));
+        assert_eq!(ranges.get(35, 6), None);
+        assert_eq!(ranges.get(50, 1), None);
+
+        let mut reader = ranges.reader();
+        reader.seek(SeekFrom::Start(120))?;
+        let mut buffer = vec![0; 30];
+        reader.read_exact(&mut buffer)?;
+        assert_eq!(buffer, &file[120..150]);
+        assert!(reader.read_exact(&mut [0]).is_err());
+
+        reader.seek(SeekFrom::Start(5))?;
+        assert!(reader.read_exact(&mut [0]).is_err());
+        Ok(())
+    }
+}
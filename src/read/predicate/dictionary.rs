@@ -0,0 +1,399 @@
+use std::collections::VecDeque;
+use std::io::{Read, Seek};
+use std::ops::Range;
+
+use crate::compression::CodecRegistry;
+use crate::deserialize::ColumnValue;
+use crate::encoding::Encoding;
+use crate::error::Result;
+use crate::indexes::Interval;
+use crate::metadata::ColumnChunkMetaData;
+use crate::page::{CompressedPage, DictPage, Page};
+use crate::schema::types::PhysicalType;
+use crate::thrift_format::{Encoding as ThriftEncoding, PageType};
+
+use super::super::{decompress_with_registry, get_page_iterator};
+use super::literal::{is_ordered, Bounds, Literal};
+use super::{Atom, Predicate};
+
+/// The entries of the dictionary of a column chunk whose value may satisfy a predicate.
+///
+/// [`DictionaryMatches::select`] selects the values of a dictionary-encoded data page whose
+/// entry may match, to only deserialize them with
+/// [`crate::deserialize::SliceFilteredIter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryMatches {
+    matches: Vec<bool>,
+}
+
+impl DictionaryMatches {
+    /// Whether the value of the dictionary entry `index` may satisfy the predicate
+    pub fn contains(&self, index: u32) -> bool {
+        self.matches.get(index as usize).copied().unwrap_or(false)
+    }
+
+    /// The indices of the dictionary entries whose value may satisfy the predicate
+    pub fn indices(&self) -> impl Iterator<Item = u32> + '_ {
+        self.matches
+            .iter()
+            .enumerate()
+            .filter(|(_, is_match)| **is_match)
+            .map(|(index, _)| index as u32)
+    }
+
+    /// Whether no dictionary entry may satisfy the predicate
+    pub fn is_empty(&self) -> bool {
+        !self.matches.iter().any(|is_match| *is_match)
+    }
+
+    /// Returns the intervals of the values of a dictionary-encoded data page whose entry may
+    /// satisfy the predicate, given the dictionary `indexes` of its non-null values, e.g.
+    /// the `indexes` of the dictionary states of [`crate::deserialize`] limited to the number
+    /// of non-null values of the page.
+    ///
+    /// Passing them with the indexes to [`crate::deserialize::SliceFilteredIter`] only yields
+    /// the indexes of those values, skipping the others without decoding them when possible.
+    /// # Errors
+    /// Errors iff `indexes` errors.
+    pub fn select<I: Iterator<Item = Result<u32>>>(
+        &self,
+        indexes: I,
+    ) -> Result<VecDeque<Interval>> {
+        let mut selected = VecDeque::<Interval>::new();
+        for (position, index) in indexes.enumerate() {
+            if !self.contains(index?) {
+                continue;
+            }
+            match selected.back_mut() {
+                Some(interval) if interval.start + interval.length == position => {
+                    interval.length += 1
+                }
+                _ => selected.push_back(Interval::new(position, 1)),
+            }
+        }
+        Ok(selected)
+    }
+}
+
+/// The result of evaluating a [`Predicate`] against the dictionary page of a column chunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DictionaryFilter {
+    /// No value of the column chunk satisfies the predicate
+    Skip,
+    /// The dictionary entries that may satisfy the predicate. Values of data pages not
+    /// dictionary-encoded and null values must still be evaluated.
+    Matches(DictionaryMatches),
+}
+
+impl Predicate {
+    /// Evaluates this predicate against `dictionary`, the dictionary page of `column`.
+    ///
+    /// Comparisons over other columns are assumed to be satisfiable. Range comparisons are
+    /// evaluated by binary search when the dictionary is sorted, and are assumed to be
+    /// satisfiable when the values of `column` are not sorted as compared (see [`Predicate`]).
+    ///
+    /// The column chunk is only skipped when, according to its metadata, all its data pages
+    /// are dictionary-encoded (as in `parquet-mr`).
+    /// # Errors
+    /// Errors iff a value does not have the physical type of `column` or the dictionary
+    /// can't be decoded.
+    pub fn evaluate_dictionary(
+        &self,
+        column: &ColumnChunkMetaData,
+        dictionary: &DictPage,
+    ) -> Result<DictionaryFilter> {
+        match column.physical_type() {
+            PhysicalType::Boolean => self.evaluate_dictionary_of::<bool>(column, dictionary),
+            PhysicalType::Int32 => self.evaluate_dictionary_of::<i32>(column, dictionary),
+            PhysicalType::Int64 => self.evaluate_dictionary_of::<i64>(column, dictionary),
+            PhysicalType::Int96 => self.evaluate_dictionary_of::<[u32; 3]>(column, dictionary),
+            PhysicalType::Float => self.evaluate_dictionary_of::<f32>(column, dictionary),
+            PhysicalType::Double => self.evaluate_dictionary_of::<f64>(column, dictionary),
+            PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_) => {
+                self.evaluate_dictionary_of::<Vec<u8>>(column, dictionary)
+            }
+        }
+    }
+
+    fn evaluate_dictionary_of<T: Literal + ColumnValue>(
+        &self,
+        column: &ColumnChunkMetaData,
+        dictionary: &DictPage,
+    ) -> Result<DictionaryFilter> {
+        let mut entries = Vec::with_capacity(dictionary.num_values);
+        T::decode(
+            &dictionary.buffer,
+            Encoding::Plain,
+            &column.physical_type(),
+            dictionary.num_values,
+            &mut entries,
+        )?;
+
+        let (mut matches, _) = self.evaluate_entries(column, &entries, dictionary.is_sorted)?;
+        // the last entry is the null value
+        let null_matches = matches.pop().unwrap_or(true);
+        let matches = DictionaryMatches { matches };
+
+        let null_count = column
+            .statistics()
+            .transpose()?
+            .and_then(|statistics| statistics.null_count());
+        let may_have_nulls =
+            column.descriptor().descriptor.max_def_level > 0 && null_count != Some(0);
+
+        if matches.is_empty() && !(null_matches && may_have_nulls) && is_dictionary_encoded(column)
+        {
+            Ok(DictionaryFilter::Skip)
+        } else {
+            Ok(DictionaryFilter::Matches(matches))
+        }
+    }
+
+    /// Returns whether each of `entries`, followed by the null value, may satisfy this
+    /// predicate and whether it may not.
+    fn evaluate_entries<T: Literal>(
+        &self,
+        column: &ColumnChunkMetaData,
+        entries: &[T],
+        is_sorted: bool,
+    ) -> Result<(Vec<bool>, Vec<bool>)> {
+        let zip = |lhs: Vec<bool>, rhs: Vec<bool>, op: fn(bool, bool) -> bool| {
+            lhs.into_iter()
+                .zip(rhs)
+                .map(|(lhs, rhs)| op(lhs, rhs))
+                .collect::<Vec<_>>()
+        };
+        match self {
+            Self::And(lhs, rhs) => {
+                let (lhs_matches, lhs_not_matches) =
+                    lhs.evaluate_entries(column, entries, is_sorted)?;
+                let (rhs_matches, rhs_not_matches) =
+                    rhs.evaluate_entries(column, entries, is_sorted)?;
+                Ok((
+                    zip(lhs_matches, rhs_matches, |lhs, rhs| lhs && rhs),
+                    zip(lhs_not_matches, rhs_not_matches, |lhs, rhs| lhs || rhs),
+                ))
+            }
+            Self::Or(lhs, rhs) => {
+                let (lhs_matches, lhs_not_matches) =
+                    lhs.evaluate_entries(column, entries, is_sorted)?;
+                let (rhs_matches, rhs_not_matches) =
+                    rhs.evaluate_entries(column, entries, is_sorted)?;
+                Ok((
+                    zip(lhs_matches, rhs_matches, |lhs, rhs| lhs || rhs),
+                    zip(lhs_not_matches, rhs_not_matches, |lhs, rhs| lhs && rhs),
+                ))
+            }
+            Self::Not(predicate) => {
+                let (matches, not_matches) =
+                    predicate.evaluate_entries(column, entries, is_sorted)?;
+                Ok((not_matches, matches))
+            }
+            Self::Eq(path, _)
+            | Self::Lt(path, _)
+            | Self::LtEq(path, _)
+            | Self::Gt(path, _)
+            | Self::GtEq(path, _)
+            | Self::Between(path, _, _)
+            | Self::In(path, _)
+            | Self::IsNull(path) => {
+                if *path != column.descriptor().path_in_schema {
+                    let all = vec![true; entries.len() + 1];
+                    return Ok((all.clone(), all));
+                }
+                let atom = self.atom::<T>(column)?;
+                let is_ordered = is_ordered::<T>(column);
+
+                let range = (is_sorted && is_ordered)
+                    .then(|| atom.range(entries))
+                    .flatten();
+                let (mut matches, mut not_matches): (Vec<_>, Vec<_>) = match range {
+                    Some(range) => (0..entries.len())
+                        .map(|index| (range.contains(&index), !range.contains(&index)))
+                        .unzip(),
+                    None => entries
+                        .iter()
+                        .map(|entry| atom.evaluate_entry(entry, is_ordered))
+                        .unzip(),
+                };
+                let (null_matches, null_not_matches) = atom.evaluate(&Bounds::null());
+                matches.push(null_matches);
+                not_matches.push(null_not_matches);
+                Ok((matches, not_matches))
+            }
+        }
+    }
+}
+
+impl<T: Literal> Atom<T> {
+    /// Returns whether the dictionary entry `entry` may satisfy this predicate and whether it
+    /// may not. Unless `is_ordered`, only equality is evaluated.
+    fn evaluate_entry(&self, entry: &T, is_ordered: bool) -> (bool, bool) {
+        if is_ordered || matches!(self, Self::Eq(_) | Self::In(_) | Self::IsNull) {
+            self.evaluate(&Bounds::value(entry))
+        } else {
+            (true, true)
+        }
+    }
+
+    /// Returns the entries of the sorted `entries` that satisfy this predicate, if they can
+    /// be searched for
+    fn range(&self, entries: &[T]) -> Option<Range<usize>> {
+        use std::cmp::Ordering::*;
+        let lt = |value: &T| entries.partition_point(|entry| entry.compare(value) == Less);
+        let lt_eq = |value: &T| entries.partition_point(|entry| entry.compare(value) != Greater);
+        match self {
+            Self::Lt(value) => Some(0..lt(value)),
+            Self::LtEq(value) => Some(0..lt_eq(value)),
+            Self::Gt(value) => Some(lt_eq(value)..entries.len()),
+            Self::GtEq(value) => Some(lt(value)..entries.len()),
+            Self::Between(low, high) => Some(lt(low)..lt_eq(high)),
+            Self::Eq(_) | Self::In(_) | Self::IsNull => None,
+        }
+    }
+}
+
+/// Whether all data pages of `column` are dictionary-encoded, according to the encoding
+/// statistics of its metadata or, when absent, to its encodings.
+fn is_dictionary_encoded(column: &ColumnChunkMetaData) -> bool {
+    let is_dictionary = |encoding: &ThriftEncoding| {
+        *encoding == ThriftEncoding::PLAIN_DICTIONARY || *encoding == ThriftEncoding::RLE_DICTIONARY
+    };
+    if let Some(stats) = &column.metadata().encoding_stats {
+        stats
+            .iter()
+            .filter(|stats| {
+                stats.page_type == PageType::DATA_PAGE || stats.page_type == PageType::DATA_PAGE_V2
+            })
+            .all(|stats| stats.count == 0 || is_dictionary(&stats.encoding))
+    } else {
+        // only the encodings of levels besides the dictionary ones: data pages can't fall back
+        // to plain encoding. This is conservative for dictionary pages encoded as `PLAIN`.
+        let encodings = column.column_encoding();
+        encodings.iter().any(is_dictionary)
+            && encodings.iter().all(|encoding| {
+                is_dictionary(encoding)
+                    || *encoding == ThriftEncoding::RLE
+                    || *encoding == ThriftEncoding::BIT_PACKED
+            })
+    }
+}
+
+/// Reads the dictionary page of `column`, if it has one, i.e. if the first page of `column` is
+/// a dictionary page (writers do not always set the dictionary page offset). Like the pages of
+/// [`get_page_iterator`], it is decompressed with the zstd dictionary of `column`, if any.
+/// # Errors
+/// Errors iff the dictionary page of `column` can't be read or decompressed.
+pub fn read_dictionary_page<R: Read + Seek>(
+    reader: &mut R,
+    column: &ColumnChunkMetaData,
+    max_page_size: usize,
+) -> Result<Option<DictPage>> {
+    read_dictionary_page_with_registry(reader, column, max_page_size, &CodecRegistry::default())
+}
+
+/// Reads the dictionary page of `column` like [`read_dictionary_page`], decompressing it
+/// with the codecs of `registry`.
+pub fn read_dictionary_page_with_registry<R: Read + Seek>(
+    reader: &mut R,
+    column: &ColumnChunkMetaData,
+    max_page_size: usize,
+    registry: &CodecRegistry,
+) -> Result<Option<DictPage>> {
+    let mut pages = get_page_iterator(column, reader, None, vec![], max_page_size)?;
+    // SPEC: the dictionary page, when present, is the first page of the column chunk
+    match pages.next().transpose()? {
+        Some(page @ CompressedPage::Dict(_)) => {
+            match decompress_with_registry(page, &mut vec![], registry)? {
+                Page::Dict(page) => Ok(Some(page)),
+                Page::Data(_) => Ok(None),
+            }
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Reads the dictionary page of `column` and evaluates `predicate` against it
+/// (see [`Predicate::evaluate_dictionary`]). Returns `None` when `column` has no dictionary.
+/// # Errors
+/// Errors iff [`read_dictionary_page`] or [`Predicate::evaluate_dictionary`] errors.
+pub fn read_dictionary_filter<R: Read + Seek>(
+    reader: &mut R,
+    column: &ColumnChunkMetaData,
+    predicate: &Predicate,
+    max_page_size: usize,
+) -> Result<Option<DictionaryFilter>> {
+    read_dictionary_filter_with_registry(
+        reader,
+        column,
+        predicate,
+        max_page_size,
+        &CodecRegistry::default(),
+    )
+}
+
+/// Reads the dictionary page of `column` and evaluates `predicate` against it like
+/// [`read_dictionary_filter`], decompressing it with the codecs of `registry`.
+pub fn read_dictionary_filter_with_registry<R: Read + Seek>(
+    reader: &mut R,
+    column: &ColumnChunkMetaData,
+    predicate: &Predicate,
+    max_page_size: usize,
+    registry: &CodecRegistry,
+) -> Result<Option<DictionaryFilter>> {
+    read_dictionary_page_with_registry(reader, column, max_page_size, registry)?
+        .map(|dictionary| predicate.evaluate_dictionary(column, &dictionary))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorted_ranges() {
+        let entries = [1i32, 3, 5, 7];
+        let atoms = [
+            Atom::Lt(5),
+            Atom::LtEq(5),
+            Atom::Gt(3),
+            Atom::GtEq(4),
+            Atom::Between(2, 7),
+            Atom::Between(8, 9),
+            Atom::Lt(0),
+        ];
+        // binary search selects the entries that satisfy the atom
+        for atom in atoms {
+            let range = atom.range(&entries).unwrap();
+            let expected = entries
+                .iter()
+                .map(|entry| atom.evaluate(&Bounds::value(entry)).0)
+                .collect::<Vec<_>>();
+            let result = (0..entries.len())
+                .map(|index| range.contains(&index))
+                .collect::<Vec<_>>();
+            assert_eq!(result, expected, "{:?}", atom);
+        }
+        assert!(Atom::Eq(3).range(&entries).is_none());
+    }
+
+    #[test]
+    fn select() -> Result<()> {
+        use crate::deserialize::SliceFilteredIter;
+        use crate::encoding::hybrid_rle::{encode_u32, HybridRleDecoder};
+
+        let matches = DictionaryMatches {
+            matches: vec![false, true, true, false],
+        };
+        let indexes = [0u32, 1, 2, 1, 3, 3, 0, 2];
+        let mut buffer = vec![];
+        encode_u32(&mut buffer, indexes.iter().copied(), 2)?;
+        let decoder = HybridRleDecoder::try_new(&buffer, 2, indexes.len())?;
+
+        let selected = matches.select(decoder.clone())?;
+        assert_eq!(selected, vec![Interval::new(1, 3), Interval::new(7, 1)]);
+        let result = SliceFilteredIter::new(decoder, selected).collect::<Result<Vec<_>>>()?;
+        assert_eq!(result, vec![1, 2, 1, 2]);
+        Ok(())
+    }
+}
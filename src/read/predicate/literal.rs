@@ -0,0 +1,248 @@
+use std::cmp::Ordering;
+
+use crate::indexes::{BooleanIndex, ByteIndex, FixedLenByteIndex, Index, NativeIndex, PageIndex};
+use crate::metadata::{get_sort_order, ColumnChunkMetaData, SortOrder};
+use crate::statistics::{
+    BinaryStatistics, BooleanStatistics, FixedLenStatistics, PrimitiveStatistics, Statistics,
+};
+use crate::types::{ord_binary, NativeType};
+
+use super::super::Value;
+
+/// The minimum and maximum values of a column chunk or of a page
+#[derive(Debug)]
+pub(super) struct Bounds<'a, T> {
+    pub min: Option<&'a T>,
+    pub max: Option<&'a T>,
+    pub null_count: Option<i64>,
+    /// Whether all values are null
+    pub all_null: bool,
+}
+
+impl<'a, T> Bounds<'a, T> {
+    pub fn from_page(page: &'a PageIndex<T>) -> Self {
+        Self {
+            min: page.min.as_ref(),
+            max: page.max.as_ref(),
+            null_count: page.null_count,
+            // SPEC: pages of only null values have no min and max
+            all_null: page.min.is_none() && page.max.is_none(),
+        }
+    }
+
+    /// These bounds, without their minimum and maximum unless the values are ordered as
+    /// literals (see [`is_ordered`])
+    pub fn ordered(self, is_ordered: bool) -> Self {
+        if is_ordered {
+            self
+        } else {
+            Self {
+                min: None,
+                max: None,
+                ..self
+            }
+        }
+    }
+
+    /// The bounds of a single non-null value
+    pub fn value(value: &'a T) -> Self {
+        Self {
+            min: Some(value),
+            max: Some(value),
+            null_count: Some(0),
+            all_null: false,
+        }
+    }
+
+    /// The bounds of a single null value
+    pub fn null() -> Self {
+        Self {
+            min: None,
+            max: None,
+            null_count: None,
+            all_null: true,
+        }
+    }
+}
+
+/// A type the values of a physical type are compared as, in predicates
+pub(super) trait Literal: Sized + std::fmt::Debug {
+    /// Returns the literal of `value`, if it is of this type
+    fn from_value(value: &Value) -> Option<Self>;
+
+    /// The sort order of [`Literal::compare`]
+    const SORT_ORDER: SortOrder;
+
+    fn compare(&self, other: &Self) -> Ordering;
+
+    /// The hash of this literal in bloom filters, if they support this type
+    #[cfg(feature = "bloom_filter")]
+    fn hash(&self) -> Option<u64>;
+
+    /// The bounds of the column chunk of `num_values` values summarized by `statistics`
+    fn statistics(statistics: &dyn Statistics, num_values: i64) -> Option<Bounds<'_, Self>>;
+
+    /// The index of every page of the column chunk indexed by `index`
+    fn pages(index: &dyn Index) -> Option<&[PageIndex<Self>]>;
+}
+
+/// Whether the values of `column` are sorted as `T` compares them, e.g. not for `UINT_32`
+/// columns of `INT32` values, so that they can be compared to its minimum and maximum
+pub(super) fn is_ordered<T: Literal>(column: &ColumnChunkMetaData) -> bool {
+    let primitive_type = &column.descriptor().descriptor.primitive_type;
+    get_sort_order(
+        &primitive_type.logical_type,
+        &primitive_type.converted_type,
+        &primitive_type.physical_type,
+    ) == T::SORT_ORDER
+}
+
+fn bounds<'a, T>(
+    min: &'a Option<T>,
+    max: &'a Option<T>,
+    null_count: Option<i64>,
+    num_values: i64,
+) -> Bounds<'a, T> {
+    Bounds {
+        min: min.as_ref(),
+        max: max.as_ref(),
+        null_count,
+        all_null: null_count == Some(num_values),
+    }
+}
+
+macro_rules! native_literal {
+    ($type:ty, $variant:ident) => {
+        impl Literal for $type {
+            fn from_value(value: &Value) -> Option<Self> {
+                match value {
+                    Value::$variant(value) => Some(*value),
+                    _ => None,
+                }
+            }
+
+            const SORT_ORDER: SortOrder = SortOrder::Signed;
+
+            fn compare(&self, other: &Self) -> Ordering {
+                self.ord(other)
+            }
+
+            #[cfg(feature = "bloom_filter")]
+            fn hash(&self) -> Option<u64> {
+                Some(crate::bloom_filter::hash_native(*self))
+            }
+
+            fn statistics(
+                statistics: &dyn Statistics,
+                num_values: i64,
+            ) -> Option<Bounds<'_, Self>> {
+                let statistics = statistics
+                    .as_any()
+                    .downcast_ref::<PrimitiveStatistics<$type>>()?;
+                Some(bounds(
+                    &statistics.min_value,
+                    &statistics.max_value,
+                    statistics.null_count,
+                    num_values,
+                ))
+            }
+
+            fn pages(index: &dyn Index) -> Option<&[PageIndex<Self>]> {
+                let index = index.as_any().downcast_ref::<NativeIndex<$type>>()?;
+                Some(&index.indexes)
+            }
+        }
+    };
+}
+
+native_literal!(i32, Int32);
+native_literal!(i64, Int64);
+native_literal!([u32; 3], Int96);
+native_literal!(f32, Float);
+native_literal!(f64, Double);
+
+impl Literal for bool {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    // false < true
+    const SORT_ORDER: SortOrder = SortOrder::Unsigned;
+
+    fn compare(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+
+    #[cfg(feature = "bloom_filter")]
+    fn hash(&self) -> Option<u64> {
+        None
+    }
+
+    fn statistics(statistics: &dyn Statistics, num_values: i64) -> Option<Bounds<'_, Self>> {
+        let statistics = statistics.as_any().downcast_ref::<BooleanStatistics>()?;
+        Some(bounds(
+            &statistics.min_value,
+            &statistics.max_value,
+            statistics.null_count,
+            num_values,
+        ))
+    }
+
+    fn pages(index: &dyn Index) -> Option<&[PageIndex<Self>]> {
+        let index = index.as_any().downcast_ref::<BooleanIndex>()?;
+        Some(&index.indexes)
+    }
+}
+
+/// `BYTE_ARRAY` and `FIXED_LEN_BYTE_ARRAY`
+impl Literal for Vec<u8> {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Bytes(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    const SORT_ORDER: SortOrder = SortOrder::Unsigned;
+
+    fn compare(&self, other: &Self) -> Ordering {
+        ord_binary(self, other)
+    }
+
+    #[cfg(feature = "bloom_filter")]
+    fn hash(&self) -> Option<u64> {
+        Some(crate::bloom_filter::hash_byte(self))
+    }
+
+    fn statistics(statistics: &dyn Statistics, num_values: i64) -> Option<Bounds<'_, Self>> {
+        let any = statistics.as_any();
+        if let Some(statistics) = any.downcast_ref::<BinaryStatistics>() {
+            Some(bounds(
+                &statistics.min_value,
+                &statistics.max_value,
+                statistics.null_count,
+                num_values,
+            ))
+        } else {
+            let statistics = any.downcast_ref::<FixedLenStatistics>()?;
+            Some(bounds(
+                &statistics.min_value,
+                &statistics.max_value,
+                statistics.null_count,
+                num_values,
+            ))
+        }
+    }
+
+    fn pages(index: &dyn Index) -> Option<&[PageIndex<Self>]> {
+        let any = index.as_any();
+        if let Some(index) = any.downcast_ref::<ByteIndex>() {
+            Some(&index.indexes)
+        } else {
+            Some(&any.downcast_ref::<FixedLenByteIndex>()?.indexes)
+        }
+    }
+}
@@ -0,0 +1,638 @@
+//! Pruning of row groups and pages with predicates, evaluated against the statistics of
+//! column chunks, their column indexes, their bloom filters and their dictionaries.
+mod dictionary;
+mod literal;
+
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+
+use crate::error::{Error, Result};
+use crate::indexes::{compute_rows, Index, Interval, PageLocation};
+use crate::metadata::{ColumnChunkMetaData, RowGroupMetaData};
+use crate::schema::types::PhysicalType;
+
+use super::indexes::{read_columns_indexes, read_pages_locations};
+use super::Value;
+pub use dictionary::{
+    read_dictionary_filter, read_dictionary_filter_with_registry, read_dictionary_page,
+    read_dictionary_page_with_registry, DictionaryFilter, DictionaryMatches,
+};
+use literal::{is_ordered, Bounds, Literal};
+
+/// A predicate over the values of leaf columns, identified by their path in the schema
+/// (see [`crate::metadata::ColumnDescriptor::path_in_schema`]).
+///
+/// Values must have the physical representation of their column, e.g. [`Value::Int32`] for
+/// `INT32` columns and [`Value::Bytes`] for `BYTE_ARRAY` columns. Null values, in the column
+/// or as [`Value::Null`], never satisfy comparisons.
+///
+/// Values are compared as signed numbers and as unsigned bytes. Columns of another sort order
+/// (see [`crate::metadata::get_sort_order`]), e.g. `UINT_32` or `DECIMAL` of bytes, are not
+/// pruned by their minimum and maximum.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Predicate {
+    /// `column == value`
+    Eq(Vec<String>, Value),
+    /// `column < value`
+    Lt(Vec<String>, Value),
+    /// `column <= value`
+    LtEq(Vec<String>, Value),
+    /// `column > value`
+    Gt(Vec<String>, Value),
+    /// `column >= value`
+    GtEq(Vec<String>, Value),
+    /// `low <= column <= high`
+    Between(Vec<String>, Value, Value),
+    /// `column` is equal to one of the values
+    In(Vec<String>, Vec<Value>),
+    /// `column` is null
+    IsNull(Vec<String>),
+    /// Both predicates are satisfied
+    And(Box<Predicate>, Box<Predicate>),
+    /// Any of the predicates is satisfied
+    Or(Box<Predicate>, Box<Predicate>),
+    /// The predicate is not satisfied
+    Not(Box<Predicate>),
+}
+
+fn path(column: &[&str]) -> Vec<String> {
+    column.iter().map(|x| x.to_string()).collect()
+}
+
+impl Predicate {
+    /// `column == value`
+    pub fn eq(column: &[&str], value: Value) -> Self {
+        Self::Eq(path(column), value)
+    }
+
+    /// `column < value`
+    pub fn lt(column: &[&str], value: Value) -> Self {
+        Self::Lt(path(column), value)
+    }
+
+    /// `column <= value`
+    pub fn lt_eq(column: &[&str], value: Value) -> Self {
+        Self::LtEq(path(column), value)
+    }
+
+    /// `column > value`
+    pub fn gt(column: &[&str], value: Value) -> Self {
+        Self::Gt(path(column), value)
+    }
+
+    /// `column >= value`
+    pub fn gt_eq(column: &[&str], value: Value) -> Self {
+        Self::GtEq(path(column), value)
+    }
+
+    /// `low <= column <= high`
+    pub fn between(column: &[&str], low: Value, high: Value) -> Self {
+        Self::Between(path(column), low, high)
+    }
+
+    /// `column` is equal to one of `values`
+    pub fn is_in(column: &[&str], values: Vec<Value>) -> Self {
+        Self::In(path(column), values)
+    }
+
+    /// `column` is null
+    pub fn is_null(column: &[&str]) -> Self {
+        Self::IsNull(path(column))
+    }
+
+    /// Both `self` and `other` are satisfied
+    pub fn and(self, other: Self) -> Self {
+        Self::And(Box::new(self), Box::new(other))
+    }
+
+    /// Any of `self` and `other` is satisfied
+    pub fn or(self, other: Self) -> Self {
+        Self::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Returns the rows of `row_group` that may satisfy this predicate, according to the
+    /// statistics of its column chunks and to `indexes`.
+    /// # Errors
+    /// Errors iff a column of this predicate is not in `row_group`, a value does not have
+    /// the physical type of its column, or the statistics or indexes are invalid.
+    pub fn select_rows(
+        &self,
+        row_group: &RowGroupMetaData,
+        indexes: &RowGroupIndexes,
+    ) -> Result<Vec<Interval>> {
+        self.evaluate(row_group, indexes)
+            .map(|selection| selection.matching)
+    }
+
+    /// The column of `path` in `row_group`
+    fn column(row_group: &RowGroupMetaData, path: &[String]) -> Result<usize> {
+        row_group
+            .columns()
+            .iter()
+            .position(|column| column.descriptor().path_in_schema == path)
+            .ok_or_else(|| {
+                Error::InvalidParameter(format!("The row group has no column {}", path.join(".")))
+            })
+    }
+
+    /// Appends the columns of this predicate in `row_group` to `columns`, and the columns
+    /// compared for equality to `equalities`.
+    fn columns(
+        &self,
+        row_group: &RowGroupMetaData,
+        columns: &mut Vec<usize>,
+        equalities: &mut Vec<usize>,
+    ) -> Result<()> {
+        match self {
+            Self::And(lhs, rhs) | Self::Or(lhs, rhs) => {
+                lhs.columns(row_group, columns, equalities)?;
+                rhs.columns(row_group, columns, equalities)
+            }
+            Self::Not(predicate) => predicate.columns(row_group, columns, equalities),
+            Self::Eq(path, _) | Self::In(path, _) => {
+                let column = Self::column(row_group, path)?;
+                columns.push(column);
+                equalities.push(column);
+                Ok(())
+            }
+            Self::Lt(path, _)
+            | Self::LtEq(path, _)
+            | Self::Gt(path, _)
+            | Self::GtEq(path, _)
+            | Self::Between(path, _, _)
+            | Self::IsNull(path) => {
+                columns.push(Self::column(row_group, path)?);
+                Ok(())
+            }
+        }
+    }
+
+    fn evaluate(
+        &self,
+        row_group: &RowGroupMetaData,
+        indexes: &RowGroupIndexes,
+    ) -> Result<Selection> {
+        match self {
+            Self::And(lhs, rhs) => {
+                let lhs = lhs.evaluate(row_group, indexes)?;
+                let rhs = rhs.evaluate(row_group, indexes)?;
+                Ok(Selection {
+                    matching: intersection(&lhs.matching, &rhs.matching),
+                    not_matching: union(&lhs.not_matching, &rhs.not_matching),
+                })
+            }
+            Self::Or(lhs, rhs) => {
+                let lhs = lhs.evaluate(row_group, indexes)?;
+                let rhs = rhs.evaluate(row_group, indexes)?;
+                Ok(Selection {
+                    matching: union(&lhs.matching, &rhs.matching),
+                    not_matching: intersection(&lhs.not_matching, &rhs.not_matching),
+                })
+            }
+            Self::Not(predicate) => {
+                let selection = predicate.evaluate(row_group, indexes)?;
+                Ok(Selection {
+                    matching: selection.not_matching,
+                    not_matching: selection.matching,
+                })
+            }
+            Self::Eq(path, _)
+            | Self::Lt(path, _)
+            | Self::LtEq(path, _)
+            | Self::Gt(path, _)
+            | Self::GtEq(path, _)
+            | Self::Between(path, _, _)
+            | Self::In(path, _)
+            | Self::IsNull(path) => {
+                let column = Self::column(row_group, path)?;
+                let chunk = &row_group.columns()[column];
+                let column = ColumnSources {
+                    chunk,
+                    num_rows: row_group.num_rows(),
+                    pages: indexes.pages.get(&column),
+                    bloom_filter: indexes.bloom_filters.get(&column),
+                };
+                match chunk.physical_type() {
+                    PhysicalType::Boolean => self.evaluate_column::<bool>(column),
+                    PhysicalType::Int32 => self.evaluate_column::<i32>(column),
+                    PhysicalType::Int64 => self.evaluate_column::<i64>(column),
+                    PhysicalType::Int96 => self.evaluate_column::<[u32; 3]>(column),
+                    PhysicalType::Float => self.evaluate_column::<f32>(column),
+                    PhysicalType::Double => self.evaluate_column::<f64>(column),
+                    PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_) => {
+                        self.evaluate_column::<Vec<u8>>(column)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the [`Atom`] of this predicate over a single column
+    fn atom<T: Literal>(&self, chunk: &ColumnChunkMetaData) -> Result<Atom<T>> {
+        let literal = |value: &Value| {
+            T::from_value(value).ok_or_else(|| {
+                Error::InvalidParameter(format!(
+                    "The value {:?} cannot be compared to the column {} of physical type {:?}",
+                    value,
+                    chunk.descriptor().path_in_schema.join("."),
+                    chunk.physical_type()
+                ))
+            })
+        };
+        let is_null = |value: &Value| matches!(value, Value::Null);
+        Ok(match self {
+            // no value satisfies a comparison to null, like an empty `In`
+            Self::Eq(_, value)
+            | Self::Lt(_, value)
+            | Self::LtEq(_, value)
+            | Self::Gt(_, value)
+            | Self::GtEq(_, value)
+                if is_null(value) =>
+            {
+                Atom::In(vec![])
+            }
+            Self::Between(_, low, high) if is_null(low) || is_null(high) => Atom::In(vec![]),
+            Self::Eq(_, value) => Atom::Eq(literal(value)?),
+            Self::Lt(_, value) => Atom::Lt(literal(value)?),
+            Self::LtEq(_, value) => Atom::LtEq(literal(value)?),
+            Self::Gt(_, value) => Atom::Gt(literal(value)?),
+            Self::GtEq(_, value) => Atom::GtEq(literal(value)?),
+            Self::Between(_, low, high) => Atom::Between(literal(low)?, literal(high)?),
+            Self::In(_, values) => Atom::In(
+                values
+                    .iter()
+                    .filter(|value| !is_null(value))
+                    .map(literal)
+                    .collect::<Result<_>>()?,
+            ),
+            Self::IsNull(_) => Atom::IsNull,
+            Self::And(_, _) | Self::Or(_, _) | Self::Not(_) => {
+                unreachable!("only predicates over a single column are atoms")
+            }
+        })
+    }
+
+    fn evaluate_column<T: Literal>(&self, column: ColumnSources) -> Result<Selection> {
+        let atom = self.atom::<T>(column.chunk)?;
+        // the minimum and maximum of values sorted otherwise than compared can't bound them
+        let is_ordered = is_ordered::<T>(column.chunk);
+
+        let all = if column.num_rows > 0 {
+            vec![Interval::new(0, column.num_rows)]
+        } else {
+            vec![]
+        };
+        let mut selection = Selection {
+            matching: all.clone(),
+            not_matching: all,
+        };
+
+        if let Some(statistics) = column.chunk.statistics().transpose()? {
+            if let Some(statistics) = T::statistics(statistics.as_ref(), column.chunk.num_values())
+            {
+                let (may_match, may_not_match) = atom.evaluate(&statistics.ordered(is_ordered));
+                if !may_match {
+                    selection.matching.clear();
+                }
+                if !may_not_match {
+                    selection.not_matching.clear();
+                }
+            }
+        }
+
+        #[cfg(feature = "bloom_filter")]
+        if let Some(bitset) = column.bloom_filter.filter(|bitset| !bitset.is_empty()) {
+            if let Some(hashes) = atom.hashes() {
+                if !hashes
+                    .into_iter()
+                    .any(|hash| crate::bloom_filter::is_in_set(bitset, hash))
+                {
+                    selection.matching.clear();
+                }
+            }
+        }
+
+        if let Some((index, locations)) = column.pages {
+            if let Some(pages) = T::pages(index.as_ref()).filter(|x| x.len() == locations.len()) {
+                let (may_match, may_not_match): (Vec<_>, Vec<_>) = pages
+                    .iter()
+                    .map(|page| atom.evaluate(&Bounds::from_page(page).ordered(is_ordered)))
+                    .unzip();
+                let matching = compute_rows(&may_match, locations, column.num_rows)?;
+                let not_matching = compute_rows(&may_not_match, locations, column.num_rows)?;
+                selection.matching = intersection(&selection.matching, &matching);
+                selection.not_matching = intersection(&selection.not_matching, &not_matching);
+            }
+        }
+        Ok(selection)
+    }
+}
+
+impl std::ops::Not for Predicate {
+    type Output = Self;
+
+    /// `self` is not satisfied
+    fn not(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+}
+
+/// The column indexes and bloom filters of the column chunks of a row group, by index of the
+/// column in the row group, used by [`Predicate::select_rows`] in addition to statistics.
+#[derive(Debug, Default)]
+pub struct RowGroupIndexes {
+    /// The column index and page locations of column chunks
+    pub pages: HashMap<usize, (Box<dyn Index>, Vec<PageLocation>)>,
+    /// The bloom filter of column chunks
+    pub bloom_filters: HashMap<usize, Vec<u8>>,
+}
+
+impl RowGroupIndexes {
+    /// Reads the column indexes and page locations of the columns of `predicate`, and the
+    /// bloom filters of the columns it compares for equality, when available.
+    /// # Errors
+    /// Errors iff a column of `predicate` is not in `row_group` or an index can't be read.
+    pub fn read<R: Read + Seek>(
+        reader: &mut R,
+        row_group: &RowGroupMetaData,
+        predicate: &Predicate,
+    ) -> Result<Self> {
+        let mut columns = vec![];
+        let mut equalities = vec![];
+        predicate.columns(row_group, &mut columns, &mut equalities)?;
+        columns.sort_unstable();
+        columns.dedup();
+        equalities.sort_unstable();
+        equalities.dedup();
+
+        let mut indexes = Self::default();
+        for column in columns {
+            let chunk = &row_group.columns()[column..column + 1];
+            let index = read_columns_indexes(reader, chunk)?.pop();
+            let locations = read_pages_locations(reader, chunk)?.pop();
+            if let (Some(index), Some(locations)) = (index, locations) {
+                indexes.pages.insert(column, (index, locations));
+            }
+        }
+
+        #[cfg(feature = "bloom_filter")]
+        for column in equalities {
+            let mut bitset = vec![];
+            crate::bloom_filter::read(&row_group.columns()[column], reader, &mut bitset)?;
+            if !bitset.is_empty() {
+                indexes.bloom_filters.insert(column, bitset);
+            }
+        }
+        Ok(indexes)
+    }
+}
+
+/// The rows of a row group selected by [`prune`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowGroupSelection {
+    /// The index of the row group
+    pub row_group: usize,
+    /// The rows of the row group that may satisfy the predicate, e.g. to select the pages of
+    /// its column chunks with [`crate::indexes::select_pages`]
+    pub rows: Vec<Interval>,
+}
+
+/// Returns the row groups of `row_groups` whose rows may satisfy `predicate` and their rows
+/// that may satisfy it, according to the statistics of their column chunks, their column
+/// indexes and their bloom filters.
+///
+/// The indexes and bloom filters are only read from `reader` for row groups not pruned by
+/// statistics, and only for the columns of `predicate`.
+/// # Errors
+/// Errors iff [`Predicate::select_rows`] or [`RowGroupIndexes::read`] errors.
+pub fn prune<R: Read + Seek>(
+    reader: &mut R,
+    row_groups: &[RowGroupMetaData],
+    predicate: &Predicate,
+) -> Result<Vec<RowGroupSelection>> {
+    let mut selected = vec![];
+    for (index, row_group) in row_groups.iter().enumerate() {
+        if predicate
+            .select_rows(row_group, &RowGroupIndexes::default())?
+            .is_empty()
+        {
+            continue;
+        }
+        let indexes = RowGroupIndexes::read(reader, row_group, predicate)?;
+        let rows = predicate.select_rows(row_group, &indexes)?;
+        if !rows.is_empty() {
+            selected.push(RowGroupSelection {
+                row_group: index,
+                rows,
+            });
+        }
+    }
+    Ok(selected)
+}
+
+/// The sources a predicate over a column chunk is evaluated against
+struct ColumnSources<'a> {
+    chunk: &'a ColumnChunkMetaData,
+    num_rows: usize,
+    pages: Option<&'a (Box<dyn Index>, Vec<PageLocation>)>,
+    #[cfg_attr(not(feature = "bloom_filter"), allow(dead_code))]
+    bloom_filter: Option<&'a Vec<u8>>,
+}
+
+/// The rows that may satisfy a predicate and the rows that may not satisfy it.
+/// Both are over-estimated, so that `Not` only swaps them.
+#[derive(Debug)]
+struct Selection {
+    matching: Vec<Interval>,
+    not_matching: Vec<Interval>,
+}
+
+/// A predicate over a single column, with values of its physical type
+#[derive(Debug)]
+enum Atom<T> {
+    Eq(T),
+    Lt(T),
+    LtEq(T),
+    Gt(T),
+    GtEq(T),
+    Between(T, T),
+    In(Vec<T>),
+    IsNull,
+}
+
+impl<T: Literal> Atom<T> {
+    /// Returns whether some values within `bounds` may satisfy this predicate and whether
+    /// some may not.
+    fn evaluate(&self, bounds: &Bounds<T>) -> (bool, bool) {
+        let may_have_nulls = bounds.null_count != Some(0);
+        match (self, bounds.min, bounds.max) {
+            (Self::IsNull, _, _) => (may_have_nulls, !bounds.all_null),
+            _ if bounds.all_null => (false, true),
+            (_, Some(min), Some(max)) => {
+                let (may_match, may_not_match) = self.compare(min, max);
+                (may_match, may_not_match || may_have_nulls)
+            }
+            // no bounds: anything may happen
+            _ => (true, true),
+        }
+    }
+
+    /// Returns whether some non-null values within `[min, max]` may satisfy this predicate
+    /// and whether some may not.
+    fn compare(&self, min: &T, max: &T) -> (bool, bool) {
+        use std::cmp::Ordering::*;
+        let lt = |lhs: &T, rhs: &T| lhs.compare(rhs) == Less;
+        let lt_eq = |lhs: &T, rhs: &T| lhs.compare(rhs) != Greater;
+        let is_only = |value: &T| min.compare(value) == Equal && max.compare(value) == Equal;
+        let contains = |value: &T| lt_eq(min, value) && lt_eq(value, max);
+        match self {
+            Self::Eq(value) => (contains(value), !is_only(value)),
+            Self::Lt(value) => (lt(min, value), !lt(max, value)),
+            Self::LtEq(value) => (lt_eq(min, value), !lt_eq(max, value)),
+            Self::Gt(value) => (lt(value, max), !lt(value, min)),
+            Self::GtEq(value) => (lt_eq(value, max), !lt_eq(value, min)),
+            Self::Between(low, high) => (
+                lt_eq(low, max) && lt_eq(min, high),
+                lt(min, low) || lt(high, max),
+            ),
+            Self::In(values) => (values.iter().any(contains), !values.iter().any(is_only)),
+            Self::IsNull => (true, true),
+        }
+    }
+
+    /// The hashes of the values that must be in the bloom filter for this predicate to be
+    /// satisfied, if any
+    #[cfg(feature = "bloom_filter")]
+    fn hashes(&self) -> Option<Vec<u64>> {
+        match self {
+            Self::Eq(value) => value.hash().map(|hash| vec![hash]),
+            Self::In(values) => values.iter().map(|value| value.hash()).collect(),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the union of `lhs` and `rhs`, as sorted, non-overlapping and non-adjacent intervals
+fn union(lhs: &[Interval], rhs: &[Interval]) -> Vec<Interval> {
+    let mut intervals = lhs
+        .iter()
+        .chain(rhs.iter())
+        .filter(|interval| interval.length > 0)
+        .copied()
+        .collect::<Vec<_>>();
+    intervals.sort_unstable_by_key(|interval| interval.start);
+
+    let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        match merged.last_mut() {
+            Some(last) if interval.start <= last.start + last.length => {
+                let end = (last.start + last.length).max(interval.start + interval.length);
+                last.length = end - last.start;
+            }
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+/// Returns the intersection of the sorted and non-overlapping intervals `lhs` and `rhs`
+fn intersection(lhs: &[Interval], rhs: &[Interval]) -> Vec<Interval> {
+    let mut intervals = vec![];
+    let (mut lhs, mut rhs) = (lhs.iter().peekable(), rhs.iter().peekable());
+    while let (Some(left), Some(right)) = (lhs.peek(), rhs.peek()) {
+        let left_end = left.start + left.length;
+        let right_end = right.start + right.length;
+        let start = left.start.max(right.start);
+        let end = left_end.min(right_end);
+        if start < end {
+            intervals.push(Interval::new(start, end - start));
+        }
+        if left_end < right_end {
+            lhs.next();
+        } else {
+            rhs.next();
+        }
+    }
+    intervals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexes::PageIndex;
+
+    #[test]
+    fn intervals() {
+        let lhs = [
+            Interval::new(0, 2),
+            Interval::new(2, 3),
+            Interval::new(8, 2),
+        ];
+        let rhs = [Interval::new(4, 5)];
+        assert_eq!(union(&lhs, &rhs), vec![Interval::new(0, 10)],);
+        assert_eq!(
+            intersection(&lhs, &rhs),
+            vec![Interval::new(4, 1), Interval::new(8, 1)],
+        );
+        assert_eq!(intersection(&lhs, &[]), vec![]);
+    }
+
+    #[test]
+    fn atoms() {
+        let page = |min: Option<i32>, max: Option<i32>, null_count| PageIndex {
+            min,
+            max,
+            null_count: Some(null_count),
+        };
+        let evaluate =
+            |atom: Atom<i32>, page: &PageIndex<i32>| atom.evaluate(&Bounds::from_page(page));
+
+        let values = page(Some(1), Some(5), 0);
+        assert_eq!(evaluate(Atom::Eq(3), &values), (true, true));
+        assert_eq!(evaluate(Atom::Eq(6), &values), (false, true));
+        assert_eq!(evaluate(Atom::Lt(1), &values), (false, true));
+        assert_eq!(evaluate(Atom::Lt(6), &values), (true, false));
+        assert_eq!(evaluate(Atom::LtEq(1), &values), (true, true));
+        assert_eq!(evaluate(Atom::Gt(5), &values), (false, true));
+        assert_eq!(evaluate(Atom::GtEq(1), &values), (true, false));
+        assert_eq!(evaluate(Atom::Between(0, 5), &values), (true, false));
+        assert_eq!(evaluate(Atom::Between(6, 9), &values), (false, true));
+        assert_eq!(evaluate(Atom::In(vec![0, 9]), &values), (false, true));
+        assert_eq!(evaluate(Atom::IsNull, &values), (false, true));
+
+        let constant = page(Some(2), Some(2), 0);
+        assert_eq!(evaluate(Atom::Eq(2), &constant), (true, false));
+        assert_eq!(evaluate(Atom::In(vec![1, 2]), &constant), (true, false));
+
+        // nulls never satisfy comparisons
+        let nullable = page(Some(2), Some(2), 1);
+        assert_eq!(evaluate(Atom::Eq(2), &nullable), (true, true));
+        assert_eq!(evaluate(Atom::IsNull, &nullable), (true, true));
+
+        let nulls = page(None, None, 3);
+        assert_eq!(evaluate(Atom::Eq(2), &nulls), (false, true));
+        assert_eq!(evaluate(Atom::IsNull, &nulls), (true, false));
+    }
+
+    #[cfg(feature = "bloom_filter")]
+    #[test]
+    fn bloom_filter() {
+        use crate::bloom_filter::{hash_native, insert, is_in_set};
+
+        let mut bitset = vec![0; 32];
+        for value in [1i64, 3, 5] {
+            insert(&mut bitset, hash_native(value));
+        }
+        let may_match = |atom: Atom<i64>| {
+            atom.hashes()
+                .unwrap()
+                .into_iter()
+                .any(|hash| is_in_set(&bitset, hash))
+        };
+        assert!(may_match(Atom::Eq(3)));
+        assert!(!may_match(Atom::Eq(4)));
+        assert!(may_match(Atom::In(vec![2, 5])));
+        assert!(Atom::<i64>::Lt(3).hashes().is_none());
+        assert!(Atom::<bool>::Eq(true).hashes().is_none());
+    }
+}
@@ -0,0 +1,202 @@
+//! Positional reads, to read columns of the same file from multiple threads.
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::Arc;
+
+use crate::page::SharedBuffer;
+
+/// A source of bytes read at an offset through a shared reference, such as a file on Unix
+/// or a buffer in memory. Contrarily to [`Read`] + [`Seek`], it has no position, so that
+/// many threads can read the same source concurrently.
+///
+/// [`ReadAt::cursor`] returns a [`Read`] + [`Seek`] over the source, accepted by every reader
+/// of this crate, e.g. [`crate::read::read_metadata`], [`crate::read::get_page_iterator`],
+/// [`crate::read::read_columns_indexes`] and [`crate::bloom_filter::read`].
+pub trait ReadAt {
+    /// Reads bytes starting at `offset` into `buf`, returning the number of bytes read,
+    /// which is `0` iff `buf` is empty or `offset` is at or past the end of the source.
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize>;
+
+    /// The number of bytes of this source
+    fn size(&self) -> std::io::Result<u64>;
+
+    /// Reads exactly `buf.len()` bytes starting at `offset` into `buf`.
+    /// # Errors
+    /// Errors with [`std::io::ErrorKind::UnexpectedEof`] iff the source ends before.
+    fn read_exact_at(&self, mut buf: &mut [u8], mut offset: u64) -> std::io::Result<()> {
+        while !buf.is_empty() {
+            match self.read_at(buf, offset) {
+                Ok(0) => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "failed to fill whole buffer",
+                    ))
+                }
+                Ok(n) => {
+                    buf = &mut buf[n..];
+                    offset += n as u64;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a [`Read`] + [`Seek`] over this source starting at position 0.
+    fn cursor(&self) -> ReadAtCursor<'_, Self> {
+        ReadAtCursor {
+            source: self,
+            position: 0,
+        }
+    }
+}
+
+impl ReadAt for [u8] {
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        let start = offset.min(self.len() as u64) as usize;
+        let length = buf.len().min(self.len() - start);
+        buf[..length].copy_from_slice(&self[start..start + length]);
+        Ok(length)
+    }
+
+    fn size(&self) -> std::io::Result<u64> {
+        Ok(self.len() as u64)
+    }
+}
+
+impl ReadAt for Vec<u8> {
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        self.as_slice().read_at(buf, offset)
+    }
+
+    fn size(&self) -> std::io::Result<u64> {
+        Ok(self.len() as u64)
+    }
+}
+
+impl<T: ReadAt + ?Sized> ReadAt for &T {
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        (**self).read_at(buf, offset)
+    }
+
+    fn size(&self) -> std::io::Result<u64> {
+        (**self).size()
+    }
+}
+
+impl<T: ReadAt + ?Sized> ReadAt for Arc<T> {
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        (**self).read_at(buf, offset)
+    }
+
+    fn size(&self) -> std::io::Result<u64> {
+        (**self).size()
+    }
+}
+
+impl ReadAt for SharedBuffer {
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        self.as_slice().read_at(buf, offset)
+    }
+
+    fn size(&self) -> std::io::Result<u64> {
+        Ok(self.len() as u64)
+    }
+}
+
+#[cfg(unix)]
+impl ReadAt for std::fs::File {
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+        std::os::unix::fs::FileExt::read_at(self, buf, offset)
+    }
+
+    fn size(&self) -> std::io::Result<u64> {
+        Ok(self.metadata()?.len())
+    }
+}
+
+/// A [`Read`] + [`Seek`] over a [`ReadAt`], returned by [`ReadAt::cursor`].
+/// Every cursor has its own position, so cursors of the same source are independent.
+#[derive(Debug)]
+pub struct ReadAtCursor<'a, T: ReadAt + ?Sized> {
+    source: &'a T,
+    position: u64,
+}
+
+impl<'a, T: ReadAt + ?Sized> Clone for ReadAtCursor<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source,
+            position: self.position,
+        }
+    }
+}
+
+impl<'a, T: ReadAt + ?Sized> Read for ReadAtCursor<'a, T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.source.read_at(buf, self.position)?;
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl<'a, T: ReadAt + ?Sized> Seek for ReadAtCursor<'a, T> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(position) => Some(position),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+            SeekFrom::End(offset) => self.source.size()?.checked_add_signed(offset),
+        };
+        self.position = position.ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Seeking to a negative position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check<T: ReadAt + ?Sized>(source: &T) -> std::io::Result<()> {
+        assert_eq!(source.size()?, 10);
+
+        let mut buf = [0; 4];
+        assert_eq!(source.read_at(&mut buf, 8)?, 2);
+        assert_eq!(&buf[..2], &[8, 9]);
+        assert_eq!(source.read_at(&mut buf, 20)?, 0);
+        source.read_exact_at(&mut buf, 3)?;
+        assert_eq!(buf, [3, 4, 5, 6]);
+        assert!(source.read_exact_at(&mut buf, 7).is_err());
+
+        let mut cursor = source.cursor();
+        cursor.seek(SeekFrom::End(-3))?;
+        let mut rest = vec![];
+        cursor.read_to_end(&mut rest)?;
+        assert_eq!(rest, vec![7, 8, 9]);
+        assert!(cursor.seek(SeekFrom::Current(-11)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn sources() -> std::io::Result<()> {
+        let data = (0..10u8).collect::<Vec<_>>();
+        check(data.as_slice())?;
+        check(&data)?;
+        check(&Arc::new(data.clone()))?;
+
+        #[cfg(unix)]
+        {
+            let path =
+                std::env::temp_dir().join(format!("parquet2-read-at-{}", std::process::id()));
+            std::fs::write(&path, &data)?;
+            let result = check(&std::fs::File::open(&path)?);
+            std::fs::remove_file(&path)?;
+            result?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,783 @@
+//! Assembly of records from the leaf columns of nested fields, following the
+//! [Dremel](https://research.google/pubs/pub36632/) algorithm.
+mod value;
+
+use std::io::{Read, Seek};
+use std::ops::Range;
+
+pub use value::{Row, Value};
+
+use crate::error::{Error, Result};
+use crate::metadata::{RowGroupMetaData, SchemaDescriptor};
+use crate::schema::types::{GroupConvertedType, GroupLogicalType, ParquetType};
+use crate::schema::Repetition;
+
+use super::column::{column_reader, ColumnBatch, ColumnChunksReader};
+
+/// How the value of a [`Node`] is assembled
+#[derive(Debug)]
+enum Kind {
+    /// The value of a leaf column
+    Primitive,
+    /// A group of fields
+    Struct(Vec<(String, Node)>),
+    /// A `LIST`-annotated group, whose single repeated field is the node of its elements
+    List(Box<Node>),
+    /// A `MAP`-annotated group, whose single repeated field is a struct of a key and a value
+    Map(Box<Node>),
+    /// The repeated group of a 3-level list, whose single field is the element
+    Element(Box<Node>),
+}
+
+/// A field of the schema with the levels of its leaf columns
+#[derive(Debug)]
+struct Node {
+    repetition: Repetition,
+    /// The definition level of the leaf columns at which this field is defined
+    def_level: u32,
+    /// The repetition level of the leaf columns at which this field is repeated
+    rep_level: u32,
+    /// The indexes of the leaf columns of this field
+    leaves: Range<usize>,
+    kind: Kind,
+}
+
+impl Node {
+    fn try_new(field: &ParquetType, parent: (u32, u32), leaves: &mut usize) -> Result<Self> {
+        Self::with_kind(field, parent, leaves, |levels, leaves| match field {
+            ParquetType::PrimitiveType(_) => {
+                *leaves += 1;
+                Ok(Kind::Primitive)
+            }
+            ParquetType::GroupType {
+                field_info,
+                logical_type,
+                converted_type,
+                fields,
+            } => {
+                if *logical_type == Some(GroupLogicalType::List)
+                    || *converted_type == Some(GroupConvertedType::List)
+                {
+                    Self::list(&field_info.name, fields, levels, leaves)
+                } else if *logical_type == Some(GroupLogicalType::Map)
+                    || matches!(
+                        converted_type,
+                        Some(GroupConvertedType::Map | GroupConvertedType::MapKeyValue)
+                    )
+                {
+                    Self::map(fields, levels, leaves)
+                } else {
+                    Self::fields(fields, levels, leaves).map(Kind::Struct)
+                }
+            }
+        })
+    }
+
+    /// Returns the node of `field` whose value is assembled as `kind`.
+    fn with_kind<F: FnOnce((u32, u32), &mut usize) -> Result<Kind>>(
+        field: &ParquetType,
+        (def_level, rep_level): (u32, u32),
+        leaves: &mut usize,
+        kind: F,
+    ) -> Result<Self> {
+        let repetition = field.get_field_info().repetition;
+        let def_level = def_level + (repetition != Repetition::Required) as u32;
+        let rep_level = rep_level + (repetition == Repetition::Repeated) as u32;
+        let start = *leaves;
+        let kind = kind((def_level, rep_level), leaves)?;
+        if start == *leaves {
+            return Err(Error::oos(format!(
+                "The group \"{}\" must have at least one field",
+                field.name()
+            )));
+        }
+        Ok(Self {
+            repetition,
+            def_level,
+            rep_level,
+            leaves: start..*leaves,
+            kind,
+        })
+    }
+
+    /// Sets the maximum definition level of the leaf columns of this node
+    fn set_max_def_levels(&self, max_def_levels: &mut [u32]) {
+        match &self.kind {
+            Kind::Primitive => max_def_levels[self.leaves.start] = self.def_level,
+            Kind::Struct(fields) => fields
+                .iter()
+                .for_each(|(_, field)| field.set_max_def_levels(max_def_levels)),
+            Kind::List(node) | Kind::Map(node) | Kind::Element(node) => {
+                node.set_max_def_levels(max_def_levels)
+            }
+        }
+    }
+
+    fn fields(
+        fields: &[ParquetType],
+        levels: (u32, u32),
+        leaves: &mut usize,
+    ) -> Result<Vec<(String, Self)>> {
+        fields
+            .iter()
+            .map(|field| {
+                Ok((
+                    field.name().to_string(),
+                    Self::try_new(field, levels, leaves)?,
+                ))
+            })
+            .collect()
+    }
+
+    fn list(
+        name: &str,
+        fields: &[ParquetType],
+        levels: (u32, u32),
+        leaves: &mut usize,
+    ) -> Result<Kind> {
+        let repeated = repeated_field(fields, "LIST")?;
+        let element = match repeated {
+            // SPEC: if the repeated field is a group with one field and is named either `array`
+            // SPEC: or uses the `LIST`-annotated group's name with `_tuple` appended, then the
+            // SPEC: repeated type is the element type and elements are required.
+            // SPEC: Otherwise, the repeated field's type is the element type with the
+            // SPEC: repeated field's repetition.
+            ParquetType::GroupType { fields, .. }
+                if fields.len() == 1
+                    && repeated.name() != "array"
+                    && repeated.name() != format!("{name}_tuple") =>
+            {
+                Self::with_kind(repeated, levels, leaves, |levels, leaves| {
+                    Ok(Kind::Element(Box::new(Self::try_new(
+                        &fields[0], levels, leaves,
+                    )?)))
+                })?
+            }
+            // legacy 2-level lists: the repeated field is the element
+            _ => Self::try_new(repeated, levels, leaves)?,
+        };
+        Ok(Kind::List(Box::new(element)))
+    }
+
+    fn map(fields: &[ParquetType], levels: (u32, u32), leaves: &mut usize) -> Result<Kind> {
+        let repeated = repeated_field(fields, "MAP")?;
+        let key_value = match repeated {
+            ParquetType::GroupType { fields, .. } if matches!(fields.len(), 1 | 2) => {
+                Self::with_kind(repeated, levels, leaves, |levels, leaves| {
+                    Self::fields(fields, levels, leaves).map(Kind::Struct)
+                })?
+            }
+            _ => {
+                return Err(Error::oos(
+                    "The repeated field of a MAP must be a group of a key and a value",
+                ))
+            }
+        };
+        Ok(Kind::Map(Box::new(key_value)))
+    }
+}
+
+/// Returns the single repeated field of a group annotated with `annotation`
+fn repeated_field<'a>(fields: &'a [ParquetType], annotation: &str) -> Result<&'a ParquetType> {
+    match fields {
+        [field] if field.get_field_info().repetition == Repetition::Repeated => Ok(field),
+        _ => Err(Error::oos(format!(
+            "A {annotation} group must have a single repeated field"
+        ))),
+    }
+}
+
+fn missing_entry() -> Error {
+    Error::oos("A leaf column has less entries than required by the other leaf columns")
+}
+
+/// The state of a leaf column during the assembly of records
+struct Leaf<L> {
+    reader: L,
+    max_def_level: u32,
+    def_levels: Vec<u32>,
+    rep_levels: Vec<u32>,
+    values: std::vec::IntoIter<Value>,
+    // the number of entries of the current batch
+    length: usize,
+    // the index of the next entry in the current batch
+    index: usize,
+    // whether an entry of the current record was consumed
+    in_record: bool,
+}
+
+impl<L: Iterator<Item = Result<ColumnBatch<Value>>>> Leaf<L> {
+    /// Returns the definition and repetition levels of the next entry of the current record
+    fn peek(&mut self) -> Result<Option<(u32, u32)>> {
+        while self.index == self.length {
+            let batch = if let Some(batch) = self.reader.next() {
+                batch?
+            } else {
+                return Ok(None);
+            };
+            self.length = batch.len();
+            self.index = 0;
+            self.def_levels = batch.def_levels;
+            self.rep_levels = batch.rep_levels;
+            self.values = batch.values.into_iter();
+        }
+        let def_level = self
+            .def_levels
+            .get(self.index)
+            .copied()
+            .unwrap_or(self.max_def_level);
+        let rep_level = self.rep_levels.get(self.index).copied().unwrap_or_default();
+        // an entry with repetition level 0 starts the next record
+        Ok((rep_level > 0 || !self.in_record).then_some((def_level, rep_level)))
+    }
+
+    /// Consumes the next entry of the current record, returning its value if it is defined
+    fn consume(&mut self) -> Result<Option<Value>> {
+        let (def_level, _) = self.peek()?.ok_or_else(missing_entry)?;
+        self.index += 1;
+        self.in_record = true;
+        if def_level == self.max_def_level {
+            self.values
+                .next()
+                .map(Some)
+                .ok_or_else(|| Error::oos("A leaf column has less values than defined entries"))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+struct Leaves<L>(Vec<Leaf<L>>);
+
+impl<L: Iterator<Item = Result<ColumnBatch<Value>>>> Leaves<L> {
+    /// Whether `node` is defined at the next entry
+    fn is_defined(&mut self, node: &Node) -> Result<bool> {
+        let (def_level, _) = self.0[node.leaves.start]
+            .peek()?
+            .ok_or_else(missing_entry)?;
+        Ok(def_level >= node.def_level)
+    }
+
+    /// Consumes the entry of every leaf column of the undefined `node`
+    fn skip(&mut self, node: &Node) -> Result<()> {
+        for leaf in &mut self.0[node.leaves.clone()] {
+            if leaf.consume()?.is_some() {
+                return Err(Error::oos(
+                    "The leaf columns of a field have inconsistent definition levels",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the value of `node`
+    fn read(&mut self, node: &Node) -> Result<Value> {
+        match node.repetition {
+            Repetition::Required => self.read_instance(node),
+            Repetition::Optional => {
+                if self.is_defined(node)? {
+                    self.read_instance(node)
+                } else {
+                    self.skip(node)?;
+                    Ok(Value::Null)
+                }
+            }
+            Repetition::Repeated => self
+                .read_repeated(node, Self::read_instance)
+                .map(Value::List),
+        }
+    }
+
+    /// Reads the instances of the repeated `node`
+    fn read_repeated<T>(
+        &mut self,
+        node: &Node,
+        read: fn(&mut Self, &Node) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let mut instances = vec![];
+        if !self.is_defined(node)? {
+            self.skip(node)?;
+            return Ok(instances);
+        }
+        loop {
+            instances.push(read(self, node)?);
+            match self.0[node.leaves.start].peek()? {
+                Some((_, rep_level)) if rep_level == node.rep_level => continue,
+                _ => return Ok(instances),
+            }
+        }
+    }
+
+    /// Reads one instance of the defined `node`
+    fn read_instance(&mut self, node: &Node) -> Result<Value> {
+        match &node.kind {
+            Kind::Primitive => self.0[node.leaves.start]
+                .consume()?
+                .ok_or_else(|| Error::oos("A required value is null")),
+            Kind::Struct(fields) => self.read_row(fields).map(Value::Group),
+            Kind::List(element) => self
+                .read_repeated(element, Self::read_instance)
+                .map(Value::List),
+            Kind::Map(key_value) => self
+                .read_repeated(key_value, Self::read_key_value)
+                .map(Value::Map),
+            Kind::Element(element) => self.read(element),
+        }
+    }
+
+    fn read_row(&mut self, fields: &[(String, Node)]) -> Result<Row> {
+        let fields = fields
+            .iter()
+            .map(|(name, field)| Ok((name.clone(), self.read(field)?)))
+            .collect::<Result<_>>()?;
+        Ok(Row { fields })
+    }
+
+    fn read_key_value(&mut self, node: &Node) -> Result<(Value, Value)> {
+        let fields = if let Kind::Struct(fields) = &node.kind {
+            fields
+        } else {
+            return Err(Error::oos("The entries of a MAP must be groups"));
+        };
+        let mut row = self
+            .read_row(fields)?
+            .fields
+            .into_iter()
+            .map(|(_, value)| value);
+        let key = row.next().unwrap_or(Value::Null);
+        let value = row.next().unwrap_or(Value::Null);
+        Ok((key, value))
+    }
+}
+
+/// An [`Iterator`] of records ([`Row`]s) assembled from the leaf columns of fields.
+///
+/// Every field of the schema is assembled to a [`Value`], with:
+/// * `LIST`-annotated groups, including the legacy 2-level lists, as [`Value::List`]
+/// * `MAP`- and `MAP_KEY_VALUE`-annotated groups as [`Value::Map`]
+/// * other repeated fields as [`Value::List`] of their values
+/// * other groups as [`Value::Group`]
+pub struct RecordReader<L> {
+    fields: Vec<(String, Node)>,
+    leaves: Leaves<L>,
+}
+
+impl<L: Iterator<Item = Result<ColumnBatch<Value>>>> RecordReader<L> {
+    /// Returns a new [`RecordReader`] of the top-level `fields` of a schema, reading the
+    /// entries of their leaf columns from `leaves`, e.g. [`crate::read::ColumnReader`]s of
+    /// [`Value`], in the order of the schema.
+    /// # Errors
+    /// Errors iff the number of `leaves` is not the number of leaf columns of `fields`, or
+    /// `fields` are not valid nested types.
+    pub fn try_new(fields: &[ParquetType], leaves: Vec<L>) -> Result<Self> {
+        let mut num_leaves = 0;
+        let fields = Node::fields(fields, (0, 0), &mut num_leaves)?;
+        if num_leaves != leaves.len() {
+            return Err(Error::InvalidParameter(format!(
+                "The fields have {} leaf columns but {} were provided",
+                num_leaves,
+                leaves.len()
+            )));
+        }
+
+        let mut max_def_levels = vec![0; num_leaves];
+        fields
+            .iter()
+            .for_each(|(_, field)| field.set_max_def_levels(&mut max_def_levels));
+
+        let leaves = leaves
+            .into_iter()
+            .zip(max_def_levels)
+            .map(|(reader, max_def_level)| Leaf {
+                reader,
+                max_def_level,
+                def_levels: vec![],
+                rep_levels: vec![],
+                values: vec![].into_iter(),
+                length: 0,
+                index: 0,
+                in_record: false,
+            })
+            .collect();
+        Ok(Self {
+            fields,
+            leaves: Leaves(leaves),
+        })
+    }
+
+    fn read_record(&mut self) -> Result<Row> {
+        let row = self.leaves.read_row(&self.fields)?;
+        for leaf in &mut self.leaves.0 {
+            if leaf.peek()?.is_some() {
+                return Err(Error::oos(
+                    "A leaf column has more entries in a record than the other leaf columns",
+                ));
+            }
+        }
+        Ok(row)
+    }
+
+    fn has_record(&mut self) -> Result<bool> {
+        let mut has_entries = vec![];
+        for leaf in &mut self.leaves.0 {
+            leaf.in_record = false;
+            has_entries.push(leaf.peek()?.is_some());
+        }
+        if has_entries.iter().any(|x| *x != has_entries[0]) {
+            return Err(Error::oos(
+                "The leaf columns have a different number of records",
+            ));
+        }
+        Ok(has_entries.first().copied().unwrap_or_default())
+    }
+}
+
+impl<L: Iterator<Item = Result<ColumnBatch<Value>>>> Iterator for RecordReader<L> {
+    type Item = Result<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.has_record() {
+            Ok(true) => Some(self.read_record()),
+            Ok(false) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Returns a [`RecordReader`] of the top-level `fields` (indexes in [`SchemaDescriptor::fields`])
+/// of `row_groups`, reading each leaf column with its own clone of `reader`.
+/// # Errors
+/// Errors iff a field does not exist.
+pub fn get_record_reader<R: Read + Seek + Clone>(
+    reader: R,
+    schema: &SchemaDescriptor,
+    row_groups: &[RowGroupMetaData],
+    fields: &[usize],
+    max_page_size: usize,
+    batch_size: usize,
+) -> Result<RecordReader<ColumnChunksReader<R, Value>>> {
+    let fields = fields
+        .iter()
+        .map(|field| {
+            schema.fields().get(*field).cloned().ok_or_else(|| {
+                Error::InvalidParameter(format!(
+                    "The schema has {} fields but field {} was requested",
+                    schema.fields().len(),
+                    field
+                ))
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let leaves = fields
+        .iter()
+        .flat_map(|field| {
+            schema
+                .columns()
+                .iter()
+                .enumerate()
+                .filter(move |(_, column)| column.path_in_schema[0] == field.name())
+        })
+        .map(|(column, descriptor)| {
+            column_reader(
+                reader.clone(),
+                row_groups,
+                column,
+                &descriptor.descriptor,
+                None,
+                max_page_size,
+                batch_size,
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    RecordReader::try_new(&fields, leaves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::io_message::from_message;
+
+    /// The batch of the entries `(def_level, rep_level, value)`
+    fn leaf(
+        entries: Vec<(u32, u32, Option<Value>)>,
+    ) -> std::vec::IntoIter<Result<ColumnBatch<Value>>> {
+        let mut batch = ColumnBatch::default();
+        for (def_level, rep_level, value) in entries {
+            batch.def_levels.push(def_level);
+            batch.rep_levels.push(rep_level);
+            batch.values.extend(value);
+        }
+        vec![Ok(batch)].into_iter()
+    }
+
+    fn read(
+        message: &str,
+        leaves: Vec<std::vec::IntoIter<Result<ColumnBatch<Value>>>>,
+    ) -> Result<Vec<Row>> {
+        let fields = match from_message(message)? {
+            ParquetType::GroupType { fields, .. } => fields,
+            _ => unreachable!(),
+        };
+        RecordReader::try_new(&fields, leaves)?.collect()
+    }
+
+    fn row(fields: Vec<(&str, Value)>) -> Row {
+        Row {
+            fields: fields
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn dremel() -> Result<()> {
+        // the example of the Dremel paper
+        let message = "
+            message Document {
+                required int64 DocId;
+                optional group Links {
+                    repeated int64 Backward;
+                    repeated int64 Forward;
+                }
+                repeated group Name {
+                    repeated group Language {
+                        required binary Code;
+                        optional binary Country;
+                    }
+                    optional binary Url;
+                }
+            }
+        ";
+        let int = |x| Some(Value::Int64(x));
+        let bytes = |x: &str| Some(Value::Bytes(x.as_bytes().to_vec()));
+        let leaves = vec![
+            leaf(vec![(0, 0, int(10)), (0, 0, int(20))]),
+            leaf(vec![(1, 0, None), (2, 0, int(10)), (2, 1, int(30))]),
+            leaf(vec![
+                (2, 0, int(20)),
+                (2, 1, int(40)),
+                (2, 1, int(60)),
+                (2, 0, int(80)),
+            ]),
+            leaf(vec![
+                (2, 0, bytes("en-us")),
+                (2, 2, bytes("en")),
+                (1, 1, None),
+                (2, 1, bytes("en-gb")),
+                (1, 0, None),
+            ]),
+            leaf(vec![
+                (3, 0, bytes("us")),
+                (2, 2, None),
+                (1, 1, None),
+                (3, 1, bytes("gb")),
+                (1, 0, None),
+            ]),
+            leaf(vec![
+                (2, 0, bytes("http://A")),
+                (2, 1, bytes("http://B")),
+                (1, 1, None),
+                (2, 0, bytes("http://C")),
+            ]),
+        ];
+        let rows = read(message, leaves)?;
+
+        let language = |code, country: Option<&str>| {
+            Value::Group(row(vec![
+                ("Code", bytes(code).unwrap()),
+                ("Country", country.and_then(bytes).unwrap_or(Value::Null)),
+            ]))
+        };
+        let name = |languages, url: Option<&str>| {
+            Value::Group(row(vec![
+                ("Language", Value::List(languages)),
+                ("Url", url.and_then(bytes).unwrap_or(Value::Null)),
+            ]))
+        };
+        let ints = |x: &[i64]| Value::List(x.iter().copied().map(Value::Int64).collect());
+        assert_eq!(
+            rows,
+            vec![
+                row(vec![
+                    ("DocId", Value::Int64(10)),
+                    (
+                        "Links",
+                        Value::Group(row(vec![
+                            ("Backward", ints(&[])),
+                            ("Forward", ints(&[20, 40, 60]))
+                        ]))
+                    ),
+                    (
+                        "Name",
+                        Value::List(vec![
+                            name(
+                                vec![language("en-us", Some("us")), language("en", None)],
+                                Some("http://A")
+                            ),
+                            name(vec![], Some("http://B")),
+                            name(vec![language("en-gb", Some("gb"))], None),
+                        ])
+                    ),
+                ]),
+                row(vec![
+                    ("DocId", Value::Int64(20)),
+                    (
+                        "Links",
+                        Value::Group(row(vec![
+                            ("Backward", ints(&[10, 30])),
+                            ("Forward", ints(&[80]))
+                        ]))
+                    ),
+                    ("Name", Value::List(vec![name(vec![], Some("http://C"))])),
+                ]),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn lists() -> Result<()> {
+        let message = "
+            message schema {
+                optional group a (LIST) {
+                    repeated group list {
+                        optional int32 element;
+                    }
+                }
+                required group b (LIST) {
+                    repeated int32 element;
+                }
+                optional group c (LIST) {
+                    repeated group c_tuple {
+                        required int32 x;
+                    }
+                }
+            }
+        ";
+        let int = |x| Some(Value::Int32(x));
+        // a: [1, null], null, []
+        // b: [2], [], [3, 4]
+        // c: null, [{x: 5}], [{x: 6}, {x: 7}]
+        let leaves = vec![
+            leaf(vec![
+                (3, 0, int(1)),
+                (2, 1, None),
+                (0, 0, None),
+                (1, 0, None),
+            ]),
+            leaf(vec![
+                (1, 0, int(2)),
+                (0, 0, None),
+                (1, 0, int(3)),
+                (1, 1, int(4)),
+            ]),
+            leaf(vec![
+                (0, 0, None),
+                (2, 0, int(5)),
+                (2, 0, int(6)),
+                (2, 1, int(7)),
+            ]),
+        ];
+        let rows = read(message, leaves)?;
+
+        let list = |x: Vec<Value>| Value::List(x);
+        let group = |x| Value::Group(row(vec![("x", Value::Int32(x))]));
+        assert_eq!(
+            rows,
+            vec![
+                row(vec![
+                    ("a", list(vec![Value::Int32(1), Value::Null])),
+                    ("b", list(vec![Value::Int32(2)])),
+                    ("c", Value::Null),
+                ]),
+                row(vec![
+                    ("a", Value::Null),
+                    ("b", list(vec![])),
+                    ("c", list(vec![group(5)])),
+                ]),
+                row(vec![
+                    ("a", list(vec![])),
+                    ("b", list(vec![Value::Int32(3), Value::Int32(4)])),
+                    ("c", list(vec![group(6), group(7)])),
+                ]),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn maps() -> Result<()> {
+        let message = "
+            message schema {
+                optional group m (MAP) {
+                    repeated group key_value {
+                        required binary key;
+                        optional int32 value;
+                    }
+                }
+            }
+        ";
+        let bytes = |x: &str| Some(Value::Bytes(x.as_bytes().to_vec()));
+        // {a: 1, b: null}, null, {}
+        let leaves = vec![
+            leaf(vec![
+                (2, 0, bytes("a")),
+                (2, 1, bytes("b")),
+                (0, 0, None),
+                (1, 0, None),
+            ]),
+            leaf(vec![
+                (3, 0, Some(Value::Int32(1))),
+                (2, 1, None),
+                (0, 0, None),
+                (1, 0, None),
+            ]),
+        ];
+        let rows = read(message, leaves)?;
+        assert_eq!(
+            rows,
+            vec![
+                row(vec![(
+                    "m",
+                    Value::Map(vec![
+                        (bytes("a").unwrap(), Value::Int32(1)),
+                        (bytes("b").unwrap(), Value::Null)
+                    ])
+                )]),
+                row(vec![("m", Value::Null)]),
+                row(vec![("m", Value::Map(vec![]))]),
+            ]
+        );
+        assert_eq!(
+            rows[0].get("m"),
+            Some(&Value::Map(vec![
+                (bytes("a").unwrap(), Value::Int32(1)),
+                (bytes("b").unwrap(), Value::Null)
+            ]))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn inconsistent_leaves() -> Result<()> {
+        let message = "
+            message schema {
+                optional int32 a;
+                optional int32 b;
+            }
+        ";
+        let leaves = vec![
+            leaf(vec![(0, 0, None), (0, 0, None)]),
+            leaf(vec![(0, 0, None)]),
+        ];
+        let rows = read(message, leaves);
+        assert!(rows.is_err());
+
+        let leaves = vec![leaf(vec![(0, 0, None)])];
+        assert!(read(message, leaves).is_err());
+        Ok(())
+    }
+}
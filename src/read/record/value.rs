@@ -0,0 +1,139 @@
+use crate::deserialize::ColumnValue;
+use crate::encoding::Encoding;
+use crate::error::Error;
+use crate::schema::types::PhysicalType;
+
+/// A dynamically-typed value of a record assembled by [`super::RecordReader`].
+///
+/// Primitive values keep their physical type; logical and converted types are not applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A null value, or an absent optional group
+    Null,
+    /// A `BOOLEAN` value
+    Boolean(bool),
+    /// An `INT32` value
+    Int32(i32),
+    /// An `INT64` value
+    Int64(i64),
+    /// An `INT96` value
+    Int96([u32; 3]),
+    /// A `FLOAT` value
+    Float(f32),
+    /// A `DOUBLE` value
+    Double(f64),
+    /// A `BYTE_ARRAY` or `FIXED_LEN_BYTE_ARRAY` value
+    Bytes(Vec<u8>),
+    /// A group (struct)
+    Group(Row),
+    /// A `LIST`-annotated group or a repeated field
+    List(Vec<Value>),
+    /// A `MAP`-annotated group, as key-value pairs
+    Map(Vec<(Value, Value)>),
+}
+
+/// A record, or a group of a record: the values of its fields, in the order of the schema.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Row {
+    /// The name and value of every field
+    pub fields: Vec<(String, Value)>,
+}
+
+impl Row {
+    /// Returns the value of the field named `name`, if any
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+    }
+}
+
+fn decode_as<T: ColumnValue>(
+    values: &[u8],
+    encoding: Encoding,
+    physical_type: &PhysicalType,
+    num_values: usize,
+    decoded: &mut Vec<Value>,
+    into: fn(T) -> Value,
+) -> Result<(), Error> {
+    let mut typed = Vec::with_capacity(num_values);
+    T::decode(values, encoding, physical_type, num_values, &mut typed)?;
+    decoded.extend(typed.into_iter().map(into));
+    Ok(())
+}
+
+/// Values of every physical type can be decoded to [`Value`], so that a
+/// [`crate::read::ColumnReader`] of [`Value`] reads any column.
+impl ColumnValue for Value {
+    fn is_compatible(_: &PhysicalType) -> bool {
+        true
+    }
+
+    fn decode(
+        values: &[u8],
+        encoding: Encoding,
+        physical_type: &PhysicalType,
+        num_values: usize,
+        decoded: &mut Vec<Self>,
+    ) -> Result<(), Error> {
+        match physical_type {
+            PhysicalType::Boolean => decode_as(
+                values,
+                encoding,
+                physical_type,
+                num_values,
+                decoded,
+                Self::Boolean,
+            ),
+            PhysicalType::Int32 => decode_as(
+                values,
+                encoding,
+                physical_type,
+                num_values,
+                decoded,
+                Self::Int32,
+            ),
+            PhysicalType::Int64 => decode_as(
+                values,
+                encoding,
+                physical_type,
+                num_values,
+                decoded,
+                Self::Int64,
+            ),
+            PhysicalType::Int96 => decode_as(
+                values,
+                encoding,
+                physical_type,
+                num_values,
+                decoded,
+                Self::Int96,
+            ),
+            PhysicalType::Float => decode_as(
+                values,
+                encoding,
+                physical_type,
+                num_values,
+                decoded,
+                Self::Float,
+            ),
+            PhysicalType::Double => decode_as(
+                values,
+                encoding,
+                physical_type,
+                num_values,
+                decoded,
+                Self::Double,
+            ),
+            PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_) => decode_as(
+                values,
+                encoding,
+                physical_type,
+                num_values,
+                decoded,
+                Self::Bytes,
+            ),
+        }
+    }
+}
@@ -2,7 +2,9 @@ use std::collections::HashSet;
 use std::io::Write;
 
 use parquet_format_safe::thrift::protocol::TCompactOutputProtocol;
-use parquet_format_safe::{ColumnChunk, ColumnMetaData, Type};
+use parquet_format_safe::{
+    ColumnChunk, ColumnMetaData, Encoding as ThriftEncoding, PageEncodingStats, Type,
+};
 
 #[cfg(feature = "async")]
 use futures::AsyncWrite;
@@ -22,7 +24,7 @@ use crate::{
 #[cfg(feature = "async")]
 use super::page::write_page_async;
 
-use super::page::{write_page, PageWriteSpec};
+use super::page::{is_data_page, write_page, PageWriteSpec};
 use super::statistics::reduce;
 use super::DynStreamingIterator;
 
@@ -119,6 +121,18 @@ fn build_column_chunk(
         .next()
         .unwrap_or(Compression::Uncompressed);
 
+    let mut zstd_dictionaries = specs.iter().map(|spec| spec.zstd_dictionary());
+    let zstd_dictionary = zstd_dictionaries.next().flatten();
+    if zstd_dictionaries.any(|dictionary| dictionary != zstd_dictionary) {
+        return Err(crate::error::Error::oos(
+            "All pages within a column chunk must be compressed with the same zstd dictionary",
+        ));
+    }
+    let key_value_metadata = zstd_dictionary
+        .map(|dictionary| dictionary.to_key_value())
+        .transpose()?
+        .map(|kv| vec![kv]);
+
     // SPEC: the total compressed size is the total compressed size of each page + the header size
     let total_compressed_size = specs
         .iter()
@@ -129,7 +143,16 @@ fn build_column_chunk(
         .iter()
         .map(|x| x.header_size as i64 + x.header.uncompressed_page_size as i64)
         .sum();
-    let data_page_offset = specs.first().map(|spec| spec.offset).unwrap_or(0) as i64;
+    let column_start = specs.first().map(|spec| spec.offset).unwrap_or(0) as i64;
+    // SPEC: the dictionary page, when present, is the first page of the column chunk
+    let dictionary_page_offset = specs
+        .first()
+        .filter(|spec| !is_data_page(spec))
+        .map(|spec| spec.offset as i64);
+    let data_page_offset = specs
+        .iter()
+        .find(|spec| is_data_page(spec))
+        .map_or(column_start, |spec| spec.offset as i64);
     let num_values = specs
         .iter()
         .map(|spec| {
@@ -148,25 +171,10 @@ fn build_column_chunk(
     let mut encodings = specs
         .iter()
         .flat_map(|spec| {
-            let type_ = spec.header.type_.try_into().unwrap();
-            match type_ {
-                PageType::DataPage => vec![
-                    spec.header.data_page_header.as_ref().unwrap().encoding,
-                    Encoding::Rle.into(),
-                ],
-                PageType::DataPageV2 => {
-                    vec![
-                        spec.header.data_page_header_v2.as_ref().unwrap().encoding,
-                        Encoding::Rle.into(),
-                    ]
-                }
-                PageType::DictionaryPage => vec![
-                    spec.header
-                        .dictionary_page_header
-                        .as_ref()
-                        .unwrap()
-                        .encoding,
-                ],
+            if is_data_page(spec) {
+                vec![page_encoding(spec), Encoding::Rle.into()]
+            } else {
+                vec![page_encoding(spec)]
             }
         })
         .collect::<HashSet<_>>() // unique
@@ -176,6 +184,20 @@ fn build_column_chunk(
     // Sort the encodings to have deterministic metadata
     encodings.sort();
 
+    // the number of pages of each type and encoding, so that readers know whether all data
+    // pages are dictionary-encoded
+    let mut encoding_stats: Vec<PageEncodingStats> = vec![];
+    for spec in specs {
+        let encoding = page_encoding(spec);
+        match encoding_stats
+            .iter_mut()
+            .find(|stats| stats.page_type == spec.header.type_ && stats.encoding == encoding)
+        {
+            Some(stats) => stats.count += 1,
+            None => encoding_stats.push(PageEncodingStats::new(spec.header.type_, encoding, 1)),
+        }
+    }
+
     let statistics = specs.iter().map(|x| &x.statistics).collect::<Vec<_>>();
     let statistics = reduce(&statistics)?;
     let statistics = statistics.map(|x| serialize_statistics(x.as_ref()));
@@ -190,18 +212,18 @@ fn build_column_chunk(
         num_values,
         total_uncompressed_size,
         total_compressed_size,
-        key_value_metadata: None,
+        key_value_metadata,
         data_page_offset,
         index_page_offset: None,
-        dictionary_page_offset: None,
+        dictionary_page_offset,
         statistics,
-        encoding_stats: None,
+        encoding_stats: Some(encoding_stats),
         bloom_filter_offset: None,
     };
 
     Ok(ColumnChunk {
         file_path: None, // same file for now.
-        file_offset: data_page_offset + total_compressed_size,
+        file_offset: column_start + total_compressed_size,
         meta_data: Some(metadata),
         offset_index_offset: None,
         offset_index_length: None,
@@ -211,3 +233,19 @@ fn build_column_chunk(
         encrypted_column_metadata: None,
     })
 }
+
+/// The encoding of the values of the page written as `spec`
+fn page_encoding(spec: &PageWriteSpec) -> ThriftEncoding {
+    let type_ = spec.header.type_.try_into().unwrap();
+    match type_ {
+        PageType::DataPage => spec.header.data_page_header.as_ref().unwrap().encoding,
+        PageType::DataPageV2 => spec.header.data_page_header_v2.as_ref().unwrap().encoding,
+        PageType::DictionaryPage => {
+            spec.header
+                .dictionary_page_header
+                .as_ref()
+                .unwrap()
+                .encoding
+        }
+    }
+}
@@ -1,48 +1,87 @@
-use crate::compression::CompressionOptions;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::compression::{self, CodecRegistry, CompressionOptions, ZstdDictionary, ZstdLevel};
 use crate::error::{Error, Result};
+use crate::page::{CompressedDataPage, DataPage, Page};
 use crate::page::{CompressedDictPage, CompressedPage, DataPageHeader, DictPage};
 use crate::FallibleStreamingIterator;
-use crate::{
-    compression,
-    page::{CompressedDataPage, DataPage, Page},
-};
+
+/// Compresses `input_buf` with the codec of `compression` in `registry`, or with
+/// `zstd_dictionary` when compressing with zstd.
+fn compress_buffer(
+    compression: CompressionOptions,
+    registry: &CodecRegistry,
+    zstd_dictionary: Option<&ZstdDictionary>,
+    input_buf: &[u8],
+    output_buf: &mut Vec<u8>,
+) -> Result<()> {
+    match (zstd_dictionary, compression) {
+        (Some(dictionary), CompressionOptions::Zstd(level)) => {
+            compression::compress_with_zstd_dictionary(level, dictionary, input_buf, output_buf)
+        }
+        _ => registry.compress(compression, input_buf, output_buf),
+    }
+}
 
 /// Compresses a [`DataPage`] into a [`CompressedDataPage`].
+/// When `uncompressed_fallback` is true, the values of V2 pages are stored uncompressed
+/// (with `is_compressed = false`) if compressing them does not reduce their size.
 fn compress_data(
     page: DataPage,
     mut compressed_buffer: Vec<u8>,
     compression: CompressionOptions,
+    registry: &CodecRegistry,
+    zstd_dictionary: Option<&ZstdDictionary>,
+    uncompressed_fallback: bool,
 ) -> Result<CompressedDataPage> {
     let DataPage {
-        mut buffer,
-        header,
+        buffer,
+        mut header,
         descriptor,
         selected_rows,
     } = page;
     let uncompressed_page_size = buffer.len();
-    if compression != CompressionOptions::Uncompressed {
-        match &header {
+    let buffer = if compression != CompressionOptions::Uncompressed {
+        match &mut header {
             DataPageHeader::V1(_) => {
-                compression::compress(compression, &buffer, &mut compressed_buffer)?;
+                compress_buffer(
+                    compression,
+                    registry,
+                    zstd_dictionary,
+                    &buffer,
+                    &mut compressed_buffer,
+                )?;
+                compressed_buffer.into()
             }
             DataPageHeader::V2(header) => {
                 let levels_byte_length = (header.repetition_levels_byte_length
                     + header.definition_levels_byte_length)
                     as usize;
                 compressed_buffer.extend_from_slice(&buffer[..levels_byte_length]);
-                compression::compress(
+                compress_buffer(
                     compression,
+                    registry,
+                    zstd_dictionary,
                     &buffer[levels_byte_length..],
                     &mut compressed_buffer,
                 )?;
+                if uncompressed_fallback && compressed_buffer.len() >= buffer.len() {
+                    header.is_compressed = Some(false);
+                    buffer
+                } else {
+                    compressed_buffer.into()
+                }
             }
-        };
+        }
     } else {
-        std::mem::swap(&mut buffer, &mut compressed_buffer);
+        // the page is moved, which does not copy shared buffers
+        buffer
     };
     Ok(CompressedDataPage::new_read(
         header,
-        compressed_buffer,
+        buffer,
         compression.into(),
         uncompressed_page_size,
         descriptor,
@@ -54,6 +93,8 @@ fn compress_dict(
     page: DictPage,
     mut compressed_buffer: Vec<u8>,
     compression: CompressionOptions,
+    registry: &CodecRegistry,
+    zstd_dictionary: Option<&ZstdDictionary>,
 ) -> Result<CompressedDictPage> {
     let DictPage {
         mut buffer,
@@ -62,7 +103,13 @@ fn compress_dict(
     } = page;
     let uncompressed_page_size = buffer.len();
     if compression != CompressionOptions::Uncompressed {
-        compression::compress(compression, &buffer, &mut compressed_buffer)?;
+        compress_buffer(
+            compression,
+            registry,
+            zstd_dictionary,
+            &buffer,
+            &mut compressed_buffer,
+        )?;
     } else {
         std::mem::swap(&mut buffer, &mut compressed_buffer);
     }
@@ -87,14 +134,188 @@ pub fn compress(
     compressed_buffer: Vec<u8>,
     compression: CompressionOptions,
 ) -> Result<CompressedPage> {
-    match page {
-        Page::Data(page) => {
-            compress_data(page, compressed_buffer, compression).map(CompressedPage::Data)
-        }
+    compress_with_registry(
+        page,
+        compressed_buffer,
+        compression,
+        &CodecRegistry::default(),
+    )
+}
+
+/// Compresses an [`EncodedPage`] into a [`CompressedPage`] like [`compress`], using the codecs
+/// of `registry`.
+/// # Errors
+/// Errors if the compressor fails
+pub fn compress_with_registry(
+    page: Page,
+    compressed_buffer: Vec<u8>,
+    compression: CompressionOptions,
+    registry: &CodecRegistry,
+) -> Result<CompressedPage> {
+    compress_page(page, compressed_buffer, compression, registry, None, false)
+}
+
+/// Compresses `page`, attaching `zstd_dictionary` to the compressed page when it is
+/// compressed with zstd.
+fn compress_page(
+    page: Page,
+    compressed_buffer: Vec<u8>,
+    compression: CompressionOptions,
+    registry: &CodecRegistry,
+    zstd_dictionary: Option<&Arc<ZstdDictionary>>,
+    uncompressed_fallback: bool,
+) -> Result<CompressedPage> {
+    let zstd_dictionary =
+        zstd_dictionary.filter(|_| matches!(compression, CompressionOptions::Zstd(_)));
+    let dictionary = zstd_dictionary.map(|dictionary| dictionary.as_ref());
+    let mut page = match page {
+        Page::Data(page) => compress_data(
+            page,
+            compressed_buffer,
+            compression,
+            registry,
+            dictionary,
+            uncompressed_fallback,
+        )
+        .map(CompressedPage::Data),
         Page::Dict(page) => {
-            compress_dict(page, compressed_buffer, compression).map(CompressedPage::Dict)
+            compress_dict(page, compressed_buffer, compression, registry, dictionary)
+                .map(CompressedPage::Dict)
+        }
+    }?;
+    page.set_zstd_dictionary(zstd_dictionary.cloned());
+    Ok(page)
+}
+
+/// Compresses an [`EncodedPage`] into a [`CompressedPage`] with zstd and `dictionary`,
+/// attaching the dictionary to the page (see [`CompressedPage::zstd_dictionary`]).
+/// # Errors
+/// Errors if the compressor fails
+pub fn compress_with_zstd_dictionary(
+    page: Page,
+    compressed_buffer: Vec<u8>,
+    dictionary: &Arc<ZstdDictionary>,
+    level: Option<ZstdLevel>,
+) -> Result<CompressedPage> {
+    compress_page(
+        page,
+        compressed_buffer,
+        CompressionOptions::Zstd(level),
+        &CodecRegistry::default(),
+        Some(dictionary),
+        false,
+    )
+}
+
+/// The outcome of trial-compressing the first pages of a column chunk with a candidate
+/// [`CompressionOptions`] (see [`AdaptiveCompression`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompressionTrial {
+    /// The candidate
+    pub compression: CompressionOptions,
+    /// The total size of the trial pages before compression
+    pub uncompressed_size: usize,
+    /// The total size of the trial pages after compression
+    pub compressed_size: usize,
+    /// The time spent compressing the trial pages
+    pub duration: Duration,
+}
+
+impl CompressionTrial {
+    /// The compression ratio of this trial, `uncompressed_size / compressed_size`.
+    pub fn ratio(&self) -> f64 {
+        self.uncompressed_size as f64 / self.compressed_size.max(1) as f64
+    }
+}
+
+type CompressionCost = Arc<dyn Fn(&CompressionTrial) -> f64 + Send + Sync>;
+type CompressionReport = Arc<dyn Fn(&[CompressionTrial], CompressionOptions) + Send + Sync>;
+
+/// Options to select the compression of a column chunk by trial-compressing its first pages
+/// with a set of candidates, see [`Compressor::with_adaptive_compression`].
+///
+/// Every candidate is scored by a user-supplied cost function of its [`CompressionTrial`],
+/// and the candidate with the lowest cost compresses the whole column chunk.
+#[derive(Clone)]
+pub struct AdaptiveCompression {
+    candidates: Vec<CompressionOptions>,
+    num_trial_pages: usize,
+    cost: CompressionCost,
+    report: Option<CompressionReport>,
+}
+
+impl std::fmt::Debug for AdaptiveCompression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdaptiveCompression")
+            .field("candidates", &self.candidates)
+            .field("num_trial_pages", &self.num_trial_pages)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AdaptiveCompression {
+    /// Returns a new [`AdaptiveCompression`] trying `candidates` on the first `num_trial_pages`
+    /// pages (at least one) of the column chunk. Ties of `cost` are won by the first candidate.
+    pub fn new<F>(candidates: Vec<CompressionOptions>, num_trial_pages: usize, cost: F) -> Self
+    where
+        F: Fn(&CompressionTrial) -> f64 + Send + Sync + 'static,
+    {
+        Self {
+            candidates,
+            num_trial_pages: num_trial_pages.max(1),
+            cost: Arc::new(cost),
+            report: None,
         }
     }
+
+    /// Calls `report` with the trials and the selected candidate once the trials are over.
+    /// Useful when the [`Compressor`] is consumed by e.g. [`crate::write::FileWriter`].
+    pub fn with_report<F>(mut self, report: F) -> Self
+    where
+        F: Fn(&[CompressionTrial], CompressionOptions) + Send + Sync + 'static,
+    {
+        self.report = Some(Arc::new(report));
+        self
+    }
+}
+
+/// Returns a copy of `page`, used to compress the same page with different candidates.
+fn copy_page(page: &Page) -> Page {
+    match page {
+        Page::Data(page) => Page::Data(page.clone()),
+        Page::Dict(page) => Page::Dict(DictPage::new(
+            page.buffer.clone(),
+            page.num_values,
+            page.is_sorted,
+        )),
+    }
+}
+
+/// The settings used to compress the pages of a column chunk, shared by [`Compressor`]
+/// and [`super::ParallelCompressor`].
+#[derive(Debug, Clone, Default)]
+pub(super) struct PageCompressor {
+    pub registry: CodecRegistry,
+    pub zstd_dictionary: Option<Arc<ZstdDictionary>>,
+    pub uncompressed_fallback: bool,
+}
+
+impl PageCompressor {
+    pub fn compress(
+        &self,
+        page: Page,
+        compressed_buffer: Vec<u8>,
+        compression: CompressionOptions,
+    ) -> Result<CompressedPage> {
+        compress_page(
+            page,
+            compressed_buffer,
+            compression,
+            &self.registry,
+            self.zstd_dictionary.as_ref(),
+            self.uncompressed_fallback,
+        )
+    }
 }
 
 /// A [`FallibleStreamingIterator`] that consumes [`Page`] and yields [`CompressedPage`]
@@ -102,6 +323,10 @@ pub fn compress(
 pub struct Compressor<I: Iterator<Item = Result<Page>>> {
     iter: I,
     compression: CompressionOptions,
+    page_compressor: PageCompressor,
+    adaptive: Option<AdaptiveCompression>,
+    trials: Vec<CompressionTrial>,
+    trial_pages: VecDeque<CompressedPage>,
     buffer: Vec<u8>,
     current: Option<CompressedPage>,
 }
@@ -112,11 +337,60 @@ impl<I: Iterator<Item = Result<Page>>> Compressor<I> {
         Self {
             iter,
             compression,
+            page_compressor: PageCompressor::default(),
+            adaptive: None,
+            trials: vec![],
+            trial_pages: VecDeque::new(),
             buffer,
             current: None,
         }
     }
 
+    /// Sets the [`CodecRegistry`] used to compress pages.
+    pub fn with_registry(mut self, registry: CodecRegistry) -> Self {
+        self.page_compressor.registry = registry;
+        self
+    }
+
+    /// Compresses pages with `dictionary` when compressing with zstd. The dictionary is
+    /// attached to the pages and stored in the metadata of their column chunk,
+    /// from where the page readers load it back.
+    ///
+    /// Other parquet implementations can't decompress the pages of such column chunks
+    /// (see [`ZstdDictionary`]).
+    pub fn with_zstd_dictionary(mut self, dictionary: ZstdDictionary) -> Self {
+        self.page_compressor.zstd_dictionary = Some(Arc::new(dictionary));
+        self
+    }
+
+    /// Stores the values of V2 pages uncompressed (with `is_compressed = false`) when
+    /// compressing them does not reduce their size, e.g. for random floats or already
+    /// compressed blobs. Defaults to `false`.
+    pub fn with_uncompressed_fallback(mut self, uncompressed_fallback: bool) -> Self {
+        self.page_compressor.uncompressed_fallback = uncompressed_fallback;
+        self
+    }
+
+    /// Selects the compression of the column chunk by trial-compressing its first pages with
+    /// the candidates of `adaptive`, instead of the compression passed to [`Compressor::new`].
+    /// The trials are available via [`Compressor::trials`] once the first page is compressed.
+    pub fn with_adaptive_compression(mut self, adaptive: AdaptiveCompression) -> Self {
+        self.adaptive = Some(adaptive);
+        self
+    }
+
+    /// The trials of the candidates of [`AdaptiveCompression`], empty until the first page
+    /// is compressed or when adaptive compression is not used.
+    pub fn trials(&self) -> &[CompressionTrial] {
+        &self.trials
+    }
+
+    /// The compression of the column chunk, i.e. the selected candidate of
+    /// [`AdaptiveCompression`] once the trials are over.
+    pub fn compression(&self) -> CompressionOptions {
+        self.compression
+    }
+
     /// Creates a new [`Compressor`] (same as `new`)
     pub fn new_from_vec(iter: I, compression: CompressionOptions, buffer: Vec<u8>) -> Self {
         Self::new(iter, compression, buffer)
@@ -125,13 +399,64 @@ impl<I: Iterator<Item = Result<Page>>> Compressor<I> {
     /// Deconstructs itself into its iterator and scratch buffer.
     pub fn into_inner(mut self) -> (I, Vec<u8>) {
         let mut buffer = if let Some(page) = self.current.as_mut() {
-            std::mem::take(page.buffer())
+            page.buffer().take_owned()
         } else {
             std::mem::take(&mut self.buffer)
         };
         buffer.clear();
         (self.iter, buffer)
     }
+
+    /// Compresses the first pages with every candidate and selects the one with the lowest
+    /// cost, keeping the pages it compressed to be yielded first.
+    fn select_compression(&mut self, adaptive: AdaptiveCompression) -> Result<()> {
+        if adaptive.candidates.is_empty() {
+            return Err(Error::InvalidParameter(
+                "Adaptive compression requires at least one candidate".to_string(),
+            ));
+        }
+        let pages = self
+            .iter
+            .by_ref()
+            .take(adaptive.num_trial_pages)
+            .collect::<Result<Vec<_>>>()?;
+        if pages.is_empty() {
+            return Ok(());
+        }
+        let mut best: Option<(f64, Vec<CompressedPage>)> = None;
+        for &compression in &adaptive.candidates {
+            let start = Instant::now();
+            let compressed = pages
+                .iter()
+                .map(|page| {
+                    self.page_compressor
+                        .compress(copy_page(page), vec![], compression)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let trial = CompressionTrial {
+                compression,
+                uncompressed_size: compressed.iter().map(|page| page.uncompressed_size()).sum(),
+                compressed_size: compressed.iter().map(|page| page.compressed_size()).sum(),
+                duration: start.elapsed(),
+            };
+            let cost = (adaptive.cost)(&trial);
+            let is_best = match &best {
+                Some((best, _)) => cost < *best,
+                None => true,
+            };
+            if is_best {
+                best = Some((cost, compressed));
+                self.compression = compression;
+            }
+            self.trials.push(trial);
+        }
+        self.trial_pages = best.map(|(_, pages)| pages).unwrap_or_default().into();
+
+        if let Some(report) = &adaptive.report {
+            report(&self.trials, self.compression);
+        }
+        Ok(())
+    }
 }
 
 impl<I: Iterator<Item = Result<Page>>> FallibleStreamingIterator for Compressor<I> {
@@ -140,16 +465,30 @@ impl<I: Iterator<Item = Result<Page>>> FallibleStreamingIterator for Compressor<
 
     fn advance(&mut self) -> std::result::Result<(), Self::Error> {
         let mut compressed_buffer = if let Some(page) = self.current.as_mut() {
-            std::mem::take(page.buffer())
+            page.buffer().take_owned()
         } else {
             std::mem::take(&mut self.buffer)
         };
         compressed_buffer.clear();
 
+        if let Some(adaptive) = self.adaptive.take() {
+            self.select_compression(adaptive)?;
+        }
+        if let Some(page) = self.trial_pages.pop_front() {
+            self.buffer = compressed_buffer;
+            self.current = Some(page);
+            return Ok(());
+        }
+
         let next = self
             .iter
             .next()
-            .map(|x| x.and_then(|page| compress(page, compressed_buffer, self.compression)))
+            .map(|x| {
+                x.and_then(|page| {
+                    self.page_compressor
+                        .compress(page, compressed_buffer, self.compression)
+                })
+            })
             .transpose()?;
         self.current = next;
         Ok(())
@@ -3,6 +3,7 @@ mod compression;
 mod file;
 mod indexes;
 pub(crate) mod page;
+mod parallel;
 mod row_group;
 pub(self) mod statistics;
 
@@ -15,12 +16,20 @@ pub use stream::FileStreamer;
 mod dyn_iter;
 pub use dyn_iter::{DynIter, DynStreamingIterator};
 
-pub use compression::{compress, Compressor};
+pub use compression::{
+    compress, compress_with_registry, compress_with_zstd_dictionary, AdaptiveCompression,
+    CompressionTrial, Compressor,
+};
+pub use parallel::{
+    CompressedColumn, CompressionPool, ParallelColumnCompressor, ParallelCompressor,
+};
 
 pub use file::{write_metadata_sidecar, FileWriter};
 
 pub use row_group::ColumnOffsetsMetadata;
 
+pub use crate::page::Version;
+
 use crate::page::CompressedPage;
 
 pub type RowGroupIter<'a, E> =
@@ -35,13 +44,6 @@ pub struct WriteOptions {
     pub version: Version,
 }
 
-/// The parquet version to use
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum Version {
-    V1,
-    V2,
-}
-
 /// Used to recall the state of the parquet writer - whether sync or async.
 #[derive(PartialEq)]
 enum State {
@@ -49,12 +51,3 @@ enum State {
     Started,
     Finished,
 }
-
-impl From<Version> for i32 {
-    fn from(version: Version) -> Self {
-        match version {
-            Version::V1 => 1,
-            Version::V2 => 2,
-        }
-    }
-}
@@ -10,7 +10,7 @@ use parquet_format_safe::thrift::protocol::TCompactOutputStreamProtocol;
 use parquet_format_safe::thrift::protocol::TCompactOutputProtocol;
 use parquet_format_safe::{DictionaryPageHeader, Encoding, PageType};
 
-use crate::compression::Compression;
+use crate::compression::{Compression, ZstdDictionary};
 use crate::error::{Error, Result};
 use crate::page::{
     CompressedDataPage, CompressedDictPage, CompressedPage, DataPageHeader, ParquetPageHeader,
@@ -48,9 +48,17 @@ pub struct PageWriteSpec {
     pub offset: u64,
     pub bytes_written: u64,
     pub compression: Compression,
+    zstd_dictionary: Option<Arc<ZstdDictionary>>,
     pub statistics: Option<Arc<dyn Statistics>>,
 }
 
+impl PageWriteSpec {
+    /// The zstd dictionary the page was compressed with, if any
+    pub fn zstd_dictionary(&self) -> Option<&Arc<ZstdDictionary>> {
+        self.zstd_dictionary.as_ref()
+    }
+}
+
 pub fn write_page<W: Write>(
     writer: &mut W,
     offset: u64,
@@ -89,6 +97,7 @@ pub fn write_page<W: Write>(
         offset,
         bytes_written,
         compression: compressed_page.compression(),
+        zstd_dictionary: compressed_page.zstd_dictionary().cloned(),
         statistics,
         num_rows: selected_rows.map(|x| x.last().unwrap().length),
         num_values,
@@ -135,6 +144,7 @@ pub async fn write_page_async<W: AsyncWrite + Unpin + Send>(
         offset,
         bytes_written,
         compression: compressed_page.compression(),
+        zstd_dictionary: compressed_page.zstd_dictionary().cloned(),
         statistics,
         num_rows: selected_rows.map(|x| x.last().unwrap().length),
         num_values,
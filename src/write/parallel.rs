@@ -0,0 +1,557 @@
+use std::collections::BTreeMap;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use crate::compression::{CodecRegistry, CompressionOptions, ZstdDictionary};
+use crate::error::{Error, Result};
+use crate::page::{CompressedPage, Page};
+use crate::FallibleStreamingIterator;
+
+use super::compression::PageCompressor;
+use super::DynStreamingIterator;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct Pool {
+    sender: Option<Mutex<Sender<Job>>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl Drop for Pool {
+    fn drop(&mut self) {
+        // closing the channel stops the workers once they have run the pending jobs
+        self.sender.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// A pool of threads compressing pages, used by [`ParallelCompressor`] and
+/// [`ParallelColumnCompressor`].
+///
+/// The pool is cheap to clone, so that the [`ParallelCompressor`]s of all columns of
+/// a row group can share it. Its threads are stopped when the last clone is dropped.
+#[derive(Clone)]
+pub struct CompressionPool {
+    pool: Arc<Pool>,
+}
+
+impl std::fmt::Debug for CompressionPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompressionPool")
+            .field("num_threads", &self.num_threads())
+            .finish()
+    }
+}
+
+impl CompressionPool {
+    /// Returns a new [`CompressionPool`] of `num_threads` threads (at least one).
+    pub fn new(num_threads: usize) -> Self {
+        let (sender, receiver) = channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..num_threads.max(1))
+            .map(|_| {
+                let receiver = receiver.clone();
+                std::thread::spawn(move || loop {
+                    // the lock is released before running the job
+                    let job = receiver.lock().unwrap().recv();
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => break,
+                    }
+                })
+            })
+            .collect();
+        Self {
+            pool: Arc::new(Pool {
+                sender: Some(Mutex::new(sender)),
+                workers,
+            }),
+        }
+    }
+
+    /// The number of threads of this pool
+    pub fn num_threads(&self) -> usize {
+        self.pool.workers.len()
+    }
+
+    fn execute(&self, job: Job) {
+        // the workers only stop when the pool is dropped
+        let _ = self.pool.sender.as_ref().unwrap().lock().unwrap().send(job);
+    }
+}
+
+type JobResult<T> = std::thread::Result<Result<T>>;
+
+/// The results of jobs run in a [`CompressionPool`], yielded in the order the jobs were read.
+///
+/// The first error, from reading a job or from running it, is yielded after the results of
+/// the previous jobs and ends the results.
+struct InFlight<T> {
+    pool: CompressionPool,
+    max_in_flight: usize,
+    sender: Sender<(usize, JobResult<T>)>,
+    receiver: Mutex<Receiver<(usize, JobResult<T>)>>,
+    // results received out of order, by index
+    results: BTreeMap<usize, Result<T>>,
+    num_read: usize,
+    num_yielded: usize,
+    is_finished: bool,
+}
+
+impl<T: Send + 'static> InFlight<T> {
+    fn new(pool: CompressionPool, max_in_flight: usize) -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            pool,
+            max_in_flight,
+            sender,
+            receiver: Mutex::new(receiver),
+            results: BTreeMap::new(),
+            num_read: 0,
+            num_yielded: 0,
+            is_finished: false,
+        }
+    }
+
+    /// Reads jobs from `next` and runs them in the pool until `max_in_flight` are in flight.
+    fn fill<F, N>(&mut self, mut next: N)
+    where
+        F: FnOnce() -> Result<T> + Send + 'static,
+        N: FnMut() -> Option<Result<F>>,
+    {
+        while !self.is_finished && self.num_read - self.num_yielded < self.max_in_flight {
+            let index = self.num_read;
+            match next() {
+                Some(Ok(job)) => {
+                    let sender = self.sender.clone();
+                    self.pool.execute(Box::new(move || {
+                        let result = catch_unwind(AssertUnwindSafe(job));
+                        // the receiver may have been dropped in the meantime
+                        let _ = sender.send((index, result));
+                    }));
+                }
+                Some(Err(error)) => {
+                    self.results.insert(index, Err(error));
+                    self.is_finished = true;
+                }
+                None => {
+                    self.is_finished = true;
+                    return;
+                }
+            }
+            self.num_read += 1;
+        }
+    }
+
+    /// Returns the result of the next job, waiting for it to run, or `None` when all the
+    /// jobs read have been yielded.
+    fn next(&mut self) -> Option<Result<T>> {
+        if self.num_yielded == self.num_read {
+            return None;
+        }
+        let result = loop {
+            if let Some(result) = self.results.remove(&self.num_yielded) {
+                break result;
+            }
+            // `self.sender` is alive, so this only returns once a job has run
+            let (index, result) = self.receiver.get_mut().unwrap().recv().unwrap();
+            match result {
+                Ok(result) => {
+                    self.results.insert(index, result);
+                }
+                Err(panic) => resume_unwind(panic),
+            }
+        };
+        self.num_yielded += 1;
+        if result.is_err() {
+            // the results of the jobs still in flight are discarded
+            self.is_finished = true;
+            self.num_read = self.num_yielded;
+            self.results.clear();
+        }
+        Some(result)
+    }
+}
+
+/// A [`FallibleStreamingIterator`] that consumes [`Page`] and yields [`CompressedPage`] like
+/// [`super::Compressor`], compressing the pages in a [`CompressionPool`].
+///
+/// Pages are yielded in the order of `iter`. At most `max_in_flight` pages are read
+/// from `iter` ahead of the last yielded page, which bounds the memory used.
+/// The buffers of yielded pages are re-used to compress the next pages.
+///
+/// An error, from `iter` or from compressing a page, is yielded after the previous pages
+/// and ends the iterator.
+/// # Panics
+/// A panic while compressing a page is resumed on the thread advancing this iterator.
+pub struct ParallelCompressor<I: Iterator<Item = Result<Page>>> {
+    iter: I,
+    compression: CompressionOptions,
+    page_compressor: Arc<PageCompressor>,
+    in_flight: InFlight<CompressedPage>,
+    buffers: Vec<Vec<u8>>,
+    current: Option<CompressedPage>,
+}
+
+impl<I: Iterator<Item = Result<Page>>> ParallelCompressor<I> {
+    /// Creates a new [`ParallelCompressor`] compressing pages with `compression` in `pool`,
+    /// with up to twice as many pages in flight as the pool has threads.
+    pub fn new(iter: I, compression: CompressionOptions, pool: CompressionPool) -> Self {
+        let max_in_flight = 2 * pool.num_threads();
+        Self {
+            iter,
+            compression,
+            page_compressor: Arc::new(PageCompressor::default()),
+            in_flight: InFlight::new(pool, max_in_flight),
+            buffers: vec![],
+            current: None,
+        }
+    }
+
+    /// Sets the maximum number of pages (at least one) read ahead of the last yielded page.
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.in_flight.max_in_flight = max_in_flight.max(1);
+        self
+    }
+
+    /// Sets the [`CodecRegistry`] used to compress pages.
+    pub fn with_registry(mut self, registry: CodecRegistry) -> Self {
+        Arc::make_mut(&mut self.page_compressor).registry = registry;
+        self
+    }
+
+    /// Compresses pages with `dictionary` when compressing with zstd
+    /// (see [`super::Compressor::with_zstd_dictionary`]).
+    pub fn with_zstd_dictionary(mut self, dictionary: ZstdDictionary) -> Self {
+        Arc::make_mut(&mut self.page_compressor).zstd_dictionary = Some(Arc::new(dictionary));
+        self
+    }
+
+    /// Stores the values of V2 pages uncompressed when compressing them does not reduce
+    /// their size (see [`super::Compressor::with_uncompressed_fallback`]).
+    pub fn with_uncompressed_fallback(mut self, uncompressed_fallback: bool) -> Self {
+        Arc::make_mut(&mut self.page_compressor).uncompressed_fallback = uncompressed_fallback;
+        self
+    }
+}
+
+impl<I: Iterator<Item = Result<Page>>> FallibleStreamingIterator for ParallelCompressor<I> {
+    type Item = CompressedPage;
+    type Error = Error;
+
+    fn advance(&mut self) -> Result<()> {
+        if let Some(mut page) = self.current.take() {
+            let mut buffer = page.buffer().take_owned();
+            buffer.clear();
+            self.buffers.push(buffer);
+        }
+
+        let Self {
+            iter,
+            compression,
+            page_compressor,
+            buffers,
+            ..
+        } = self;
+        self.in_flight.fill(|| {
+            iter.next().map(|page| {
+                page.map(|page| {
+                    let buffer = buffers.pop().unwrap_or_default();
+                    let (compression, page_compressor) = (*compression, page_compressor.clone());
+                    move || page_compressor.compress(page, buffer, compression)
+                })
+            })
+        });
+        self.current = self.in_flight.next().transpose()?;
+        Ok(())
+    }
+
+    fn get(&self) -> Option<&Self::Item> {
+        self.current.as_ref()
+    }
+}
+
+/// A compressed column chunk yielded by [`ParallelColumnCompressor`]
+pub type CompressedColumn = DynStreamingIterator<'static, CompressedPage, Error>;
+
+/// An [`Iterator`] of the column chunks of a row group, compressing whole column chunks
+/// concurrently in a [`CompressionPool`], so that it can be written with
+/// [`super::FileWriter::write`].
+///
+/// Column chunks are yielded in the order of `columns`. At most `max_in_flight` column
+/// chunks are read ahead of the last yielded one; the compressed pages of a column chunk are
+/// held in memory until it is written.
+///
+/// An error, from `columns` or from compressing a column chunk, is yielded after the
+/// previous column chunks and ends the iterator.
+/// # Panics
+/// A panic while compressing a column chunk is resumed on the thread advancing this iterator.
+pub struct ParallelColumnCompressor<I, C>
+where
+    I: Iterator<Item = Result<C>>,
+    C: Iterator<Item = Result<Page>> + Send + 'static,
+{
+    columns: I,
+    compression: CompressionOptions,
+    page_compressor: Arc<PageCompressor>,
+    in_flight: InFlight<Vec<CompressedPage>>,
+}
+
+impl<I, C> ParallelColumnCompressor<I, C>
+where
+    I: Iterator<Item = Result<C>>,
+    C: Iterator<Item = Result<Page>> + Send + 'static,
+{
+    /// Creates a new [`ParallelColumnCompressor`] compressing the pages of `columns` with
+    /// `compression` in `pool`, with as many column chunks in flight as the pool has threads.
+    pub fn new(columns: I, compression: CompressionOptions, pool: CompressionPool) -> Self {
+        let max_in_flight = pool.num_threads();
+        Self {
+            columns,
+            compression,
+            page_compressor: Arc::new(PageCompressor::default()),
+            in_flight: InFlight::new(pool, max_in_flight),
+        }
+    }
+
+    /// Sets the maximum number of column chunks (at least one) read ahead of the last
+    /// yielded column chunk.
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.in_flight.max_in_flight = max_in_flight.max(1);
+        self
+    }
+
+    /// Sets the [`CodecRegistry`] used to compress pages.
+    pub fn with_registry(mut self, registry: CodecRegistry) -> Self {
+        Arc::make_mut(&mut self.page_compressor).registry = registry;
+        self
+    }
+
+    /// Compresses pages with `dictionary` when compressing with zstd
+    /// (see [`super::Compressor::with_zstd_dictionary`]).
+    pub fn with_zstd_dictionary(mut self, dictionary: ZstdDictionary) -> Self {
+        Arc::make_mut(&mut self.page_compressor).zstd_dictionary = Some(Arc::new(dictionary));
+        self
+    }
+
+    /// Stores the values of V2 pages uncompressed when compressing them does not reduce
+    /// their size (see [`super::Compressor::with_uncompressed_fallback`]).
+    pub fn with_uncompressed_fallback(mut self, uncompressed_fallback: bool) -> Self {
+        Arc::make_mut(&mut self.page_compressor).uncompressed_fallback = uncompressed_fallback;
+        self
+    }
+}
+
+impl<I, C> Iterator for ParallelColumnCompressor<I, C>
+where
+    I: Iterator<Item = Result<C>>,
+    C: Iterator<Item = Result<Page>> + Send + 'static,
+{
+    type Item = Result<CompressedColumn>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Self {
+            columns,
+            compression,
+            page_compressor,
+            ..
+        } = self;
+        self.in_flight.fill(|| {
+            columns.next().map(|pages| {
+                pages.map(|pages| {
+                    let (compression, page_compressor) = (*compression, page_compressor.clone());
+                    move || {
+                        pages
+                            .map(|page| page_compressor.compress(page?, vec![], compression))
+                            .collect::<Result<Vec<_>>>()
+                    }
+                })
+            })
+        });
+        self.in_flight.next().map(|pages| {
+            pages.map(|pages| {
+                DynStreamingIterator::new(CompressedPages {
+                    pages: pages.into_iter(),
+                    current: None,
+                })
+            })
+        })
+    }
+}
+
+/// The compressed pages of a column chunk, as a [`FallibleStreamingIterator`]
+struct CompressedPages {
+    pages: std::vec::IntoIter<CompressedPage>,
+    current: Option<CompressedPage>,
+}
+
+impl FallibleStreamingIterator for CompressedPages {
+    type Item = CompressedPage;
+    type Error = Error;
+
+    fn advance(&mut self) -> Result<()> {
+        self.current = self.pages.next();
+        Ok(())
+    }
+
+    fn get(&self) -> Option<&Self::Item> {
+        self.current.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::{Codec, Compression};
+    use crate::page::DictPage;
+
+    fn pages(num_pages: usize) -> Vec<Page> {
+        (0..num_pages)
+            .map(|i| Page::Dict(DictPage::new(vec![i as u8; 1000 + i], i, false)))
+            .collect()
+    }
+
+    #[test]
+    fn ordered() -> Result<()> {
+        let pool = CompressionPool::new(3);
+        for max_in_flight in [1, 2, 10] {
+            let mut compressor = ParallelCompressor::new(
+                pages(20).into_iter().map(Ok),
+                CompressionOptions::Uncompressed,
+                pool.clone(),
+            )
+            .with_max_in_flight(max_in_flight);
+
+            let mut num_pages = 0;
+            while let Some(page) = compressor.next()? {
+                assert_eq!(page.compression(), Compression::Uncompressed);
+                match page {
+                    CompressedPage::Dict(page) => {
+                        assert_eq!(page.num_values, num_pages);
+                        assert_eq!(
+                            page.buffer.as_slice(),
+                            vec![num_pages as u8; 1000 + num_pages]
+                        );
+                    }
+                    CompressedPage::Data(_) => unreachable!(),
+                }
+                num_pages += 1;
+            }
+            assert_eq!(num_pages, 20);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn error() {
+        let iter = pages(5)
+            .into_iter()
+            .map(Ok)
+            .chain(std::iter::once(Err(Error::OutOfSpec("a".to_string()))))
+            .chain(pages(5).into_iter().map(Ok));
+        let mut compressor = ParallelCompressor::new(
+            iter,
+            CompressionOptions::Uncompressed,
+            CompressionPool::new(2),
+        );
+        for _ in 0..5 {
+            assert!(compressor.next().unwrap().is_some());
+        }
+        assert!(compressor.next().is_err());
+        assert!(compressor.next().unwrap().is_none());
+    }
+
+    /// A codec copying its input, failing to compress buffers starting with 7
+    #[derive(Debug)]
+    struct FailingCodec;
+
+    impl Codec for FailingCodec {
+        fn compress(&self, input: &[u8], output: &mut Vec<u8>) -> Result<()> {
+            if input.first() == Some(&7) {
+                return Err(Error::InvalidParameter("7".to_string()));
+            }
+            output.extend_from_slice(input);
+            Ok(())
+        }
+
+        fn decompress(&self, input: &[u8], output: &mut [u8]) -> Result<()> {
+            output.copy_from_slice(input);
+            Ok(())
+        }
+
+        fn max_compressed_len(&self, input_length: usize) -> Result<usize> {
+            Ok(input_length)
+        }
+    }
+
+    fn failing_registry() -> CodecRegistry {
+        let mut registry = CodecRegistry::new();
+        registry.register(Compression::Snappy, Arc::new(FailingCodec));
+        registry
+    }
+
+    #[test]
+    fn compression_error() {
+        // the pages after the failing one are in flight
+        let mut compressor = ParallelCompressor::new(
+            pages(10).into_iter().map(Ok),
+            CompressionOptions::Snappy,
+            CompressionPool::new(2),
+        )
+        .with_registry(failing_registry())
+        .with_max_in_flight(10);
+        for _ in 0..7 {
+            assert!(compressor.next().unwrap().is_some());
+        }
+        assert!(compressor.next().is_err());
+        assert!(compressor.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn columns() -> Result<()> {
+        let columns = (0..5).map(|column| Ok(pages(column + 1).into_iter().map(Ok)));
+        let compressor = ParallelColumnCompressor::new(
+            columns,
+            CompressionOptions::Uncompressed,
+            CompressionPool::new(3),
+        );
+
+        let mut num_columns = 0;
+        for pages in compressor {
+            let mut pages = pages?;
+            let mut num_pages = 0;
+            while let Some(page) = pages.next()? {
+                match page {
+                    CompressedPage::Dict(page) => assert_eq!(page.num_values, num_pages),
+                    CompressedPage::Data(_) => unreachable!(),
+                }
+                num_pages += 1;
+            }
+            num_columns += 1;
+            assert_eq!(num_pages, num_columns);
+        }
+        assert_eq!(num_columns, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn columns_error() {
+        // the third column fails to compress, while the next ones are in flight
+        let columns = (0..5).map(|column| Ok(pages(3 * column + 2).into_iter().map(Ok)));
+        let mut compressor = ParallelColumnCompressor::new(
+            columns,
+            CompressionOptions::Snappy,
+            CompressionPool::new(2),
+        )
+        .with_registry(failing_registry())
+        .with_max_in_flight(5);
+        assert!(compressor.next().unwrap().is_ok());
+        assert!(compressor.next().unwrap().is_ok());
+        assert!(compressor.next().unwrap().is_err());
+        assert!(compressor.next().is_none());
+    }
+}
@@ -27,5 +27,15 @@ pub fn page_to_vec(page: &DataPage, dict: Option<&BinaryPageDict>) -> Result<Vec
                 .map(|x| x.and_then(|x| dict.dict.value(x as usize).map(|x| x.to_vec())));
             deserialize_optional(validity, values)
         }
+        BinaryPageState::RequiredDeltaLengthByteArray(values) => {
+            values.map(|x| x.map(|x| Some(x.to_vec()))).collect()
+        }
+        BinaryPageState::OptionalDeltaLengthByteArray(validity, values) => {
+            deserialize_optional(validity, values.map(|x| x.map(|x| x.to_vec())))
+        }
+        BinaryPageState::RequiredDeltaByteArray(values) => values.map(|x| x.map(Some)).collect(),
+        BinaryPageState::OptionalDeltaByteArray(validity, values) => {
+            deserialize_optional(validity, values)
+        }
     }
 }
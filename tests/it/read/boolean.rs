@@ -1,4 +1,4 @@
-use parquet2::deserialize::BooleanPageState;
+use parquet2::deserialize::{BooleanPageState, HybridRleBooleanIter};
 use parquet2::encoding::hybrid_rle::BitmapIter;
 use parquet2::error::Result;
 use parquet2::page::DataPage;
@@ -17,5 +17,9 @@ pub fn page_to_vec(page: &DataPage) -> Result<Vec<Option<bool>>> {
             .into_iter()
             .map(Some)
             .collect()),
+        BooleanPageState::OptionalRle(validity, values) => deserialize_optional(validity, values),
+        BooleanPageState::RequiredRle(runs) => HybridRleBooleanIter::new(runs)
+            .map(|x| x.map(Some))
+            .collect(),
     }
 }
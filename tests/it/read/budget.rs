@@ -0,0 +1,194 @@
+use std::io::Cursor;
+
+use parquet2::compression::CompressionOptions;
+use parquet2::error::{Error, Result};
+use parquet2::metadata::SchemaDescriptor;
+use parquet2::read::{
+    get_page_iterator, read_column_with_budget, read_metadata, Decompressor, MemoryBudget,
+};
+use parquet2::schema::types::{ParquetType, PhysicalType};
+use parquet2::write::{
+    Compressor, DynIter, DynStreamingIterator, FileWriter, Version, WriteOptions,
+};
+use parquet2::FallibleStreamingIterator;
+
+use crate::write::array_to_page_v1;
+
+#[test]
+fn memory_budget() -> Result<()> {
+    let options = WriteOptions {
+        write_statistics: false,
+        version: Version::V1,
+    };
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int64,
+        )],
+    );
+    let pages = (0..4)
+        .map(|_| {
+            let array = (0..1000).map(|i| Some(i % 100)).collect::<Vec<_>>();
+            array_to_page_v1(&array, &options, &schema.columns()[0].descriptor)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let compressor = Compressor::new(
+        DynIter::new(pages.into_iter().map(Ok)),
+        CompressionOptions::Zstd(None),
+        vec![],
+    );
+    let columns = std::iter::once(Ok(DynStreamingIterator::new(compressor)));
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.write(DynIter::new(columns))?;
+    writer.end(None)?;
+    let mut reader = Cursor::new(writer.into_inner().into_inner());
+
+    let metadata = read_metadata(&mut reader)?;
+    let column_metadata = &metadata.row_groups[0].columns()[0];
+    let compressed_size = column_metadata.compressed_size() as usize;
+    let uncompressed_size = column_metadata.uncompressed_size() as usize;
+
+    // the reader and the decompressor share the budget
+    let budget = MemoryBudget::new(compressed_size + uncompressed_size);
+    let pages = get_page_iterator(column_metadata, &mut reader, None, vec![], usize::MAX)?
+        .with_memory_budget(budget.clone());
+    let mut pages = Decompressor::new(pages, vec![]).with_memory_budget(budget.clone());
+    let mut num_pages = 0;
+    while pages.next()?.is_some() {
+        assert!(budget.used() > 0);
+        num_pages += 1;
+    }
+    assert_eq!(num_pages, 4);
+    drop(pages);
+    assert_eq!(budget.used(), 0);
+
+    // pages hold their memory until they are dropped
+    let budget = MemoryBudget::new(compressed_size);
+    let pages = get_page_iterator(column_metadata, &mut reader, None, vec![], usize::MAX)?
+        .with_memory_budget(budget.clone());
+    let mut used = 0;
+    let pages = pages
+        .map(|page| {
+            assert!(budget.used() > used);
+            used = budget.used();
+            page
+        })
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(pages.len(), 4);
+    assert_eq!(budget.used(), used);
+    drop(pages);
+    assert_eq!(budget.used(), 0);
+
+    // the decompressor exhausts the budget
+    let budget = MemoryBudget::new(100);
+    let pages = get_page_iterator(column_metadata, &mut reader, None, vec![], usize::MAX)?;
+    let mut pages = Decompressor::new(pages, vec![]).with_memory_budget(budget.clone());
+    assert!(matches!(pages.next(), Err(Error::MemoryBudgetExceeded(_))));
+
+    // the budget is exhausted by another reservation
+    let budget = MemoryBudget::new(compressed_size);
+    let (chunk, reservation) = read_column_with_budget(&mut reader, column_metadata, &budget)?;
+    assert_eq!(chunk.len(), compressed_size);
+    let mut pages = get_page_iterator(column_metadata, &mut reader, None, vec![], usize::MAX)?
+        .with_memory_budget(budget.clone());
+    assert!(matches!(
+        pages.next(),
+        Some(Err(Error::MemoryBudgetExceeded(_)))
+    ));
+    assert_eq!(budget.used(), compressed_size);
+    drop(reservation);
+    assert_eq!(budget.used(), 0);
+    Ok(())
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn memory_budget_streams() -> Result<()> {
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+
+    use futures::task::noop_waker_ref;
+    use futures::StreamExt;
+    use parquet2::read::get_page_stream_with_budget;
+
+    let options = WriteOptions {
+        write_statistics: false,
+        version: Version::V1,
+    };
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int64,
+        )],
+    );
+    // pages of increasing sizes
+    let pages = (1..=4)
+        .map(|size| {
+            let array = (0..size * 100).map(Some).collect::<Vec<_>>();
+            array_to_page_v1(&array, &options, &schema.columns()[0].descriptor)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let compressor = Compressor::new(
+        DynIter::new(pages.into_iter().map(Ok)),
+        CompressionOptions::Uncompressed,
+        vec![],
+    );
+    let columns = std::iter::once(Ok(DynStreamingIterator::new(compressor)));
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.write(DynIter::new(columns))?;
+    writer.end(None)?;
+    let data = writer.into_inner().into_inner();
+
+    let metadata = read_metadata(&mut Cursor::new(&data))?;
+    let column_metadata = &metadata.row_groups[0].columns()[0];
+
+    // two streams of the column sharing a budget that fits the largest page of one stream
+    // with the third page of the other, but not the last pages of both: a stream holding its
+    // previous page while waiting for the next one would dead-lock them
+    let budget = MemoryBudget::new(2500);
+    let read = |budget: MemoryBudget| {
+        let data = data.clone();
+        async move {
+            let mut reader = futures::io::Cursor::new(data);
+            let pages = get_page_stream_with_budget(
+                column_metadata,
+                &mut reader,
+                vec![],
+                Arc::new(|_, _| true),
+                usize::MAX,
+                budget.clone(),
+            )
+            .await?;
+            let mut pages = Box::pin(pages);
+            let mut num_pages = 0;
+            while let Some(page) = pages.next().await {
+                let _page = page?;
+                assert!(budget.used() > 0);
+                num_pages += 1;
+                // let the other stream read its next page
+                futures::pending!();
+            }
+            Result::Ok(num_pages)
+        }
+    };
+    let mut both = Box::pin(futures::future::join(
+        read(budget.clone()),
+        read(budget.clone()),
+    ));
+
+    // the readers are in memory: the streams are only pending on the budget
+    let mut cx = Context::from_waker(noop_waker_ref());
+    let (a, b) = (0..100)
+        .find_map(|_| match both.as_mut().poll(&mut cx) {
+            Poll::Ready(result) => Some(result),
+            Poll::Pending => None,
+        })
+        .expect("the streams must not wait for each other forever");
+    assert_eq!(a?, 4);
+    assert_eq!(b?, 4);
+    assert_eq!(budget.used(), 0);
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use parquet2::encoding::{byte_stream_split, Encoding};
+use parquet2::error::Result;
+use parquet2::metadata::{Descriptor, SchemaDescriptor};
+use parquet2::page::Version;
+
+use super::{data_page, fixed_binary, primitive, Levels};
+
+/// Returns the descriptor of the only column of `message`
+fn descriptor(message: &str) -> Result<Descriptor> {
+    let schema = SchemaDescriptor::try_from_message(message)?;
+    Ok(schema.columns()[0].descriptor.clone())
+}
+
+#[test]
+fn float() -> Result<()> {
+    let values = vec![Some(1.5f32), None, Some(-2.25), Some(f32::MAX), None];
+    let validity = values.iter().map(Option::is_some).collect::<Vec<_>>();
+    let mut buffer = vec![];
+    byte_stream_split::encode(
+        &values.iter().flatten().copied().collect::<Vec<_>>(),
+        &mut buffer,
+    );
+
+    let page = data_page(
+        descriptor("message schema { optional float c; }")?,
+        Version::V1,
+        Levels::Validity(&validity),
+        Encoding::ByteStreamSplit,
+        buffer,
+    )?;
+    assert_eq!(primitive::page_to_vec::<f32>(&page, None)?, values);
+    Ok(())
+}
+
+#[test]
+fn double() -> Result<()> {
+    let values = (0..100).map(|x| x as f64 * 0.1).collect::<Vec<_>>();
+    let mut buffer = vec![];
+    byte_stream_split::encode(&values, &mut buffer);
+
+    let page = data_page(
+        descriptor("message schema { required double c; }")?,
+        Version::V1,
+        Levels::Validity(&[true; 100]),
+        Encoding::ByteStreamSplit,
+        buffer,
+    )?;
+    let expected = values.into_iter().map(Some).collect::<Vec<_>>();
+    assert_eq!(primitive::page_to_vec::<f64>(&page, None)?, expected);
+    Ok(())
+}
+
+fn fixed_len(size: usize) -> Result<()> {
+    let values = (0..10u8)
+        .map(|x| (x % 3 != 0).then(|| vec![x; size]))
+        .collect::<Vec<_>>();
+    let validity = values.iter().map(Option::is_some).collect::<Vec<_>>();
+    let mut buffer = vec![];
+    byte_stream_split::encode_fixed_len(
+        &values
+            .iter()
+            .flatten()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>(),
+        size,
+        &mut buffer,
+    )?;
+
+    let message = format!(
+        "message schema {{ optional fixed_len_byte_array({}) c; }}",
+        size
+    );
+    let page = data_page(
+        descriptor(&message)?,
+        Version::V1,
+        Levels::Validity(&validity),
+        Encoding::ByteStreamSplit,
+        buffer,
+    )?;
+    assert_eq!(fixed_binary::page_to_vec(&page, None)?, values);
+    Ok(())
+}
+
+#[test]
+fn fixed_len_binary() -> Result<()> {
+    fixed_len(3)
+}
+
+#[test]
+fn large_fixed_len_binary() -> Result<()> {
+    // larger than the stack buffer of the decoder
+    fixed_len(20)
+}
@@ -0,0 +1,119 @@
+use std::io::Cursor;
+
+use parquet2::compression::{CodecRegistry, CompressionOptions};
+use parquet2::encoding::dictionary::NativeEncoder;
+use parquet2::error::Result;
+use parquet2::metadata::SchemaDescriptor;
+use parquet2::read::{
+    get_column_reader, read_metadata, ColumnBatch, ColumnIterator, ColumnPages, ColumnReader,
+    Decompressor, MemoryBudget,
+};
+use parquet2::schema::types::{ParquetType, PhysicalType};
+use parquet2::write::{
+    Compressor, DynIter, DynStreamingIterator, FileWriter, Version, WriteOptions,
+};
+
+use crate::write::array_to_page_v1;
+
+/// Writes `values` in a dictionary-encoded row group of 30 rows followed by a plain-encoded
+/// row group of the remaining rows
+pub fn write_row_groups(values: &[Option<i32>]) -> Result<Vec<u8>> {
+    let (first, second) = values.split_at(30);
+
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+    let descriptor = schema.columns()[0].descriptor.clone();
+    let options = WriteOptions {
+        write_statistics: false,
+        version: Version::V1,
+    };
+
+    // a dictionary-encoded row group followed by a plain-encoded row group
+    let mut encoder = NativeEncoder::<i32>::try_new(descriptor.clone(), Default::default())?;
+    for value in first {
+        assert!(encoder.push(*value)?);
+    }
+    let row_groups = vec![
+        encoder.finish()?,
+        vec![array_to_page_v1(second, &options, &descriptor)?],
+    ];
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    for pages in row_groups {
+        let pages = DynStreamingIterator::new(Compressor::new_from_vec(
+            DynIter::new(pages.into_iter().map(Ok)),
+            CompressionOptions::Snappy,
+            vec![],
+        ));
+        writer.write(DynIter::new(std::iter::once(Ok(pages))))?;
+    }
+    writer.end(None)?;
+    Ok(writer.into_inner().into_inner())
+}
+
+pub fn values() -> Vec<Option<i32>> {
+    (0..50)
+        .map(|x| (x % 3 != 0).then_some(x % 5))
+        .collect::<Vec<_>>()
+}
+
+#[test]
+fn column_reader() -> Result<()> {
+    let values = values();
+    let mut reader = Cursor::new(write_row_groups(&values)?);
+
+    let metadata = read_metadata(&mut reader)?;
+    assert_eq!(metadata.row_groups.len(), 2);
+    let batches =
+        get_column_reader::<_, i32>(reader.clone(), &metadata.row_groups, 0, None, usize::MAX, 7)?
+            .collect::<Result<Vec<ColumnBatch<i32>>>>()?;
+
+    assert_eq!(batches.len(), 8);
+    assert!(batches[..7].iter().all(|batch| batch.len() == 7));
+    let result = batches
+        .iter()
+        .flat_map(|batch| {
+            let mut values = batch.values.iter();
+            batch
+                .def_levels
+                .iter()
+                .map(move |level| (*level == 1).then(|| *values.next().unwrap()))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(result, values);
+    assert!(batches.iter().all(|batch| batch.rep_levels.is_empty()));
+
+    // the physical type is checked
+    assert!(
+        get_column_reader::<_, i64>(reader.clone(), &metadata.row_groups, 0, None, 1024, 7)
+            .is_err()
+    );
+
+    // the pages are decompressed by a decompressor with its own codecs and memory budget
+    let columns = metadata
+        .row_groups
+        .iter()
+        .map(|row_group| row_group.columns()[0].clone())
+        .collect::<Vec<_>>();
+    let descriptor = &columns[0].descriptor().descriptor;
+    let pages = |budget| {
+        let columns = ColumnIterator::new(reader.clone(), columns.clone(), None, vec![], 1024);
+        Decompressor::new(ColumnPages::new(columns), vec![])
+            .with_registry(CodecRegistry::default())
+            .with_memory_budget(MemoryBudget::new(budget))
+    };
+    let batches = ColumnReader::<_, i32>::try_new(pages(usize::MAX), descriptor, 7)?
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(batches.len(), 8);
+
+    // the reader does not resume after an error
+    let mut reader = ColumnReader::<_, i32>::try_new(pages(1), descriptor, 7)?;
+    assert!(reader.next().unwrap().is_err());
+    assert!(reader.next().is_none());
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use parquet2::encoding::Encoding;
+use parquet2::encoding::{delta_bitpacked, delta_byte_array, delta_length_byte_array};
+use parquet2::error::Result;
+use parquet2::indexes::Interval;
+use parquet2::metadata::Descriptor;
+use parquet2::page::Version;
+use parquet2::schema::types::{PhysicalType, PrimitiveType};
+use parquet2::schema::Repetition;
+
+use super::{data_page, page_to_array, Levels};
+use crate::Array;
+
+/// Returns the descriptor of a column of `physical_type` without repetition
+fn descriptor(physical_type: PhysicalType, repetition: Repetition) -> Descriptor {
+    let mut primitive_type = PrimitiveType::from_physical("a".to_string(), physical_type);
+    primitive_type.field_info.repetition = repetition;
+    Descriptor {
+        primitive_type,
+        max_def_level: (repetition == Repetition::Optional) as i16,
+        max_rep_level: 0,
+    }
+}
+
+fn select<T: Clone>(values: &[T], intervals: &[Interval]) -> Vec<T> {
+    intervals
+        .iter()
+        .flat_map(|i| values[i.start..i.start + i.length].to_vec())
+        .collect()
+}
+
+#[test]
+fn int32_optional() -> Result<()> {
+    let expected = (0..200)
+        .map(|x| (x % 7 != 0).then_some(x * 3 - 100))
+        .collect::<Vec<Option<i32>>>();
+    let validity = expected.iter().map(|x| x.is_some()).collect::<Vec<_>>();
+    let mut values = vec![];
+    delta_bitpacked::encode_i32(expected.iter().flatten().copied(), &mut values);
+
+    let mut page = data_page(
+        descriptor(PhysicalType::Int32, Repetition::Optional),
+        Version::V2,
+        Levels::Validity(&validity),
+        Encoding::DeltaBinaryPacked,
+        values,
+    )?;
+    assert_eq!(page_to_array(&page, None)?, Array::Int32(expected.clone()));
+
+    let intervals = vec![Interval::new(5, 10), Interval::new(150, 50)];
+    page.selected_rows = Some(intervals.clone());
+    assert_eq!(
+        page_to_array(&page, None)?,
+        Array::Int32(select(&expected, &intervals))
+    );
+    Ok(())
+}
+
+#[test]
+fn int64_required() -> Result<()> {
+    let expected = (0..300).map(|x| x * x).collect::<Vec<i64>>();
+    let mut values = vec![];
+    delta_bitpacked::encode(expected.iter().copied(), &mut values);
+
+    let mut page = data_page(
+        descriptor(PhysicalType::Int64, Repetition::Required),
+        Version::V2,
+        Levels::Validity(&vec![true; expected.len()]),
+        Encoding::DeltaBinaryPacked,
+        values,
+    )?;
+    let expected = expected.into_iter().map(Some).collect::<Vec<_>>();
+    assert_eq!(page_to_array(&page, None)?, Array::Int64(expected.clone()));
+
+    let intervals = vec![Interval::new(0, 1), Interval::new(129, 3)];
+    page.selected_rows = Some(intervals.clone());
+    assert_eq!(
+        page_to_array(&page, None)?,
+        Array::Int64(select(&expected, &intervals))
+    );
+    Ok(())
+}
+
+#[test]
+fn binary_delta_length() -> Result<()> {
+    let expected = (0..50)
+        .map(|x| (x % 3 != 0).then(|| format!("value {}", x).into_bytes()))
+        .collect::<Vec<_>>();
+    let validity = expected.iter().map(|x| x.is_some()).collect::<Vec<_>>();
+    let mut values = vec![];
+    delta_length_byte_array::encode(expected.iter().flatten(), &mut values);
+
+    let page = data_page(
+        descriptor(PhysicalType::ByteArray, Repetition::Optional),
+        Version::V2,
+        Levels::Validity(&validity),
+        Encoding::DeltaLengthByteArray,
+        values,
+    )?;
+    assert_eq!(page_to_array(&page, None)?, Array::Binary(expected));
+    Ok(())
+}
+
+#[test]
+fn binary_delta() -> Result<()> {
+    let expected = ["Hello", "Helicopter", "", "Hell", "World"]
+        .iter()
+        .map(|x| Some(x.as_bytes().to_vec()))
+        .collect::<Vec<_>>();
+    let mut values = vec![];
+    delta_byte_array::encode(expected.iter().flatten().map(|x| x.as_slice()), &mut values);
+
+    let page = data_page(
+        descriptor(PhysicalType::ByteArray, Repetition::Required),
+        Version::V2,
+        Levels::Validity(&vec![true; expected.len()]),
+        Encoding::DeltaByteArray,
+        values,
+    )?;
+    assert_eq!(page_to_array(&page, None)?, Array::Binary(expected));
+    Ok(())
+}
+
+#[test]
+fn fixed_len_delta() -> Result<()> {
+    let expected = (0..20)
+        .map(|x| (x % 4 != 0).then(|| format!("v{:03}", x * 11).into_bytes()))
+        .collect::<Vec<_>>();
+    let validity = expected.iter().map(|x| x.is_some()).collect::<Vec<_>>();
+    let mut values = vec![];
+    delta_byte_array::encode(expected.iter().flatten().map(|x| x.as_slice()), &mut values);
+
+    let page = data_page(
+        descriptor(PhysicalType::FixedLenByteArray(4), Repetition::Optional),
+        Version::V2,
+        Levels::Validity(&validity),
+        Encoding::DeltaByteArray,
+        values,
+    )?;
+    assert_eq!(page_to_array(&page, None)?, Array::FixedLenBinary(expected));
+    Ok(())
+}
@@ -0,0 +1,228 @@
+use std::io::Cursor;
+
+use parquet2::compression::{builtin_codec, CodecRegistry, Compression, CompressionOptions};
+use parquet2::deserialize::{NativePageState, SliceFilteredIter};
+use parquet2::encoding::dictionary::NativeEncoder;
+use parquet2::error::Result;
+use parquet2::metadata::{ColumnChunkMetaData, SchemaDescriptor};
+use parquet2::page::Page;
+use parquet2::read::{
+    get_page_iterator, read_dictionary_filter, read_dictionary_page,
+    read_dictionary_page_with_registry, read_metadata, BasicDecompressor, DictionaryFilter,
+    Predicate, Value,
+};
+use parquet2::write::{
+    Compressor, DynIter, DynStreamingIterator, FileWriter, Version, WriteOptions,
+};
+use parquet2::FallibleStreamingIterator;
+use parquet_format_safe::{Encoding, PageEncodingStats, PageType};
+
+use super::column_reader::{values, write_row_groups};
+
+fn filter(
+    reader: &Cursor<Vec<u8>>,
+    chunk: &ColumnChunkMetaData,
+    predicate: &Predicate,
+) -> Result<DictionaryFilter> {
+    read_dictionary_filter(&mut reader.clone(), chunk, predicate, 1024)
+        .map(|filter| filter.unwrap())
+}
+
+fn matches(
+    reader: &Cursor<Vec<u8>>,
+    chunk: &ColumnChunkMetaData,
+    predicate: &Predicate,
+) -> Result<Vec<u32>> {
+    Ok(match filter(reader, chunk, predicate)? {
+        DictionaryFilter::Matches(matches) => matches.indices().collect(),
+        DictionaryFilter::Skip => vec![],
+    })
+}
+
+#[test]
+fn dictionary_filter() -> Result<()> {
+    let values = values();
+    let mut reader = Cursor::new(write_row_groups(&values)?);
+    let metadata = read_metadata(&mut reader)?;
+    let chunk = &metadata.row_groups[0].columns()[0];
+    let reader = &reader;
+
+    // the dictionary has the 5 distinct values
+    let dictionary = read_dictionary_page(&mut reader.clone(), chunk, 1024)?.unwrap();
+    assert_eq!(dictionary.num_values, 5);
+    let int = Value::Int32;
+    let column = &["col"];
+    assert_eq!(
+        matches(reader, chunk, &Predicate::eq(column, int(3)))?.len(),
+        1
+    );
+    assert_eq!(
+        matches(reader, chunk, &Predicate::lt(column, int(3)))?.len(),
+        3
+    );
+    let predicate =
+        Predicate::is_in(column, vec![int(1), int(4)]).and(!Predicate::eq(column, int(4)));
+    assert_eq!(matches(reader, chunk, &predicate)?.len(), 1);
+
+    // all data pages are dictionary-encoded according to the metadata of the written file
+    let missing = Predicate::eq(column, int(7));
+    assert_eq!(filter(reader, chunk, &missing)?, DictionaryFilter::Skip);
+    // nulls may still satisfy the predicate
+    let predicate = missing.clone().or(Predicate::is_null(column));
+    assert_ne!(filter(reader, chunk, &predicate)?, DictionaryFilter::Skip);
+    assert_eq!(matches(reader, chunk, &!missing.clone())?.len(), 5);
+    // no value is equal to null
+    let null = Predicate::eq(column, Value::Null);
+    assert_eq!(filter(reader, chunk, &null)?, DictionaryFilter::Skip);
+    assert_eq!(matches(reader, chunk, &!null)?.len(), 5);
+
+    // data pages that fell back to plain encoding must still be evaluated
+    let mut column_chunk = chunk.column_chunk().clone();
+    let encoding_stats = column_chunk
+        .meta_data
+        .as_mut()
+        .unwrap()
+        .encoding_stats
+        .as_mut();
+    encoding_stats.unwrap().push(PageEncodingStats::new(
+        PageType::DATA_PAGE,
+        Encoding::PLAIN,
+        1,
+    ));
+    let fallback = ColumnChunkMetaData::new(column_chunk, chunk.descriptor().clone());
+    assert!(matches!(
+        filter(reader, &fallback, &missing)?,
+        DictionaryFilter::Matches(matches) if matches.is_empty()
+    ));
+
+    // the dictionary is decompressed with the codecs of the registry
+    let mut registry = CodecRegistry::new();
+    let snappy = builtin_codec(CompressionOptions::Snappy)?;
+    registry.register(Compression::Snappy, snappy.into());
+    let dictionary =
+        read_dictionary_page_with_registry(&mut reader.clone(), chunk, 1024, &registry)?;
+    assert_eq!(dictionary.unwrap().num_values, 5);
+    #[cfg(feature = "gzip")]
+    {
+        use parquet2::read::read_dictionary_filter_with_registry;
+
+        let gzip = builtin_codec(CompressionOptions::Gzip(None))?;
+        registry.register(Compression::Snappy, gzip.into());
+        let predicate = Predicate::eq(column, int(3));
+        let mut reader = reader.clone();
+        assert!(read_dictionary_filter_with_registry(
+            &mut reader,
+            chunk,
+            &predicate,
+            1024,
+            &registry
+        )
+        .is_err());
+    }
+
+    // the first page is read when the dictionary page offset is not set
+    let mut column_chunk = chunk.column_chunk().clone();
+    let meta_data = column_chunk.meta_data.as_mut().unwrap();
+    meta_data.data_page_offset = meta_data.dictionary_page_offset.take().unwrap();
+    let without_offset = ColumnChunkMetaData::new(column_chunk, chunk.descriptor().clone());
+    let dictionary = read_dictionary_page(&mut reader.clone(), &without_offset, 1024)?;
+    assert_eq!(dictionary.unwrap().num_values, 5);
+    assert_eq!(
+        matches(reader, &without_offset, &Predicate::eq(column, int(3)))?.len(),
+        1
+    );
+
+    // the plain-encoded row group has no dictionary
+    let chunk = &metadata.row_groups[1].columns()[0];
+    assert!(read_dictionary_page(&mut reader.clone(), chunk, 1024)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn dictionary_filter_select() -> Result<()> {
+    let values = values();
+    let mut reader = Cursor::new(write_row_groups(&values)?);
+    let metadata = read_metadata(&mut reader)?;
+    let chunk = &metadata.row_groups[0].columns()[0];
+
+    let dictionary = read_dictionary_page(&mut reader.clone(), chunk, 1024)?.unwrap();
+    let entries = dictionary
+        .buffer
+        .chunks_exact(4)
+        .map(|chunk| i32::from_le_bytes(chunk.try_into().unwrap()))
+        .collect::<Vec<_>>();
+    let predicate = Predicate::lt(&["col"], Value::Int32(2));
+    let matches = match filter(&reader, chunk, &predicate)? {
+        DictionaryFilter::Matches(matches) => matches,
+        DictionaryFilter::Skip => unreachable!(),
+    };
+
+    // only the values whose entry matches are deserialized
+    let pages = get_page_iterator(chunk, reader, None, vec![], usize::MAX)?;
+    let mut pages = BasicDecompressor::new(pages, vec![]);
+    let mut result = vec![];
+    while let Some(page) = pages.next()? {
+        let Page::Data(page) = page else { continue };
+        match NativePageState::<i32, &[i32]>::try_new(page, Some(&entries))? {
+            NativePageState::OptionalDictionary(_, dictionary) => {
+                // the page has the 30 values of the row group, of which the non-null are indexed
+                assert_eq!(page.num_values(), 30);
+                let indexes = dictionary
+                    .indexes
+                    .take(values[..30].iter().flatten().count());
+                let selected = matches.select(indexes.clone())?;
+                for index in SliceFilteredIter::new(indexes, selected) {
+                    result.push(dictionary.dict[index? as usize]);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+    let expected = values[..30]
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|value| *value < 2)
+        .collect::<Vec<_>>();
+    assert_eq!(result, expected);
+    Ok(())
+}
+
+#[test]
+fn dictionary_filter_unsigned() -> Result<()> {
+    let schema =
+        SchemaDescriptor::try_from_message("message schema { required int32 u (UINT_32); }")?;
+    let options = WriteOptions {
+        write_statistics: false,
+        version: Version::V1,
+    };
+
+    // 3_000_000_000 is negative as an `i32`
+    let large = 3_000_000_000u32 as i32;
+    let mut encoder =
+        NativeEncoder::<i32>::try_new(schema.columns()[0].descriptor.clone(), Default::default())?;
+    for value in [1, large] {
+        assert!(encoder.push(Some(value))?);
+    }
+    let pages = DynStreamingIterator::new(Compressor::new_from_vec(
+        DynIter::new(encoder.finish()?.into_iter().map(Ok)),
+        CompressionOptions::Uncompressed,
+        vec![],
+    ));
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.write(DynIter::new(std::iter::once(Ok(pages))))?;
+    writer.end(None)?;
+    let mut reader = Cursor::new(writer.into_inner().into_inner());
+    let metadata = read_metadata(&mut reader)?;
+    let chunk = &metadata.row_groups[0].columns()[0];
+
+    // the entries can't be compared as signed integers, but still for equality
+    let predicate = Predicate::gt(&["u"], Value::Int32(5));
+    assert_eq!(matches(&reader, chunk, &predicate)?, vec![0, 1]);
+    let predicate = Predicate::eq(&["u"], Value::Int32(large));
+    assert_eq!(matches(&reader, chunk, &predicate)?, vec![1]);
+    let predicate = Predicate::eq(&["u"], Value::Int32(7));
+    assert_eq!(filter(&reader, chunk, &predicate)?, DictionaryFilter::Skip);
+    Ok(())
+}
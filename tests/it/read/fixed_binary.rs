@@ -1,4 +1,7 @@
-use parquet2::{deserialize::FixedLenBinaryPageState, error::Result, page::DataPage};
+use parquet2::{
+    deserialize::FixedLenBinaryPageState, encoding::byte_stream_split::FixedLenDecoder,
+    error::Result, page::DataPage, FallibleStreamingIterator,
+};
 
 use super::dictionary::FixedLenByteArrayPageDict;
 use super::utils::deserialize_optional;
@@ -28,5 +31,29 @@ pub fn page_to_vec(
                 .map(|x| x.and_then(|x| dict.dict.value(x as usize).map(|x| x.to_vec())));
             deserialize_optional(validity, values)
         }
+        FixedLenBinaryPageState::RequiredByteStreamSplit(values) => {
+            Ok(collect_byte_stream_split(values)?
+                .into_iter()
+                .map(Some)
+                .collect())
+        }
+        FixedLenBinaryPageState::OptionalByteStreamSplit(validity, values) => {
+            let values = collect_byte_stream_split(values)?;
+            deserialize_optional(validity, values.into_iter().map(Ok))
+        }
+        FixedLenBinaryPageState::RequiredDeltaByteArray(values) => {
+            values.map(|x| x.map(Some)).collect()
+        }
+        FixedLenBinaryPageState::OptionalDeltaByteArray(validity, values) => {
+            deserialize_optional(validity, values)
+        }
+    }
+}
+
+fn collect_byte_stream_split(mut values: FixedLenDecoder) -> Result<Vec<Vec<u8>>> {
+    let mut result = Vec::with_capacity(values.len());
+    while let Some(value) = values.next()? {
+        result.push(value.to_vec());
     }
+    Ok(result)
 }
@@ -0,0 +1,117 @@
+use std::io::Cursor;
+
+use futures::TryStreamExt;
+use parquet2::error::Result;
+use parquet2::indexes::{select_pages, Interval};
+use parquet2::page::{CompressedPage, Page};
+use parquet2::read::{
+    dictionary_page_location, get_indexed_page_stream, get_indexed_page_stream_with_budget,
+    read_metadata, read_pages_locations, BasicDecompressor, MemoryBudget,
+};
+use parquet2::FallibleStreamingIterator;
+
+use super::column_reader::{values, write_row_groups};
+use super::{collect, write_indexed_file};
+use crate::Array;
+
+#[tokio::test]
+async fn read_indexed_page_async() -> Result<()> {
+    let data = write_indexed_file()?;
+    let mut reader = Cursor::new(data.clone());
+
+    let metadata = read_metadata(&mut reader)?;
+
+    let column = 0;
+    let columns = &metadata.row_groups[0].columns();
+
+    // selected the rows
+    let intervals = &[Interval::new(2, 2)];
+
+    let pages = read_pages_locations(&mut reader, columns)?;
+
+    let pages = select_pages(intervals, &pages[column], metadata.row_groups[0].num_rows())?;
+    let dictionary_page = dictionary_page_location(columns[column].byte_range().0, &pages);
+
+    let mut reader = futures::io::Cursor::new(data);
+    let pages = get_indexed_page_stream(
+        &mut reader,
+        &columns[column],
+        pages,
+        dictionary_page,
+        vec![],
+        vec![],
+    )
+    .try_collect::<Vec<_>>()
+    .await?;
+    assert_eq!(pages.len(), 1);
+    match &pages[0] {
+        CompressedPage::Data(page) => {
+            assert_eq!(page.selected_rows(), Some([Interval::new(2, 2)].as_ref()))
+        }
+        CompressedPage::Dict(_) => unreachable!(),
+    }
+
+    let pages = BasicDecompressor::new(pages.into_iter().map(Ok), vec![]);
+
+    let arrays = collect(pages, columns[column].physical_type())?;
+
+    // the second item and length 2
+    assert_eq!(arrays, vec![Array::Int32(vec![None, Some(3)])]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_indexed_dictionary_page_async() -> Result<()> {
+    let values = values();
+    let data = write_row_groups(&values)?;
+    let mut reader = Cursor::new(data.clone());
+
+    let metadata = read_metadata(&mut reader)?;
+
+    // the first row group is dictionary-encoded
+    let row_group = &metadata.row_groups[0];
+    let column = &row_group.columns()[0];
+
+    let intervals = &[Interval::new(10, 5)];
+    let pages = read_pages_locations(&mut reader, row_group.columns())?;
+    let pages = select_pages(intervals, &pages[0], row_group.num_rows())?;
+    let dictionary_page = dictionary_page_location(column.byte_range().0, &pages);
+    assert!(dictionary_page.is_some());
+
+    let budget = MemoryBudget::new(column.compressed_size() as usize);
+    let mut reader = futures::io::Cursor::new(data);
+    let pages = get_indexed_page_stream_with_budget(
+        &mut reader,
+        column,
+        pages,
+        dictionary_page,
+        vec![],
+        vec![],
+        budget.clone(),
+    )
+    .try_collect::<Vec<_>>()
+    .await?;
+    assert_eq!(pages.len(), 2);
+    assert!(matches!(pages[0], CompressedPage::Dict(_)));
+    assert!(matches!(pages[1], CompressedPage::Data(_)));
+
+    // the memory of the pages is held until they are dropped
+    assert!(budget.used() > 0);
+    let mut pages = BasicDecompressor::new(pages.into_iter().map(Ok), vec![]);
+    match pages.next()? {
+        Some(Page::Dict(page)) => assert_eq!(page.num_values, 5),
+        _ => unreachable!(),
+    }
+    match pages.next()? {
+        Some(Page::Data(page)) => {
+            assert_eq!(page.selected_rows(), Some(intervals.as_ref()));
+            assert_eq!(page.num_values(), 30);
+        }
+        _ => unreachable!(),
+    }
+    assert!(pages.next()?.is_none());
+    assert_eq!(budget.used(), 0);
+
+    Ok(())
+}
@@ -24,7 +24,7 @@ df = spark.createDataFrame(data, ["id", "string", "bool"]).repartition(1)
 
 df.write.parquet("bla.parquet", mode = "overwrite")
 */
-const FILE: &[u8] = &[
+pub const FILE: &[u8] = &[
     80, 65, 82, 49, 21, 0, 21, 172, 1, 21, 138, 1, 21, 169, 161, 209, 137, 5, 28, 21, 20, 21, 0,
     21, 6, 21, 8, 0, 0, 86, 24, 2, 0, 0, 0, 20, 1, 0, 13, 1, 17, 9, 1, 22, 1, 1, 0, 3, 1, 5, 12, 0,
     0, 0, 4, 1, 5, 12, 0, 0, 0, 5, 1, 5, 12, 0, 0, 0, 6, 1, 5, 12, 0, 0, 0, 7, 1, 5, 72, 0, 0, 0,
@@ -0,0 +1,101 @@
+//! Pages whose levels are encoded with the deprecated `BIT_PACKED` encoding, as written by
+//! old versions of parquet-mr and Impala.
+use parquet2::encoding::Encoding;
+use parquet2::error::Result;
+use parquet2::metadata::Descriptor;
+use parquet2::page::{split_buffer, Version};
+use parquet2::schema::types::{PhysicalType, PrimitiveType};
+
+use super::{data_page, page_to_array, Levels};
+use crate::Array;
+
+fn descriptor(max_rep_level: i16, max_def_level: i16) -> Descriptor {
+    Descriptor {
+        primitive_type: PrimitiveType::from_physical("a".to_string(), PhysicalType::Int32),
+        max_def_level,
+        max_rep_level,
+    }
+}
+
+fn plain(values: &[i32]) -> Vec<u8> {
+    values.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+#[test]
+fn optional() -> Result<()> {
+    // def levels [1, 0, 1, 1, 0, 0, 0, 0, 1], MSB first and not prefixed by their length
+    let page = data_page(
+        descriptor(0, 1),
+        Version::V1,
+        Levels::BitPacked(vec![0b10110000, 0b10000000], 9),
+        Encoding::Plain,
+        plain(&[1, 2, 3, 4]),
+    )?;
+
+    let (rep, def, values) = split_buffer(&page)?;
+    assert!(rep.is_empty());
+    assert_eq!(def, &[0b10110000, 0b10000000]);
+    assert_eq!(values.len(), 16);
+
+    let expected = vec![
+        Some(1),
+        None,
+        Some(2),
+        Some(3),
+        None,
+        None,
+        None,
+        None,
+        Some(4),
+    ];
+    assert_eq!(page_to_array(&page, None)?, Array::Int32(expected));
+    Ok(())
+}
+
+#[test]
+fn optional_levels() -> Result<()> {
+    // def levels [0, 1, 2, 2, 1] with a bit width of 2: 00 01 10 10 | 01 000000
+    let page = data_page(
+        descriptor(0, 2),
+        Version::V1,
+        Levels::BitPacked(vec![0b00011010, 0b01000000], 5),
+        Encoding::Plain,
+        plain(&[10, 20]),
+    )?;
+
+    let expected = vec![None, None, Some(10), Some(20), None];
+    assert_eq!(page_to_array(&page, None)?, Array::Int32(expected));
+    Ok(())
+}
+
+#[test]
+fn split_rep_and_def() -> Result<()> {
+    // 3 values; rep levels with a bit width of 1 ([0, 1, 0]) and def levels with a bit width
+    // of 2 ([3, 3, 1]), each padded to a whole byte.
+    let page = data_page(
+        descriptor(1, 3),
+        Version::V1,
+        Levels::BitPacked(vec![0b01000000, 0b11110100], 3),
+        Encoding::Plain,
+        plain(&[1, 2]),
+    )?;
+
+    let (rep, def, values) = split_buffer(&page)?;
+    assert_eq!(rep, &[0b01000000]);
+    assert_eq!(def, &[0b11110100]);
+    assert_eq!(values, plain(&[1, 2]));
+    Ok(())
+}
+
+#[test]
+fn too_short() -> Result<()> {
+    let page = data_page(
+        descriptor(0, 1),
+        Version::V1,
+        Levels::BitPacked(vec![0b10110000], 9),
+        Encoding::Plain,
+        vec![],
+    )?;
+    assert!(split_buffer(&page).is_err());
+    Ok(())
+}
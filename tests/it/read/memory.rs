@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use parquet2::error::Result;
+use parquet2::page::{Page, SharedBuffer};
+use parquet2::read::{
+    get_page_iterator, read_metadata, BasicDecompressor, Decompressor, MemoryPageReader, ReadAt,
+};
+use parquet2::FallibleStreamingIterator;
+
+use super::{collect, write_indexed_file};
+
+#[test]
+fn read_from_shared_buffer() -> Result<()> {
+    let data = SharedBuffer::new(write_indexed_file()?);
+    let metadata = read_metadata(&mut data.cursor())?;
+    let column = &metadata.row_groups[0].columns()[0];
+
+    // the pages are un-compressed: they share the memory of `data`
+    let pages = MemoryPageReader::new(data.clone(), column, Arc::new(|_, _| true), usize::MAX);
+    let mut pages = Decompressor::new(pages, vec![]);
+    let mut num_pages = 0;
+    while let Some(page) = pages.next()? {
+        match page {
+            Page::Data(page) => assert!(page.is_shared()),
+            Page::Dict(_) => unreachable!(),
+        }
+        num_pages += 1;
+    }
+    assert_eq!(num_pages, 2);
+
+    let pages = MemoryPageReader::new(data.clone(), column, Arc::new(|_, _| true), usize::MAX);
+    let arrays = collect(Decompressor::new(pages, vec![]), column.physical_type())?;
+
+    let pages = get_page_iterator(column, data.cursor(), None, vec![], usize::MAX)?;
+    let expected = collect(
+        BasicDecompressor::new(pages, vec![]),
+        column.physical_type(),
+    )?;
+    assert_eq!(arrays, expected);
+
+    // a buffer truncated within the column chunk errors
+    let truncated = data.slice(0, column.byte_range().0 as usize + 10).unwrap();
+    let mut pages = MemoryPageReader::new(truncated, column, Arc::new(|_, _| true), usize::MAX);
+    assert!(pages.next().unwrap().is_err());
+    Ok(())
+}
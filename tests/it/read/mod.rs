@@ -3,28 +3,50 @@
 /// but OTOH it has no external dependencies and is very familiar to Rust developers.
 mod binary;
 mod boolean;
+mod byte_stream_split;
+mod column_reader;
+mod delta;
 mod deserialize;
 mod dictionary;
+mod dictionary_filter;
 mod fixed_binary;
 mod indexes;
+mod legacy_levels;
+mod memory;
+mod planner;
 mod primitive;
 mod primitive_nested;
+mod prune;
+mod read_at;
+mod record_reader;
 mod struct_;
 mod utils;
 
 #[cfg(any(feature = "lz4", feature = "lz4_flex"))]
 mod lz4_legacy;
 
+#[cfg(feature = "zstd")]
+mod budget;
+
+#[cfg(feature = "async")]
+mod indexed_stream;
+
 use std::fs::File;
+use std::io::Cursor;
 
 #[cfg(feature = "async")]
 use futures::StreamExt;
 
+use parquet2::compression::CompressionOptions;
+use parquet2::encoding::hybrid_rle::encode_bool;
+use parquet2::encoding::Encoding;
 use parquet2::error::Error;
 use parquet2::error::Result;
-use parquet2::metadata::ColumnChunkMetaData;
+use parquet2::metadata::{ColumnChunkMetaData, Descriptor, SchemaDescriptor};
 use parquet2::page::Page;
-use parquet2::page::{CompressedPage, DataPage};
+use parquet2::page::{
+    CompressedPage, DataPage, DataPageHeader, DataPageHeaderV1, DataPageHeaderV2, Version,
+};
 #[cfg(feature = "async")]
 use parquet2::read::get_page_stream;
 #[cfg(feature = "async")]
@@ -38,9 +60,11 @@ use parquet2::schema::types::PhysicalType;
 use parquet2::schema::Repetition;
 use parquet2::statistics::{BinaryStatistics, BooleanStatistics, PrimitiveStatistics, Statistics};
 use parquet2::types::int96_to_i64_ns;
+use parquet2::write::{Compressor, DynIter, DynStreamingIterator, FileWriter, WriteOptions};
 use parquet2::FallibleStreamingIterator;
 
 use super::*;
+use crate::write::array_to_page_v1;
 use dictionary::{deserialize as deserialize_dict, DecodedDictPage};
 
 /// Reads a page into an [`Array`].
@@ -162,6 +186,67 @@ pub fn collect<I: FallibleStreamingIterator<Item = Page, Error = Error>>(
     Ok(arrays)
 }
 
+/// The levels of a page built by [`data_page`]
+pub enum Levels<'a> {
+    /// The validity of the values of a column without repetition, RLE-encoded as definition
+    /// levels unless the column is required
+    Validity(&'a [bool]),
+    /// The repetition and definition levels of `num_values` values, encoded with the deprecated
+    /// `BIT_PACKED` encoding of V1 pages
+    BitPacked(Vec<u8>, usize),
+}
+
+/// Returns an un-compressed data page of `descriptor` whose `values` are encoded with `encoding`.
+/// The RLE-encoded levels of V1 pages are prefixed by their length.
+pub fn data_page(
+    descriptor: Descriptor,
+    version: Version,
+    levels: Levels,
+    encoding: Encoding,
+    values: Vec<u8>,
+) -> Result<DataPage> {
+    let (mut buffer, num_values, num_nulls, levels_encoding) = match levels {
+        Levels::Validity(validity) => {
+            let mut buffer = vec![];
+            if descriptor.max_def_level > 0 {
+                encode_bool(&mut buffer, validity.iter().copied())?;
+            }
+            let num_nulls = validity.iter().filter(|x| !**x).count();
+            (buffer, validity.len(), num_nulls, Encoding::Rle)
+        }
+        Levels::BitPacked(buffer, num_values) => {
+            assert_eq!(version, Version::V1);
+            (buffer, num_values, 0, Encoding::BitPacked)
+        }
+    };
+    let levels_length = buffer.len();
+    if version == Version::V1 && levels_encoding == Encoding::Rle && levels_length > 0 {
+        buffer.splice(0..0, (levels_length as u32).to_le_bytes());
+    }
+    buffer.extend(values);
+
+    let header = match version {
+        Version::V1 => DataPageHeader::V1(DataPageHeaderV1 {
+            num_values: num_values as i32,
+            encoding: encoding.into(),
+            definition_level_encoding: levels_encoding.into(),
+            repetition_level_encoding: levels_encoding.into(),
+            statistics: None,
+        }),
+        Version::V2 => DataPageHeader::V2(DataPageHeaderV2 {
+            num_values: num_values as i32,
+            num_nulls: num_nulls as i32,
+            num_rows: num_values as i32,
+            encoding: encoding.into(),
+            definition_levels_byte_length: levels_length as i32,
+            repetition_levels_byte_length: 0,
+            is_compressed: Some(false),
+            statistics: None,
+        }),
+    };
+    Ok(DataPage::new(header, buffer, descriptor, None))
+}
+
 /// Reads columns into an [`Array`].
 /// This is CPU-intensive: decompress, decode and de-serialize.
 pub fn columns_to_array<II, I>(mut columns: I, field: &ParquetType) -> Result<Array>
@@ -244,6 +329,46 @@ pub fn read_column<R: std::io::Read + std::io::Seek>(
     Ok((array, statistics.pop().unwrap()))
 }
 
+/// Writes a file of one optional `Int32` column of two un-compressed pages with indexes,
+/// `[0, 1, None, 3, 4, 5, 6]` and `[10, 11]`.
+pub fn write_indexed_file() -> Result<Vec<u8>> {
+    let page1 = vec![Some(0), Some(1), None, Some(3), Some(4), Some(5), Some(6)];
+    let page2 = vec![Some(10), Some(11)];
+
+    let options = WriteOptions {
+        write_statistics: true,
+        version: Version::V1,
+    };
+
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col1".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+
+    let pages = vec![
+        array_to_page_v1::<i32>(&page1, &options, &schema.columns()[0].descriptor),
+        array_to_page_v1::<i32>(&page2, &options, &schema.columns()[0].descriptor),
+    ];
+
+    let pages = DynStreamingIterator::new(Compressor::new(
+        DynIter::new(pages.into_iter()),
+        CompressionOptions::Uncompressed,
+        vec![],
+    ));
+    let columns = std::iter::once(Ok(pages));
+
+    let writer = Cursor::new(vec![]);
+    let mut writer = FileWriter::new(writer, schema, options, None);
+
+    writer.write(DynIter::new(columns))?;
+    writer.end(None)?;
+
+    Ok(writer.into_inner().into_inner())
+}
+
 #[cfg(feature = "async")]
 pub async fn read_column_async<
     R: futures::AsyncRead + futures::AsyncSeek + Send + std::marker::Unpin,
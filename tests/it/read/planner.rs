@@ -0,0 +1,100 @@
+use std::io::Cursor;
+
+use parquet2::error::Result;
+use parquet2::indexes::{select_pages, Interval};
+use parquet2::read::{
+    read_columns_indexes, read_metadata, read_pages_locations, BasicDecompressor, CoalesceOptions,
+    IndexedPageReader, RangeFetch, ReadPlanner, SeekFetch,
+};
+
+use super::{collect, write_indexed_file};
+use crate::Array;
+
+/// A [`RangeFetch`] counting the ranges it fetches
+struct CountingFetch {
+    inner: SeekFetch<Cursor<Vec<u8>>>,
+    requests: Vec<(u64, u64)>,
+}
+
+impl RangeFetch for CountingFetch {
+    fn fetch(&mut self, ranges: &[(u64, u64)]) -> Result<Vec<Vec<u8>>> {
+        self.requests.extend_from_slice(ranges);
+        self.inner.fetch(ranges)
+    }
+}
+
+#[test]
+fn read_planned_ranges() -> Result<()> {
+    let data = write_indexed_file()?;
+    let mut reader = Cursor::new(data.clone());
+    let metadata = read_metadata(&mut reader)?;
+    let columns = &metadata.row_groups[0].columns();
+
+    let mut fetcher = CountingFetch {
+        inner: SeekFetch(Cursor::new(data)),
+        requests: vec![],
+    };
+
+    // the indexes are next to each other: a single request
+    let mut planner = ReadPlanner::default();
+    planner
+        .add_column_index(&columns[0])
+        .add_offset_index(&columns[0]);
+    let ranges = planner.fetch(&mut fetcher)?;
+    assert_eq!(fetcher.requests.len(), 1);
+
+    let mut ranges_reader = ranges.reader();
+    let pages = read_pages_locations(&mut ranges_reader, columns)?;
+    let indexes = read_columns_indexes(&mut ranges_reader, columns)?;
+    assert_eq!(pages, read_pages_locations(&mut reader, columns)?);
+    assert_eq!(indexes, read_columns_indexes(&mut reader, columns)?);
+
+    // only the second page is selected
+    let pages = select_pages(
+        &[Interval::new(7, 2)],
+        &pages[0],
+        metadata.row_groups[0].num_rows(),
+    )?;
+    let options = CoalesceOptions {
+        max_gap: 0,
+        max_size: u64::MAX,
+    };
+    let mut planner = ReadPlanner::new(options);
+    planner.add_filtered_pages(&columns[0], &pages);
+    fetcher.requests.clear();
+    let ranges = planner.fetch(&mut fetcher)?;
+    assert_eq!(fetcher.requests, vec![(67, 47)]);
+
+    let pages = IndexedPageReader::new(ranges.reader(), &columns[0], pages, vec![], vec![]);
+    let arrays = collect(
+        BasicDecompressor::new(pages, vec![]),
+        columns[0].physical_type(),
+    )?;
+    assert_eq!(arrays, vec![Array::Int32(vec![Some(10), Some(11)])]);
+
+    // the whole column chunk
+    let mut planner = ReadPlanner::new(options);
+    planner.add_column(&columns[0]);
+    let ranges = planner.fetch(&mut fetcher)?;
+    let pages = ranges.get_page_iterator(&columns[0], None, vec![], usize::MAX)?;
+    let arrays = collect(
+        BasicDecompressor::new(pages, vec![]),
+        columns[0].physical_type(),
+    )?;
+    assert_eq!(
+        arrays,
+        vec![
+            Array::Int32(vec![
+                Some(0),
+                Some(1),
+                None,
+                Some(3),
+                Some(4),
+                Some(5),
+                Some(6)
+            ]),
+            Array::Int32(vec![Some(10), Some(11)]),
+        ]
+    );
+    Ok(())
+}
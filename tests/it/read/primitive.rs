@@ -1,7 +1,7 @@
 use parquet2::{
     deserialize::{
-        native_cast, Casted, HybridRleDecoderIter, HybridRleIter, NativePageState, OptionalValues,
-        SliceFilteredIter,
+        native_cast, Casted, DeltaBinaryPackedIter, HybridRleDecoderIter, HybridRleIter,
+        NativePageState, OptionalValues, SliceFilteredIter,
     },
     encoding::{hybrid_rle::Decoder, Encoding},
     error::Error,
@@ -22,6 +22,18 @@ where
     Optional(SliceFilteredIter<OptionalValues<T, HybridRleDecoderIter<'a>, Casted<'a, T>>>),
     /// A page of required values
     Required(SliceFilteredIter<Casted<'a, T>>),
+    /// A page of optional, `DELTA_BINARY_PACKED`-encoded values
+    OptionalDeltaBinaryPacked(
+        SliceFilteredIter<
+            OptionalValues<
+                Result<T, Error>,
+                HybridRleDecoderIter<'a>,
+                DeltaBinaryPackedIter<'a, T>,
+            >,
+        >,
+    ),
+    /// A page of required, `DELTA_BINARY_PACKED`-encoded values
+    RequiredDeltaBinaryPacked(SliceFilteredIter<DeltaBinaryPackedIter<'a, T>>),
 }
 
 /// The deserialization state of a `DataPage` of `Primitive` parquet primitive type
@@ -72,6 +84,34 @@ impl<'a, T: NativeType> PageState<'a, T> {
                     );
                     Ok(Self::Filtered(FilteredPageState::Required(values)))
                 }
+                (Encoding::DeltaBinaryPacked, _, true) => {
+                    let (_, def_levels, values) = split_buffer(page)?;
+
+                    let validity = HybridRleDecoderIter::new(HybridRleIter::new(
+                        Decoder::new(def_levels, 1),
+                        page.num_values(),
+                    ));
+                    let values = DeltaBinaryPackedIter::try_new(values)?;
+
+                    let values = OptionalValues::new(validity, values);
+                    let values =
+                        SliceFilteredIter::new(values, selected_rows.iter().copied().collect());
+
+                    Ok(Self::Filtered(
+                        FilteredPageState::OptionalDeltaBinaryPacked(values),
+                    ))
+                }
+                (Encoding::DeltaBinaryPacked, _, false) => {
+                    let (_, _, values) = split_buffer(page)?;
+
+                    let values = SliceFilteredIter::new(
+                        DeltaBinaryPackedIter::try_new(values)?,
+                        selected_rows.iter().copied().collect(),
+                    );
+                    Ok(Self::Filtered(
+                        FilteredPageState::RequiredDeltaBinaryPacked(values),
+                    ))
+                }
                 _ => Err(Error::FeatureNotSupported(format!(
                     "Viewing page for encoding {:?} for native type {}",
                     page.encoding(),
@@ -107,10 +147,26 @@ pub fn page_to_vec<T: NativeType>(
                     .map(|x| x.and_then(|x| dict.dict.value(x as usize).copied()));
                 deserialize_optional(validity, values)
             }
+            NativePageState::RequiredByteStreamSplit(values) => Ok(values.map(Some).collect()),
+            NativePageState::OptionalByteStreamSplit(validity, mut values) => {
+                deserialize_optional(validity, values.by_ref().map(Ok))
+            }
+            NativePageState::RequiredDeltaBinaryPacked(values) => {
+                values.map(|x| x.map(Some)).collect()
+            }
+            NativePageState::OptionalDeltaBinaryPacked(validity, values) => {
+                deserialize_optional(validity, values)
+            }
         },
         PageState::Filtered(state) => match state {
             FilteredPageState::Optional(values) => values.collect(),
             FilteredPageState::Required(values) => Ok(values.map(Some).collect()),
+            FilteredPageState::OptionalDeltaBinaryPacked(values) => {
+                values.map(|x| x.and_then(|x| x.transpose())).collect()
+            }
+            FilteredPageState::RequiredDeltaBinaryPacked(values) => {
+                values.map(|x| x.map(Some)).collect()
+            }
         },
     }
 }
@@ -0,0 +1,132 @@
+use std::io::Cursor;
+
+use parquet2::compression::CompressionOptions;
+use parquet2::error::Result;
+use parquet2::indexes::Interval;
+use parquet2::metadata::SchemaDescriptor;
+use parquet2::read::{prune, read_metadata, Predicate, RowGroupIndexes, RowGroupSelection, Value};
+use parquet2::write::{
+    Compressor, DynIter, DynStreamingIterator, FileWriter, Version, WriteOptions,
+};
+
+use super::write_indexed_file;
+use crate::write::array_to_page_v1;
+
+#[test]
+fn prune_with_indexes() -> Result<()> {
+    let data = write_indexed_file()?;
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let row_groups = &metadata.row_groups;
+
+    // the pages have the rows [0, 7) and [7, 9)
+    let selected = |predicate: &Predicate, reader: &mut Cursor<Vec<u8>>| {
+        prune(reader, row_groups, predicate).map(|selected| {
+            selected
+                .into_iter()
+                .map(|RowGroupSelection { row_group, rows }| {
+                    assert_eq!(row_group, 0);
+                    rows
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+    let int = Value::Int32;
+    let column = &["col1"];
+
+    let predicate = Predicate::eq(column, int(11));
+    assert_eq!(
+        selected(&predicate, &mut reader)?,
+        vec![vec![Interval::new(7, 2)]]
+    );
+
+    // pruned by statistics
+    let predicate = Predicate::lt(column, int(0));
+    assert_eq!(selected(&predicate, &mut reader)?, Vec::<Vec<_>>::new());
+
+    let predicate = Predicate::is_null(column);
+    assert_eq!(
+        selected(&predicate, &mut reader)?,
+        vec![vec![Interval::new(0, 7)]]
+    );
+
+    let predicate = Predicate::gt_eq(column, int(5)).and(Predicate::lt(column, int(10)));
+    assert_eq!(
+        selected(&predicate, &mut reader)?,
+        vec![vec![Interval::new(0, 7)]]
+    );
+
+    let predicate = Predicate::is_in(column, vec![int(2), int(10)]).or(Predicate::is_null(column));
+    assert_eq!(
+        selected(&predicate, &mut reader)?,
+        vec![vec![Interval::new(0, 9)]]
+    );
+
+    // the second page only has values in [10, 11]
+    let predicate = !Predicate::between(column, int(10), int(11));
+    assert_eq!(
+        selected(&predicate, &mut reader)?,
+        vec![vec![Interval::new(0, 7)]]
+    );
+
+    // without indexes, only statistics are used
+    let predicate = Predicate::eq(column, int(11));
+    assert_eq!(
+        predicate.select_rows(&row_groups[0], &RowGroupIndexes::default())?,
+        vec![Interval::new(0, 9)]
+    );
+
+    // no value is equal to null
+    let predicate = Predicate::eq(column, Value::Null);
+    assert_eq!(selected(&predicate, &mut reader)?, Vec::<Vec<_>>::new());
+    assert_eq!(
+        selected(&!predicate, &mut reader)?,
+        vec![vec![Interval::new(0, 7), Interval::new(7, 2)]]
+    );
+    let predicate = Predicate::is_in(column, vec![int(11), Value::Null]);
+    assert_eq!(
+        selected(&predicate, &mut reader)?,
+        vec![vec![Interval::new(7, 2)]]
+    );
+    let predicate = Predicate::between(column, Value::Null, int(11));
+    assert_eq!(selected(&predicate, &mut reader)?, Vec::<Vec<_>>::new());
+
+    assert!(selected(&Predicate::eq(column, Value::Int64(1)), &mut reader).is_err());
+    assert!(selected(&Predicate::eq(&["col2"], int(1)), &mut reader).is_err());
+    Ok(())
+}
+
+#[test]
+fn prune_unsigned() -> Result<()> {
+    let options = WriteOptions {
+        write_statistics: true,
+        version: Version::V1,
+    };
+    let schema =
+        SchemaDescriptor::try_from_message("message schema { optional int32 u (UINT_32); }")?;
+
+    // 3_000_000_000 is negative as an `i32`, so the maximum is 1 as a signed integer
+    let values = vec![Some(3_000_000_000u32 as i32), Some(1)];
+    let page = array_to_page_v1::<i32>(&values, &options, &schema.columns()[0].descriptor);
+    let pages = DynStreamingIterator::new(Compressor::new(
+        DynIter::new(std::iter::once(page)),
+        CompressionOptions::Uncompressed,
+        vec![],
+    ));
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.write(DynIter::new(std::iter::once(Ok(pages))))?;
+    writer.end(None)?;
+    let mut reader = Cursor::new(writer.into_inner().into_inner());
+    let metadata = read_metadata(&mut reader)?;
+
+    let predicate = Predicate::gt(&["u"], Value::Int32(5));
+    assert_eq!(
+        prune(&mut reader, &metadata.row_groups, &predicate)?,
+        vec![RowGroupSelection {
+            row_group: 0,
+            rows: vec![Interval::new(0, 2)],
+        }]
+    );
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use parquet2::error::Result;
+use parquet2::read::{
+    get_page_iterator, read_columns_indexes, read_metadata, BasicDecompressor, ReadAt,
+};
+
+use super::collect;
+use super::indexes::FILE;
+use crate::Array;
+
+#[test]
+fn read_at_shared() -> Result<()> {
+    let data = Arc::new(FILE.to_vec());
+    let metadata = read_metadata(&mut data.cursor())?;
+    let column = &metadata.row_groups[0].columns()[0];
+
+    // every thread reads the same buffer through its own cursor
+    let results = std::thread::scope(|scope| {
+        let handles = (0..4)
+            .map(|_| {
+                scope.spawn(|| {
+                    let indexes =
+                        read_columns_indexes(&mut data.cursor(), std::slice::from_ref(column))?;
+
+                    #[cfg(feature = "bloom_filter")]
+                    {
+                        use parquet2::bloom_filter::{hash_native, is_in_set, read};
+
+                        let mut bitset = vec![];
+                        read(column, &mut data.cursor(), &mut bitset)?;
+                        assert!((0..10i64).all(|x| is_in_set(&bitset, hash_native(x))));
+                        assert!(!is_in_set(&bitset, hash_native(10i64)));
+                    }
+
+                    let pages = get_page_iterator(column, data.cursor(), None, vec![], usize::MAX)?;
+                    let arrays = collect(
+                        BasicDecompressor::new(pages, vec![]),
+                        column.physical_type(),
+                    )?;
+                    Result::Ok((indexes, arrays))
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Result<Vec<_>>>()
+    })?;
+
+    let mut reader = Cursor::new(data.as_slice());
+    let expected_indexes = read_columns_indexes(&mut reader, std::slice::from_ref(column))?;
+    let expected_arrays = vec![Array::Int64((0..10).map(Some).collect())];
+    for (indexes, arrays) in results {
+        assert_eq!(indexes, expected_indexes);
+        assert_eq!(arrays, expected_arrays);
+    }
+    Ok(())
+}
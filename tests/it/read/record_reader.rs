@@ -0,0 +1,175 @@
+use std::io::Cursor;
+
+use parquet2::compression::CompressionOptions;
+use parquet2::encoding::{get_bit_width, hybrid_rle::encode_u32, Encoding};
+use parquet2::error::Result;
+use parquet2::metadata::{Descriptor, SchemaDescriptor};
+use parquet2::page::{DataPage, DataPageHeader, DataPageHeaderV1, Page};
+use parquet2::read::{get_record_reader, read_metadata, Row, Value};
+use parquet2::write::{
+    Compressor, DynIter, DynStreamingIterator, FileWriter, Version, WriteOptions,
+};
+
+use super::column_reader::{values, write_row_groups};
+
+#[test]
+fn record_reader() -> Result<()> {
+    let values = values();
+    let mut reader = Cursor::new(write_row_groups(&values)?);
+
+    let metadata = read_metadata(&mut reader)?;
+    let rows = get_record_reader(
+        reader,
+        metadata.schema(),
+        &metadata.row_groups,
+        &[0],
+        1024,
+        7,
+    )?
+    .collect::<Result<Vec<_>>>()?;
+
+    let expected = values
+        .iter()
+        .map(|value| Row {
+            fields: vec![(
+                "col".to_string(),
+                value.map(Value::Int32).unwrap_or(Value::Null),
+            )],
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(rows, expected);
+    Ok(())
+}
+
+/// Returns a V1 data page of `num_rows` rows with the levels `rep_levels` and `def_levels` and
+/// the PLAIN-encoded `values`
+fn nested_page(
+    descriptor: &Descriptor,
+    num_rows: usize,
+    rep_levels: &[u32],
+    def_levels: &[u32],
+    values: Vec<u8>,
+) -> Result<Page> {
+    let mut buffer = vec![];
+    for (levels, max_level) in [
+        (rep_levels, descriptor.max_rep_level),
+        (def_levels, descriptor.max_def_level),
+    ] {
+        let mut encoded = vec![];
+        encode_u32(
+            &mut encoded,
+            levels.iter().copied(),
+            get_bit_width(max_level),
+        )?;
+        buffer.extend_from_slice(&(encoded.len() as u32).to_le_bytes());
+        buffer.extend(encoded);
+    }
+    buffer.extend(values);
+
+    let header = DataPageHeaderV1 {
+        num_values: def_levels.len() as i32,
+        encoding: Encoding::Plain.into(),
+        definition_level_encoding: Encoding::Rle.into(),
+        repetition_level_encoding: Encoding::Rle.into(),
+        statistics: None,
+    };
+    Ok(Page::Data(DataPage::new(
+        DataPageHeader::V1(header),
+        buffer,
+        descriptor.clone(),
+        Some(num_rows),
+    )))
+}
+
+#[test]
+fn nested_record_reader() -> Result<()> {
+    let schema = SchemaDescriptor::try_from_message(
+        "
+        message schema {
+            optional group list (LIST) {
+                repeated group list {
+                    optional int32 element;
+                }
+            }
+            optional group map (MAP) {
+                repeated group key_value {
+                    required binary key;
+                    optional int64 value;
+                }
+            }
+        }
+        ",
+    )?;
+    let descriptor = |column: usize| &schema.columns()[column].descriptor;
+
+    // list: [1, null, 2], null, [], [3]
+    // map: {a: 10, b: null}, {}, null, {c: 30}
+    let elements = [1i32, 2, 3].iter().flat_map(|x| x.to_le_bytes()).collect();
+    let keys = ["a", "b", "c"]
+        .iter()
+        .flat_map(|x| [&(x.len() as u32).to_le_bytes()[..], x.as_bytes()].concat())
+        .collect();
+    let values = [10i64, 30].iter().flat_map(|x| x.to_le_bytes()).collect();
+    let pages = vec![
+        nested_page(
+            descriptor(0),
+            4,
+            &[0, 1, 1, 0, 0, 0],
+            &[3, 2, 3, 0, 1, 3],
+            elements,
+        )?,
+        nested_page(descriptor(1), 4, &[0, 1, 0, 0, 0], &[2, 2, 1, 0, 2], keys)?,
+        nested_page(descriptor(2), 4, &[0, 1, 0, 0, 0], &[3, 2, 1, 0, 3], values)?,
+    ];
+
+    let options = WriteOptions {
+        write_statistics: false,
+        version: Version::V1,
+    };
+    let columns = pages.into_iter().map(|page| {
+        Ok(DynStreamingIterator::new(Compressor::new_from_vec(
+            DynIter::new(std::iter::once(Ok(page))),
+            CompressionOptions::Uncompressed,
+            vec![],
+        )))
+    });
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema.clone(), options, None);
+    writer.write(DynIter::new(columns))?;
+    writer.end(None)?;
+    let mut reader = Cursor::new(writer.into_inner().into_inner());
+
+    let metadata = read_metadata(&mut reader)?;
+    let rows = get_record_reader(
+        reader,
+        metadata.schema(),
+        &metadata.row_groups,
+        &[0, 1],
+        1024,
+        3,
+    )?
+    .collect::<Result<Vec<_>>>()?;
+
+    let row = |list, map| Row {
+        fields: vec![("list".to_string(), list), ("map".to_string(), map)],
+    };
+    let bytes = |x: &str| Value::Bytes(x.as_bytes().to_vec());
+    assert_eq!(
+        rows,
+        vec![
+            row(
+                Value::List(vec![Value::Int32(1), Value::Null, Value::Int32(2)]),
+                Value::Map(vec![
+                    (bytes("a"), Value::Int64(10)),
+                    (bytes("b"), Value::Null)
+                ]),
+            ),
+            row(Value::Null, Value::Map(vec![])),
+            row(Value::List(vec![]), Value::Null),
+            row(
+                Value::List(vec![Value::Int32(3)]),
+                Value::Map(vec![(bytes("c"), Value::Int64(30))]),
+            ),
+        ]
+    );
+    Ok(())
+}
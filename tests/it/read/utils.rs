@@ -1,6 +1,6 @@
 use parquet2::{
     deserialize::{DefLevelsDecoder, HybridDecoderBitmapIter, HybridEncoded},
-    encoding::hybrid_rle::{BitmapIter, HybridRleDecoder},
+    encoding::hybrid_rle::BitmapIter,
     error::Error,
 };
 
@@ -13,6 +13,9 @@ pub fn deserialize_optional<C: Clone, I: Iterator<Item = Result<C, Error>>>(
         DefLevelsDecoder::Levels(levels, max_level) => {
             deserialize_levels(levels, max_level, values)
         }
+        DefLevelsDecoder::BitPacked(levels, max_level) => {
+            deserialize_levels(levels.map(Ok), max_level, values)
+        }
     }
 }
 
@@ -48,13 +51,16 @@ fn deserialize_bitmap<C: Clone, I: Iterator<Item = Result<C, Error>>>(
     Ok(deserialized)
 }
 
-fn deserialize_levels<C: Clone, I: Iterator<Item = Result<C, Error>>>(
-    levels: HybridRleDecoder,
+fn deserialize_levels<
+    C: Clone,
+    L: Iterator<Item = Result<u32, Error>>,
+    I: Iterator<Item = Result<C, Error>>,
+>(
+    levels: L,
     max: u32,
     mut values: I,
 ) -> Result<Vec<Option<C>>, Error> {
     levels
-        .into_iter()
         .map(|x| {
             if x? == max {
                 values.next().transpose()
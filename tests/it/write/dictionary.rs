@@ -0,0 +1,162 @@
+use std::io::Cursor;
+
+use parquet2::compression::CompressionOptions;
+use parquet2::encoding::dictionary::{BinaryEncoder, DictionaryOptions, NativeEncoder};
+use parquet2::error::Result;
+use parquet2::metadata::SchemaDescriptor;
+use parquet2::page::Page;
+use parquet2::read::read_metadata;
+use parquet2::schema::types::{ParquetType, PhysicalType};
+use parquet2::write::{
+    Compressor, DynIter, DynStreamingIterator, FileWriter, Version, WriteOptions,
+};
+
+use parquet_format_safe::{Encoding, PageEncodingStats, PageType};
+
+use super::primitive::array_to_page_v1;
+use super::read_column;
+use crate::Array;
+
+fn write_pages(physical_type: PhysicalType, pages: Vec<Page>, version: Version) -> Result<Vec<u8>> {
+    let options = WriteOptions {
+        write_statistics: false,
+        version,
+    };
+
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical("col".to_string(), physical_type)],
+    );
+
+    let pages = DynStreamingIterator::new(Compressor::new_from_vec(
+        DynIter::new(pages.into_iter().map(Ok)),
+        CompressionOptions::Snappy,
+        vec![],
+    ));
+    let columns = std::iter::once(Ok(pages));
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.write(DynIter::new(columns))?;
+    writer.end(None)?;
+    Ok(writer.into_inner().into_inner())
+}
+
+fn options(version: Version) -> DictionaryOptions {
+    // the values of these tests fit in a single data page (see `max_page_values`), since the
+    // reader of these tests only returns the last data page
+    DictionaryOptions {
+        version,
+        ..Default::default()
+    }
+}
+
+fn int32(version: Version) -> Result<()> {
+    let values = (0..50)
+        .map(|x| (x % 3 != 0).then_some(x % 5))
+        .collect::<Vec<_>>();
+
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+    let descriptor = schema.columns()[0].descriptor.clone();
+
+    let mut encoder = NativeEncoder::<i32>::try_new(descriptor, options(version))?;
+    for value in &values {
+        assert!(encoder.push(*value)?);
+    }
+    let data = write_pages(PhysicalType::Int32, encoder.finish()?, version)?;
+
+    // the column chunk locates its dictionary page and counts its pages by encoding
+    let metadata = read_metadata(&mut Cursor::new(&data))?;
+    let column = &metadata.row_groups[0].columns()[0];
+    let data_page = match version {
+        Version::V1 => PageType::DATA_PAGE,
+        Version::V2 => PageType::DATA_PAGE_V2,
+    };
+    // the dictionary page follows the magic number
+    assert_eq!(column.dictionary_page_offset(), Some(4));
+    assert!(column.data_page_offset() > 4);
+    assert_eq!(
+        column.metadata().encoding_stats,
+        Some(vec![
+            PageEncodingStats::new(PageType::DICTIONARY_PAGE, Encoding::PLAIN, 1),
+            PageEncodingStats::new(data_page, Encoding::RLE_DICTIONARY, 1),
+        ])
+    );
+
+    let (result, _) = read_column(&mut Cursor::new(data))?;
+    assert_eq!(result, Array::Int32(values));
+    Ok(())
+}
+
+#[test]
+fn int32_v1() -> Result<()> {
+    int32(Version::V1)
+}
+
+#[test]
+fn int32_v2() -> Result<()> {
+    int32(Version::V2)
+}
+
+#[test]
+fn plain_column_chunk() -> Result<()> {
+    let values = vec![Some(1), None, Some(3)];
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+    let options = WriteOptions {
+        write_statistics: false,
+        version: Version::V1,
+    };
+    let page = array_to_page_v1(&values, &options, &schema.columns()[0].descriptor)?;
+    let data = write_pages(PhysicalType::Int32, vec![page], Version::V1)?;
+
+    let metadata = read_metadata(&mut Cursor::new(data))?;
+    let column = &metadata.row_groups[0].columns()[0];
+    assert_eq!(column.dictionary_page_offset(), None);
+    assert_eq!(column.data_page_offset(), 4);
+    assert_eq!(
+        column.metadata().encoding_stats,
+        Some(vec![PageEncodingStats::new(
+            PageType::DATA_PAGE,
+            Encoding::PLAIN,
+            1
+        )])
+    );
+    Ok(())
+}
+
+#[test]
+fn binary() -> Result<()> {
+    let values = (0..30)
+        .map(|x| (x % 4 != 1).then(|| format!("value {}", x % 6).into_bytes()))
+        .collect::<Vec<_>>();
+
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::ByteArray,
+        )],
+    );
+    let descriptor = schema.columns()[0].descriptor.clone();
+
+    let mut encoder = BinaryEncoder::try_new(descriptor, options(Version::V1))?;
+    for value in &values {
+        assert!(encoder.push(value.as_deref())?);
+    }
+    let data = write_pages(PhysicalType::ByteArray, encoder.finish()?, Version::V1)?;
+
+    let (result, _) = read_column(&mut Cursor::new(data))?;
+    assert_eq!(result, Array::Binary(values));
+    Ok(())
+}
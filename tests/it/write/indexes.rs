@@ -1,64 +1,20 @@
 use std::io::Cursor;
 
-use parquet2::compression::CompressionOptions;
 use parquet2::error::Result;
 use parquet2::indexes::{
     select_pages, BoundaryOrder, Index, Interval, NativeIndex, PageIndex, PageLocation,
 };
-use parquet2::metadata::SchemaDescriptor;
 use parquet2::read::{
     read_columns_indexes, read_metadata, read_pages_locations, BasicDecompressor, IndexedPageReader,
 };
-use parquet2::schema::types::{ParquetType, PhysicalType, PrimitiveType};
-use parquet2::write::WriteOptions;
-use parquet2::write::{Compressor, DynIter, DynStreamingIterator, FileWriter, Version};
+use parquet2::schema::types::{PhysicalType, PrimitiveType};
 
-use crate::read::collect;
+use crate::read::{collect, write_indexed_file};
 use crate::Array;
 
-use super::primitive::array_to_page_v1;
-
-fn write_file() -> Result<Vec<u8>> {
-    let page1 = vec![Some(0), Some(1), None, Some(3), Some(4), Some(5), Some(6)];
-    let page2 = vec![Some(10), Some(11)];
-
-    let options = WriteOptions {
-        write_statistics: true,
-        version: Version::V1,
-    };
-
-    let schema = SchemaDescriptor::new(
-        "schema".to_string(),
-        vec![ParquetType::from_physical(
-            "col1".to_string(),
-            PhysicalType::Int32,
-        )],
-    );
-
-    let pages = vec![
-        array_to_page_v1::<i32>(&page1, &options, &schema.columns()[0].descriptor),
-        array_to_page_v1::<i32>(&page2, &options, &schema.columns()[0].descriptor),
-    ];
-
-    let pages = DynStreamingIterator::new(Compressor::new(
-        DynIter::new(pages.into_iter()),
-        CompressionOptions::Uncompressed,
-        vec![],
-    ));
-    let columns = std::iter::once(Ok(pages));
-
-    let writer = Cursor::new(vec![]);
-    let mut writer = FileWriter::new(writer, schema, options, None);
-
-    writer.write(DynIter::new(columns))?;
-    writer.end(None)?;
-
-    Ok(writer.into_inner().into_inner())
-}
-
 #[test]
 fn read_indexed_page() -> Result<()> {
-    let data = write_file()?;
+    let data = write_indexed_file()?;
     let mut reader = Cursor::new(data);
 
     let metadata = read_metadata(&mut reader)?;
@@ -87,7 +43,7 @@ fn read_indexed_page() -> Result<()> {
 
 #[test]
 fn read_indexes_and_locations() -> Result<()> {
-    let data = write_file()?;
+    let data = write_indexed_file()?;
     let mut reader = Cursor::new(data);
 
     let metadata = read_metadata(&mut reader)?;
@@ -1,4 +1,5 @@
 mod binary;
+mod dictionary;
 mod indexes;
 mod primitive;
 mod sidecar;
@@ -6,20 +7,30 @@ mod sidecar;
 use std::io::{Cursor, Read, Seek};
 use std::sync::Arc;
 
-use parquet2::compression::{BrotliLevel, CompressionOptions};
-use parquet2::error::Result;
-use parquet2::metadata::SchemaDescriptor;
-use parquet2::read::read_metadata;
+use parquet2::compression::{
+    BrotliLevel, Codec, CodecRegistry, Compression, CompressionOptions, ZstdDictionary,
+    ZSTD_DICTIONARY_KEY,
+};
+use parquet2::error::{Error, Result};
+use parquet2::metadata::{ColumnChunkMetaData, SchemaDescriptor};
+use parquet2::page::{CompressedPage, DataPage, DataPageHeader, DataPageHeaderV2, DictPage};
+use parquet2::read::{
+    decompress, get_page_iterator, read_metadata, BasicDecompressor, Decompressor,
+};
 use parquet2::schema::types::{ParquetType, PhysicalType};
 use parquet2::statistics::Statistics;
 #[cfg(feature = "async")]
 use parquet2::write::FileStreamer;
-use parquet2::write::{Compressor, DynIter, DynStreamingIterator, FileWriter, Version};
+use parquet2::write::{
+    AdaptiveCompression, CompressionPool, Compressor, DynIter, DynStreamingIterator, FileWriter,
+    ParallelColumnCompressor, ParallelCompressor, Version,
+};
+use parquet2::FallibleStreamingIterator;
 use parquet2::{metadata::Descriptor, page::Page, write::WriteOptions};
 
 use super::Array;
 use super::{alltypes_plain, alltypes_statistics};
-use primitive::array_to_page_v1;
+pub use primitive::array_to_page_v1;
 
 pub fn array_to_page(
     array: &Array,
@@ -234,6 +245,521 @@ fn basic() -> Result<()> {
     Ok(())
 }
 
+/// A codec that xors every byte, to check that registered codecs are used
+#[derive(Debug)]
+struct XorCodec;
+
+impl Codec for XorCodec {
+    fn compress(&self, input: &[u8], output: &mut Vec<u8>) -> Result<()> {
+        output.extend(input.iter().map(|x| x ^ 0xa5));
+        Ok(())
+    }
+
+    fn decompress(&self, input: &[u8], output: &mut [u8]) -> Result<()> {
+        if input.len() != output.len() {
+            return Err(Error::OutOfSpec("unexpected decompressed size".to_string()));
+        }
+        output
+            .iter_mut()
+            .zip(input)
+            .for_each(|(out, x)| *out = x ^ 0xa5);
+        Ok(())
+    }
+
+    fn max_compressed_len(&self, input_length: usize) -> Result<usize> {
+        Ok(input_length)
+    }
+}
+
+#[test]
+fn custom_codec() -> Result<()> {
+    let array = vec![Some(0), Some(1), None, Some(3), Some(4)];
+
+    let options = WriteOptions {
+        write_statistics: false,
+        version: Version::V1,
+    };
+
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+    let page = array_to_page_v1(&array, &options, &schema.columns()[0].descriptor)?;
+    let expected = match &page {
+        Page::Data(page) => page.buffer().to_vec(),
+        Page::Dict(_) => unreachable!(),
+    };
+
+    let mut registry = CodecRegistry::new();
+    assert!(registry
+        .register(Compression::Snappy, Arc::new(XorCodec))
+        .is_none());
+
+    let pages = DynStreamingIterator::new(
+        Compressor::new_from_vec(
+            DynIter::new(std::iter::once(Ok(page))),
+            CompressionOptions::Snappy,
+            vec![],
+        )
+        .with_registry(registry.clone()),
+    );
+    let columns = std::iter::once(Ok(pages));
+
+    let writer = Cursor::new(vec![]);
+    let mut writer = FileWriter::new(writer, schema, options, None);
+
+    writer.write(DynIter::new(columns))?;
+    writer.end(None)?;
+
+    let data = writer.into_inner().into_inner();
+    let mut reader = Cursor::new(data);
+
+    let metadata = read_metadata(&mut reader)?;
+    let column_metadata = &metadata.row_groups[0].columns()[0];
+    assert_eq!(column_metadata.compression(), Compression::Snappy);
+
+    let pages = get_page_iterator(column_metadata, &mut reader, None, vec![], usize::MAX)?;
+    let mut pages = Decompressor::new(pages, vec![]).with_registry(registry.clone());
+    match pages.next()? {
+        Some(Page::Data(page)) => assert_eq!(page.buffer(), expected),
+        _ => unreachable!(),
+    }
+    assert!(pages.next()?.is_none());
+
+    let pages = get_page_iterator(column_metadata, &mut reader, None, vec![], usize::MAX)?;
+    let mut pages = BasicDecompressor::new(pages, vec![]).with_registry(registry);
+    match pages.next()? {
+        Some(Page::Data(page)) => assert_eq!(page.buffer(), expected),
+        _ => unreachable!(),
+    }
+    assert!(pages.next()?.is_none());
+    Ok(())
+}
+
+#[cfg(feature = "zstd")]
+fn write_json_pages(dictionary: Option<ZstdDictionary>) -> Result<(Vec<u8>, Vec<Vec<u8>>)> {
+    let options = WriteOptions {
+        write_statistics: false,
+        version: Version::V1,
+    };
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::ByteArray,
+        )],
+    );
+    let pages = (0..50)
+        .map(|page| {
+            let array = (0..10)
+                .map(|i| {
+                    let json = format!(
+                        r#"{{"id": {}, "kind": "event", "source": "sensor-{}", "ok": true}}"#,
+                        page * 10 + i,
+                        i % 3
+                    );
+                    Some(json.into_bytes())
+                })
+                .collect::<Vec<_>>();
+            binary::array_to_page_v1(&array, &options, &schema.columns()[0].descriptor)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let buffers = pages
+        .iter()
+        .map(|page| match page {
+            Page::Data(page) => page.buffer().to_vec(),
+            Page::Dict(_) => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+
+    let compressor = Compressor::new(
+        DynIter::new(pages.into_iter().map(Ok)),
+        CompressionOptions::Zstd(None),
+        vec![],
+    );
+    let compressor = match dictionary {
+        Some(dictionary) => compressor.with_zstd_dictionary(dictionary),
+        None => compressor,
+    };
+    let columns = std::iter::once(Ok(DynStreamingIterator::new(compressor)));
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.write(DynIter::new(columns))?;
+    writer.end(None)?;
+    Ok((writer.into_inner().into_inner(), buffers))
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn zstd_dictionary() -> Result<()> {
+    let (data, expected) = write_json_pages(None)?;
+    let metadata = read_metadata(&mut Cursor::new(&data))?;
+    let size_without_dictionary = metadata.row_groups[0].columns()[0].compressed_size();
+
+    let samples = expected
+        .iter()
+        .map(|buffer| Page::Dict(DictPage::new(buffer.clone(), 10, false)))
+        .collect::<Vec<_>>();
+    let dictionary = ZstdDictionary::train(&samples, 1024)?;
+
+    let (data, expected) = write_json_pages(Some(dictionary.clone()))?;
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let column_metadata = &metadata.row_groups[0].columns()[0];
+    assert!(column_metadata.compressed_size() < size_without_dictionary);
+    assert!(column_metadata
+        .metadata()
+        .key_value_metadata
+        .as_ref()
+        .unwrap()
+        .iter()
+        .any(|kv| kv.key == ZSTD_DICTIONARY_KEY));
+
+    // the dictionary is loaded back to decompress the pages
+    let pages = get_page_iterator(column_metadata, &mut reader, None, vec![], usize::MAX)?;
+    let mut buffer = vec![];
+    let mut num_pages = 0;
+    for (page, expected) in pages.zip(expected.iter()) {
+        let page = page?;
+        assert_eq!(
+            page.zstd_dictionary().map(|x| x.as_ref()),
+            Some(&dictionary)
+        );
+        match decompress(page, &mut buffer)? {
+            Page::Data(page) => assert_eq!(page.buffer(), expected),
+            Page::Dict(_) => unreachable!(),
+        }
+        num_pages += 1;
+    }
+    assert_eq!(num_pages, expected.len());
+
+    // a malformed dictionary errors when reading the first zstd page instead of failing to
+    // decompress
+    let mut column_chunk = column_metadata.column_chunk().clone();
+    for kv in column_chunk
+        .meta_data
+        .as_mut()
+        .unwrap()
+        .key_value_metadata
+        .as_mut()
+        .unwrap()
+    {
+        if kv.key == ZSTD_DICTIONARY_KEY {
+            kv.value = Some("not base64!".to_string());
+        }
+    }
+    let malformed = ColumnChunkMetaData::new(column_chunk, column_metadata.descriptor().clone());
+    assert!(parquet2::read::PageMetaData::from(&malformed)
+        .zstd_dictionary()
+        .is_err());
+    let mut pages = get_page_iterator(&malformed, &mut reader, None, vec![], usize::MAX)?;
+    assert!(matches!(
+        pages.next(),
+        Some(Err(Error::OutOfSpec(message))) if message.contains("base64")
+    ));
+    Ok(())
+}
+
+#[cfg(all(feature = "snappy", feature = "zstd"))]
+#[test]
+fn adaptive_compression() -> Result<()> {
+    let options = WriteOptions {
+        write_statistics: false,
+        version: Version::V1,
+    };
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+    let pages = (0..5)
+        .map(|_| {
+            let array = (0..1000).map(|i| Some(i % 10)).collect::<Vec<_>>();
+            array_to_page_v1(&array, &options, &schema.columns()[0].descriptor)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let candidates = vec![
+        CompressionOptions::Uncompressed,
+        CompressionOptions::Snappy,
+        CompressionOptions::Zstd(None),
+    ];
+    let reported = Arc::new(std::sync::Mutex::new(None));
+    let report = reported.clone();
+    let adaptive = AdaptiveCompression::new(candidates, 2, |trial| trial.compressed_size as f64)
+        .with_report(move |trials, selected| {
+            *report.lock().unwrap() = Some((trials.to_vec(), selected));
+        });
+
+    let mut compressor = Compressor::new(
+        DynIter::new(pages.into_iter().map(Ok)),
+        CompressionOptions::Uncompressed,
+        vec![],
+    )
+    .with_adaptive_compression(adaptive);
+    assert!(compressor.trials().is_empty());
+
+    let mut num_pages = 0;
+    while let Some(page) = compressor.next()? {
+        match page {
+            CompressedPage::Data(page) => assert_eq!(page.compression(), Compression::Zstd),
+            CompressedPage::Dict(_) => unreachable!(),
+        }
+        num_pages += 1;
+    }
+    assert_eq!(num_pages, 5);
+
+    let trials = compressor.trials();
+    assert_eq!(trials.len(), 3);
+    assert_eq!(trials[0].compressed_size, trials[0].uncompressed_size);
+    assert!(trials
+        .iter()
+        .all(|trial| trial.compressed_size <= trials[0].compressed_size));
+    assert!(trials[2].ratio() > trials[1].ratio());
+    assert_eq!(compressor.compression(), CompressionOptions::Zstd(None));
+
+    let (reported_trials, selected) = reported.lock().unwrap().take().unwrap();
+    assert_eq!(reported_trials, trials);
+    assert_eq!(selected, CompressionOptions::Zstd(None));
+    Ok(())
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn parallel_compressor() -> Result<()> {
+    let options = WriteOptions {
+        write_statistics: false,
+        version: Version::V1,
+    };
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int64,
+        )],
+    );
+    let pages = (0..20)
+        .map(|page| {
+            let array = (0..1000)
+                .map(|i| (i % 5 != 0).then_some(page * 1000 + i))
+                .collect::<Vec<_>>();
+            array_to_page_v1(&array, &options, &schema.columns()[0].descriptor)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let expected = pages
+        .iter()
+        .map(|page| match page {
+            Page::Data(page) => page.buffer().to_vec(),
+            Page::Dict(_) => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+
+    let compressor = ParallelCompressor::new(
+        DynIter::new(pages.into_iter().map(Ok)),
+        CompressionOptions::Zstd(None),
+        CompressionPool::new(4),
+    );
+    let columns = std::iter::once(Ok(DynStreamingIterator::new(compressor)));
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.write(DynIter::new(columns))?;
+    writer.end(None)?;
+    let mut reader = Cursor::new(writer.into_inner().into_inner());
+
+    let metadata = read_metadata(&mut reader)?;
+    let column_metadata = &metadata.row_groups[0].columns()[0];
+    assert_eq!(column_metadata.compression(), Compression::Zstd);
+    assert_eq!(column_metadata.num_values(), 20 * 1000);
+
+    let pages = get_page_iterator(column_metadata, &mut reader, None, vec![], usize::MAX)?;
+    let mut pages = Decompressor::new(pages, vec![]);
+    let mut num_pages = 0;
+    while let Some(page) = pages.next()? {
+        match page {
+            Page::Data(page) => assert_eq!(page.buffer(), expected[num_pages]),
+            Page::Dict(_) => unreachable!(),
+        }
+        num_pages += 1;
+    }
+    assert_eq!(num_pages, expected.len());
+    Ok(())
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn parallel_column_compressor() -> Result<()> {
+    let options = WriteOptions {
+        write_statistics: false,
+        version: Version::V1,
+    };
+    let fields = (0..4)
+        .map(|column| ParquetType::from_physical(format!("col{}", column), PhysicalType::Int64))
+        .collect();
+    let schema = SchemaDescriptor::new("schema".to_string(), fields);
+    let columns = schema
+        .columns()
+        .iter()
+        .enumerate()
+        .map(|(column, descriptor)| {
+            (0..3)
+                .map(|page| {
+                    let array = (0..1000)
+                        .map(|i| Some(column as i64 * 10000 + page * 1000 + i))
+                        .collect::<Vec<_>>();
+                    array_to_page_v1(&array, &options, &descriptor.descriptor)
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let expected = columns
+        .iter()
+        .map(|pages| {
+            pages
+                .iter()
+                .map(|page| match page {
+                    Page::Data(page) => page.buffer().to_vec(),
+                    Page::Dict(_) => unreachable!(),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // every column chunk of the row group is compressed concurrently
+    let compressor = ParallelColumnCompressor::new(
+        columns
+            .into_iter()
+            .map(|pages| Ok(pages.into_iter().map(Ok))),
+        CompressionOptions::Zstd(None),
+        CompressionPool::new(4),
+    );
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.write(DynIter::new(compressor))?;
+    writer.end(None)?;
+    let mut reader = Cursor::new(writer.into_inner().into_inner());
+
+    let metadata = read_metadata(&mut reader)?;
+    let row_group = &metadata.row_groups[0];
+    assert_eq!(row_group.num_rows(), 3000);
+    for (column_metadata, expected) in row_group.columns().iter().zip(expected) {
+        assert_eq!(column_metadata.compression(), Compression::Zstd);
+        let pages = get_page_iterator(column_metadata, &mut reader, None, vec![], usize::MAX)?;
+        let mut pages = Decompressor::new(pages, vec![]);
+        let mut num_pages = 0;
+        while let Some(page) = pages.next()? {
+            match page {
+                Page::Data(page) => assert_eq!(page.buffer(), expected[num_pages]),
+                Page::Dict(_) => unreachable!(),
+            }
+            num_pages += 1;
+        }
+        assert_eq!(num_pages, expected.len());
+    }
+    Ok(())
+}
+
+/// Returns a V2 page of required, PLAIN-encoded `values`
+#[cfg(feature = "snappy")]
+fn page_v2(values: &[f64], descriptor: &Descriptor) -> Page {
+    let buffer = values
+        .iter()
+        .flat_map(|x| x.to_le_bytes())
+        .collect::<Vec<_>>();
+    let header = DataPageHeader::V2(DataPageHeaderV2 {
+        num_values: values.len() as i32,
+        num_nulls: 0,
+        num_rows: values.len() as i32,
+        encoding: parquet2::encoding::Encoding::Plain.into(),
+        definition_levels_byte_length: 0,
+        repetition_levels_byte_length: 0,
+        is_compressed: Some(true),
+        statistics: None,
+    });
+    Page::Data(DataPage::new(
+        header,
+        buffer,
+        descriptor.clone(),
+        Some(values.len()),
+    ))
+}
+
+#[cfg(feature = "snappy")]
+#[test]
+fn uncompressed_fallback() -> Result<()> {
+    let options = WriteOptions {
+        write_statistics: false,
+        version: Version::V2,
+    };
+    let mut primitive_type = parquet2::schema::types::PrimitiveType::from_physical(
+        "col".to_string(),
+        PhysicalType::Double,
+    );
+    primitive_type.field_info.repetition = parquet2::schema::Repetition::Required;
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::PrimitiveType(primitive_type)],
+    );
+    let descriptor = &schema.columns()[0].descriptor;
+
+    let mut state = 1u64;
+    let random = (0..1000)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            f64::from_bits(state)
+        })
+        .collect::<Vec<_>>();
+    let repeated = vec![1.5; 1000];
+    let pages = vec![page_v2(&random, descriptor), page_v2(&repeated, descriptor)];
+    let expected = pages
+        .iter()
+        .map(|page| match page {
+            Page::Data(page) => page.buffer().to_vec(),
+            Page::Dict(_) => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+
+    let compressor = Compressor::new(
+        DynIter::new(pages.into_iter().map(Ok)),
+        CompressionOptions::Snappy,
+        vec![],
+    )
+    .with_uncompressed_fallback(true);
+    let columns = std::iter::once(Ok(DynStreamingIterator::new(compressor)));
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.write(DynIter::new(columns))?;
+    writer.end(None)?;
+    let mut reader = Cursor::new(writer.into_inner().into_inner());
+
+    let metadata = read_metadata(&mut reader)?;
+    let column_metadata = &metadata.row_groups[0].columns()[0];
+    assert_eq!(column_metadata.compression(), Compression::Snappy);
+
+    let pages = get_page_iterator(column_metadata, &mut reader, None, vec![], usize::MAX)?;
+    let mut buffer = vec![];
+    let mut is_compressed = vec![];
+    for (page, expected) in pages.zip(expected.iter()) {
+        let page = page?;
+        match &page {
+            CompressedPage::Data(page) => is_compressed.push(page.is_compressed()),
+            CompressedPage::Dict(_) => unreachable!(),
+        }
+        match decompress(page, &mut buffer)? {
+            Page::Data(page) => assert_eq!(page.buffer(), expected),
+            Page::Dict(_) => unreachable!(),
+        }
+    }
+    // only the random values are stored uncompressed
+    assert_eq!(is_compressed, vec![false, true]);
+    Ok(())
+}
+
 #[cfg(feature = "async")]
 async fn test_column_async(column: &str, compression: CompressionOptions) -> Result<()> {
     let array = alltypes_plain(column);